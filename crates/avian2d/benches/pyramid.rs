@@ -6,8 +6,12 @@ use benches_common_2d::bench_app;
 use bevy::prelude::*;
 use criterion::{criterion_group, criterion_main, Criterion};
 
-fn setup(app: &mut App, base_count: u32) {
+fn setup(app: &mut App, base_count: u32, method: BroadPhaseMethod) {
     app.insert_resource(SubstepCount(8));
+    app.insert_resource(BroadPhaseConfig {
+        method,
+        ..default()
+    });
     app.add_systems(Startup, move |mut commands: Commands| {
         // Ground
         commands.spawn((
@@ -38,21 +42,17 @@ fn setup(app: &mut App, base_count: u32) {
 }
 
 fn criterion_benchmark(c: &mut Criterion) {
-    c.bench_function("pyramid with base of 20 boxes, 5 steps", |b| {
-        bench_app(b, 5, |app| setup(app, 20))
-    });
-
-    c.bench_function("pyramid with base of 40 boxes, 5 steps", |b| {
-        bench_app(b, 5, |app| setup(app, 40))
-    });
-
-    c.bench_function("pyramid with base of 60 boxes, 5 steps", |b| {
-        bench_app(b, 5, |app| setup(app, 60))
-    });
-
-    c.bench_function("pyramid with base of 80 boxes, 5 steps", |b| {
-        bench_app(b, 5, |app| setup(app, 80))
-    });
+    for (method, name) in [
+        (BroadPhaseMethod::SweepAndPrune, "sweep and prune"),
+        (BroadPhaseMethod::DynamicAabbTree, "dynamic AABB tree"),
+    ] {
+        for base_count in [20, 40, 60, 80] {
+            c.bench_function(
+                &format!("pyramid with base of {base_count} boxes, 5 steps, {name}"),
+                |b| bench_app(b, 5, |app| setup(app, base_count, method)),
+            );
+        }
+    }
 }
 
 criterion_group!(
@@ -1,11 +1,11 @@
 //! A 2D platformer example with one-way platforms to demonstrate
-//! filtering collisions with systems in the `PostProcessCollisions` schedule.
+//! filtering collisions with `CollisionHooks`.
 //!
 //! Move with arrow keys, jump with Space and descend through
 //! platforms by pressing Space while holding the down arrow.
 
 use avian2d::{math::*, prelude::*};
-use bevy::{prelude::*, sprite::MaterialMesh2dBundle, utils::HashSet};
+use bevy::{ecs::system::SystemParam, prelude::*, sprite::MaterialMesh2dBundle, utils::HashSet};
 use examples_common_2d::ExampleCommonPlugin;
 
 fn main() {
@@ -15,13 +15,15 @@ fn main() {
             ExampleCommonPlugin,
             // Add physics plugins and specify a units-per-meter scaling factor, 1 meter = 20 pixels.
             // The unit allows the engine to tune its parameters for the scale of the world, improving stability.
-            PhysicsPlugins::default().with_length_unit(20.0),
+            // The collision hooks are used for filtering collisions with one-way platforms.
+            PhysicsPlugins::default()
+                .with_length_unit(20.0)
+                .with_collision_hooks::<PlatformerCollisionHooks>(),
         ))
         .insert_resource(ClearColor(Color::srgb(0.05, 0.05, 0.1)))
         .insert_resource(Gravity(Vector::NEG_Y * 1000.0))
         .add_systems(Startup, setup)
         .add_systems(Update, (movement, pass_through_one_way_platform))
-        .add_systems(PostProcessCollisions, one_way_platform)
         .run();
 }
 
@@ -126,6 +128,8 @@ fn setup(
             RigidBody::Static,
             Collider::rectangle(50.0, 50.0),
             OneWayPlatform::default(),
+            // Call the collision hooks for contacts with this platform.
+            ActiveCollisionHooks::MODIFY_CONTACTS,
         ));
     }
 
@@ -194,18 +198,19 @@ fn pass_through_one_way_platform(
 
 /// Allows entities to pass through [`OneWayPlatform`] entities.
 ///
-/// Passing through is achieved by removing the collisions between the [`OneWayPlatform`]
-/// and the other entity if the entity should pass through.
+/// Passing through is achieved by discarding the contacts between the [`OneWayPlatform`]
+/// and the other entity in [`CollisionHooks::modify_contacts`] if the entity should pass through.
 /// If a [`PassThroughOneWayPlatform`] is present on the non-platform entity,
 /// the value of the component dictates the pass-through behaviour.
 ///
 /// Entities known to be passing through each [`OneWayPlatform`] are stored in the
-/// [`OneWayPlatform`]. If an entity is known to be passing through a [`OneWayPlatform`],
+/// [`OneWayPlatform`]. Because collision hooks only have read-only access to the world,
+/// the set is updated using commands. If an entity is known to be passing through a [`OneWayPlatform`],
 /// it is allowed to continue to do so, even if [`PassThroughOneWayPlatform`] has been
 /// set to disallow passing through.
 ///
 /// > Note that this is a very simplistic implementation of one-way
-/// > platforms to demonstrate filtering collisions via [`PostProcessCollisions`].
+/// > platforms to demonstrate filtering collisions via [`CollisionHooks`].
 /// > You will probably want something more robust to implement one-way
 /// > platforms properly, or may elect to use a sensor collider for your entities instead,
 /// > which means you won't need to filter collisions at all.
@@ -243,17 +248,20 @@ fn pass_through_one_way_platform(
 /// Even if an entity is changed to [`PassThroughOneWayPlatform::Never`], it will be allowed to pass
 /// through a [`OneWayPlatform`] if it is already penetrating the platform. Once it exits the platform,
 /// it will no longer be allowed to pass through.
-fn one_way_platform(
-    mut one_way_platforms_query: Query<&mut OneWayPlatform>,
+#[derive(SystemParam)]
+struct PlatformerCollisionHooks<'w, 's> {
+    one_way_platforms_query: Query<'w, 's, &'static OneWayPlatform>,
+    // NOTE: This precludes OneWayPlatform passing through a OneWayPlatform
     other_colliders_query: Query<
-        Option<&PassThroughOneWayPlatform>,
-        (With<Collider>, Without<OneWayPlatform>), // NOTE: This precludes OneWayPlatform passing through a OneWayPlatform
+        'w,
+        's,
+        Option<&'static PassThroughOneWayPlatform>,
+        (With<Collider>, Without<OneWayPlatform>),
     >,
-    mut collisions: ResMut<Collisions>,
-) {
-    // This assumes that Collisions contains empty entries for entities
-    // that were once colliding but no longer are.
-    collisions.retain(|contacts| {
+}
+
+impl CollisionHooks for PlatformerCollisionHooks<'_, '_> {
+    fn modify_contacts(&self, contacts: &mut Contacts, commands: &mut Commands) -> bool {
         // Differentiate between which normal of the manifold we should use
         enum RelevantNormal {
             Normal1,
@@ -262,11 +270,22 @@ fn one_way_platform(
 
         // First, figure out which entity is the one-way platform, and which is the other.
         // Choose the appropriate normal for pass-through depending on which is which.
-        let (mut one_way_platform, other_entity, relevant_normal) =
-            if let Ok(one_way_platform) = one_way_platforms_query.get_mut(contacts.entity1) {
-                (one_way_platform, contacts.entity2, RelevantNormal::Normal1)
-            } else if let Ok(one_way_platform) = one_way_platforms_query.get_mut(contacts.entity2) {
-                (one_way_platform, contacts.entity1, RelevantNormal::Normal2)
+        let (platform_entity, one_way_platform, other_entity, relevant_normal) =
+            if let Ok(one_way_platform) = self.one_way_platforms_query.get(contacts.entity1) {
+                (
+                    contacts.entity1,
+                    one_way_platform,
+                    contacts.entity2,
+                    RelevantNormal::Normal1,
+                )
+            } else if let Ok(one_way_platform) = self.one_way_platforms_query.get(contacts.entity2)
+            {
+                (
+                    contacts.entity2,
+                    one_way_platform,
+                    contacts.entity1,
+                    RelevantNormal::Normal2,
+                )
             } else {
                 // Neither is a one-way-platform, so accept the collision:
                 // we're done here.
@@ -287,17 +306,30 @@ fn one_way_platform(
                 return false;
             } else {
                 // If it's no longer penetrating us, forget it.
-                one_way_platform.0.remove(&other_entity);
+                commands.add(move |world: &mut World| {
+                    if let Some(mut platform) = world.get_mut::<OneWayPlatform>(platform_entity) {
+                        platform.0.remove(&other_entity);
+                    }
+                });
             }
         }
 
-        match other_colliders_query.get(other_entity) {
+        // Registers the other entity as one that's currently penetrating the platform.
+        let mut allow_pass_through = || {
+            commands.add(move |world: &mut World| {
+                if let Some(mut platform) = world.get_mut::<OneWayPlatform>(platform_entity) {
+                    platform.0.insert(other_entity);
+                }
+            });
+        };
+
+        match self.other_colliders_query.get(other_entity) {
             // Pass-through is set to never, so accept the collision.
             Ok(Some(PassThroughOneWayPlatform::Never)) => true,
             // Pass-through is set to always, so always ignore this collision
             // and register it as an entity that's currently penetrating.
             Ok(Some(PassThroughOneWayPlatform::Always)) => {
-                one_way_platform.0.insert(other_entity);
+                allow_pass_through();
                 false
             }
             // Default behaviour is "by normal".
@@ -316,10 +348,10 @@ fn one_way_platform(
                 } else {
                     // Otherwise, ignore the collision and register
                     // the other entity as one that's currently penetrating.
-                    one_way_platform.0.insert(other_entity);
+                    allow_pass_through();
                     false
                 }
             }
         }
-    });
+    }
 }
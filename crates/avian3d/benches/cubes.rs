@@ -6,8 +6,15 @@ use benches_common_3d::bench_app;
 use bevy::prelude::*;
 use criterion::{criterion_group, criterion_main, Criterion};
 
-fn setup_cubes(app: &mut App, size: u32) {
+fn setup_cubes(app: &mut App, size: u32, method: BroadPhaseMethod) {
+    // Required by collider constructors with the `collider-from-mesh` and `bevy_scene` features.
+    app.add_plugins((AssetPlugin::default(), bevy::scene::ScenePlugin))
+        .init_resource::<Assets<Mesh>>();
     app.insert_resource(SubstepCount(8));
+    app.insert_resource(BroadPhaseConfig {
+        method,
+        ..default()
+    });
     app.add_systems(Startup, move |mut commands: Commands| {
         commands.spawn((
             RigidBody::Static,
@@ -28,17 +35,16 @@ fn setup_cubes(app: &mut App, size: u32) {
 }
 
 fn criterion_benchmark(c: &mut Criterion) {
-    c.bench_function("cubes 3x3, 30 steps", |b| {
-        bench_app(b, 30, |app| setup_cubes(app, 3))
-    });
-
-    c.bench_function("cubes 5x5, 30 steps", |b| {
-        bench_app(b, 30, |app| setup_cubes(app, 5))
-    });
-
-    c.bench_function("cubes 10x10, 30 steps", |b| {
-        bench_app(b, 30, |app| setup_cubes(app, 10))
-    });
+    for (method, name) in [
+        (BroadPhaseMethod::SweepAndPrune, "sweep and prune"),
+        (BroadPhaseMethod::DynamicAabbTree, "dynamic AABB tree"),
+    ] {
+        for size in [3, 5, 10] {
+            c.bench_function(&format!("cubes {size}x{size}, 30 steps, {name}"), |b| {
+                bench_app(b, 30, |app| setup_cubes(app, size, method))
+            });
+        }
+    }
 }
 
 criterion_group!(
@@ -0,0 +1,779 @@
+//! A dynamic bounding volume hierarchy of [`ColliderAabb`]s that is updated incrementally.
+//!
+//! See [`DynamicAabbTree`].
+
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+use crate::prelude::*;
+
+/// The index of a node that doesn't exist.
+const NULL_NODE: u32 = u32::MAX;
+
+/// A stable identifier for a leaf, or *proxy*, in a [`DynamicAabbTree`].
+///
+/// The identifier stays the same until the proxy is removed from the tree,
+/// even if the proxy is moved or the tree is rebalanced.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ProxyId(u32);
+
+impl ProxyId {
+    /// Returns the identifier as an index.
+    #[inline(always)]
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+#[derive(Clone, Debug)]
+struct TreeNode<T> {
+    /// The enlarged AABB of a leaf, or the AABB enclosing both children of an internal node.
+    aabb: ColliderAabb,
+    /// The parent of a node in the tree, or the next free node for nodes in the free list.
+    parent_or_next: u32,
+    child1: u32,
+    child2: u32,
+    /// The height of the subtree rooted at this node. Leaves have a height of `0`,
+    /// and free nodes have a height of `-1`.
+    height: i32,
+    /// The data associated with a leaf.
+    data: Option<T>,
+}
+
+impl<T> TreeNode<T> {
+    #[inline(always)]
+    fn is_leaf(&self) -> bool {
+        self.child1 == NULL_NODE
+    }
+}
+
+/// A dynamic bounding volume hierarchy (BVH) of [`ColliderAabb`]s, also known as a dynamic AABB tree.
+///
+/// Each leaf, or *proxy*, stores an AABB and some associated data. Internal nodes store an AABB
+/// that encloses the AABBs of their children, which allows quickly discarding large parts of
+/// the tree when performing [AABB queries](Self::query_aabb).
+///
+/// Unlike a tree that is rebuilt from scratch, the dynamic tree is updated *incrementally*:
+///
+/// - Leaves store *fattened* AABBs that are enlarged by a margin. As long as a proxy stays
+///   within its fattened AABB, [moving](Self::move_proxy) it doesn't modify the tree at all.
+/// - When a proxy leaves its fattened AABB, it is either *refit* in place if its parent still encloses
+///   the new AABB, or removed and reinserted at the best position according to a surface area heuristic.
+/// - Insertions and removals perform tree rotations to keep the tree balanced, and the tree can be
+///   further optimized over multiple steps using [`rebalance`](Self::rebalance).
+///
+/// The implementation is based on the dynamic tree used in [Box2D](https://github.com/erincatto/box2d).
+#[derive(Clone, Debug)]
+pub struct DynamicAabbTree<T> {
+    nodes: Vec<TreeNode<T>>,
+    root: u32,
+    free_list: u32,
+    proxy_count: usize,
+    /// The node from which the next [`rebalance`](Self::rebalance) call continues.
+    rebalance_cursor: u32,
+}
+
+impl<T> Default for DynamicAabbTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> DynamicAabbTree<T> {
+    /// Creates an empty [`DynamicAabbTree`].
+    pub const fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            root: NULL_NODE,
+            free_list: NULL_NODE,
+            proxy_count: 0,
+            rebalance_cursor: 0,
+        }
+    }
+
+    /// Returns the number of proxies in the tree.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.proxy_count
+    }
+
+    /// Returns `true` if the tree contains no proxies.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.proxy_count == 0
+    }
+
+    /// Returns the height of the tree, or `0` if the tree is empty.
+    pub fn height(&self) -> u32 {
+        if self.root == NULL_NODE {
+            0
+        } else {
+            self.nodes[self.root as usize].height as u32
+        }
+    }
+
+    /// Returns the AABB enclosing all proxies in the tree, or `None` if the tree is empty.
+    pub fn root_aabb(&self) -> Option<ColliderAabb> {
+        (self.root != NULL_NODE).then(|| self.nodes[self.root as usize].aabb)
+    }
+
+    /// Removes all proxies from the tree.
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.root = NULL_NODE;
+        self.free_list = NULL_NODE;
+        self.proxy_count = 0;
+        self.rebalance_cursor = 0;
+    }
+
+    /// Returns the fattened AABB stored for the given proxy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the proxy is not in the tree.
+    #[inline]
+    pub fn fat_aabb(&self, proxy: ProxyId) -> ColliderAabb {
+        let node = &self.nodes[proxy.index()];
+        assert!(node.is_leaf() && node.height == 0, "invalid proxy");
+        node.aabb
+    }
+
+    /// Returns a reference to the data of the given proxy, or `None` if the proxy is not in the tree.
+    #[inline]
+    pub fn get(&self, proxy: ProxyId) -> Option<&T> {
+        self.nodes.get(proxy.index())?.data.as_ref()
+    }
+
+    /// Returns a mutable reference to the data of the given proxy, or `None` if the proxy is not in the tree.
+    #[inline]
+    pub fn get_mut(&mut self, proxy: ProxyId) -> Option<&mut T> {
+        self.nodes.get_mut(proxy.index())?.data.as_mut()
+    }
+
+    /// Returns an iterator over all proxies in the tree and their data.
+    pub fn iter(&self) -> impl Iterator<Item = (ProxyId, &T)> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter_map(|(i, node)| node.data.as_ref().map(|data| (ProxyId(i as u32), data)))
+    }
+
+    /// Inserts a proxy with the given AABB and data into the tree, and returns its identifier.
+    ///
+    /// The AABB is enlarged by `margin` in each direction.
+    pub fn insert(&mut self, aabb: ColliderAabb, margin: Scalar, data: T) -> ProxyId {
+        let leaf = self.allocate_node();
+        let node = &mut self.nodes[leaf as usize];
+        node.aabb = aabb.grow(Vector::splat(margin));
+        node.height = 0;
+        node.data = Some(data);
+
+        self.insert_leaf(leaf);
+        self.proxy_count += 1;
+
+        ProxyId(leaf)
+    }
+
+    /// Removes the given proxy from the tree and returns its data,
+    /// or `None` if the proxy is not in the tree.
+    pub fn remove(&mut self, proxy: ProxyId) -> Option<T> {
+        let node = self.nodes.get_mut(proxy.index())?;
+        let data = node.data.take()?;
+
+        self.remove_leaf(proxy.0);
+        self.free_node(proxy.0);
+        self.proxy_count -= 1;
+
+        Some(data)
+    }
+
+    /// Updates the AABB of the given proxy.
+    ///
+    /// If the AABB is still contained within the proxy's fattened AABB, the tree is not modified.
+    /// Otherwise, a new fattened AABB is computed using the given `margin`, and the proxy is either
+    /// refit in place or reinserted into the tree.
+    ///
+    /// Returns `true` if the fattened AABB was changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the proxy is not in the tree.
+    pub fn move_proxy(&mut self, proxy: ProxyId, aabb: ColliderAabb, margin: Scalar) -> bool {
+        let leaf = proxy.0;
+        let fat_aabb = self.fat_aabb(proxy);
+
+        if contains(&fat_aabb, &aabb) {
+            return false;
+        }
+
+        let new_fat_aabb = aabb.grow(Vector::splat(margin));
+        let parent = self.nodes[leaf as usize].parent_or_next;
+
+        // Refit: if the parent still encloses the new AABB, the ancestors remain valid bounds,
+        // so the leaf can be updated in place without restructuring the tree.
+        if parent != NULL_NODE && contains(&self.nodes[parent as usize].aabb, &new_fat_aabb) {
+            self.nodes[leaf as usize].aabb = new_fat_aabb;
+            return true;
+        }
+
+        self.remove_leaf(leaf);
+        self.nodes[leaf as usize].aabb = new_fat_aabb;
+        self.insert_leaf(leaf);
+
+        true
+    }
+
+    /// Incrementally improves the quality of the tree by reinserting up to `iterations` proxies.
+    ///
+    /// Proxies that have been refit in place or moved a lot can leave internal nodes larger than
+    /// necessary. Reinserting them tightens the bounds and reduces the cost of queries.
+    /// Subsequent calls continue from where the previous call left off.
+    pub fn rebalance(&mut self, iterations: usize) {
+        if self.proxy_count < 2 {
+            return;
+        }
+
+        let node_count = self.nodes.len() as u32;
+        let mut reinserted = 0;
+        let mut visited = 0;
+
+        while reinserted < iterations.min(self.proxy_count) && visited < node_count {
+            if self.rebalance_cursor >= node_count {
+                self.rebalance_cursor = 0;
+            }
+
+            let index = self.rebalance_cursor;
+            self.rebalance_cursor += 1;
+            visited += 1;
+
+            let node = &self.nodes[index as usize];
+            if node.height != 0 {
+                continue;
+            }
+
+            self.remove_leaf(index);
+            self.insert_leaf(index);
+            reinserted += 1;
+        }
+    }
+
+    /// Calls the given `callback` for each proxy whose fattened AABB intersects `aabb`.
+    ///
+    /// The traversal is stopped if the callback returns `false`.
+    pub fn query_aabb(&self, aabb: ColliderAabb, mut callback: impl FnMut(ProxyId, &T) -> bool) {
+        self.query(
+            |node_aabb| node_aabb.intersects(&aabb),
+            |proxy, data| callback(proxy, data),
+        );
+    }
+
+    /// Traverses the tree, visiting the subtrees whose AABBs satisfy `predicate`,
+    /// and calls `callback` for each leaf that satisfies it.
+    ///
+    /// This can be used to implement custom queries like raycasts.
+    ///
+    /// The traversal is stopped if the callback returns `false`.
+    pub fn query(
+        &self,
+        mut predicate: impl FnMut(&ColliderAabb) -> bool,
+        mut callback: impl FnMut(ProxyId, &T) -> bool,
+    ) {
+        if self.root == NULL_NODE {
+            return;
+        }
+
+        let mut stack = Vec::with_capacity(64);
+        stack.push(self.root);
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index as usize];
+
+            if !predicate(&node.aabb) {
+                continue;
+            }
+
+            if node.is_leaf() {
+                let data = node.data.as_ref().expect("leaf has no data");
+                if !callback(ProxyId(index), data) {
+                    return;
+                }
+            } else {
+                stack.push(node.child1);
+                stack.push(node.child2);
+            }
+        }
+    }
+
+    /// Traverses the tree in the order of increasing cost, and returns the leaf result
+    /// with the smallest cost, or `None` if no leaf produced a result.
+    ///
+    /// - `node_cost` returns a lower bound for the cost of any leaf in a subtree with the given AABB,
+    ///   or `None` if the subtree should be skipped.
+    /// - `leaf_cost` computes the cost and result for a leaf. It is also given the current best cost,
+    ///   which can be used for early termination.
+    ///
+    /// This can be used to implement custom closest-hit queries like raycasts and shapecasts.
+    pub fn traverse_best_first<R>(
+        &self,
+        mut node_cost: impl FnMut(&ColliderAabb) -> Option<Scalar>,
+        mut leaf_cost: impl FnMut(ProxyId, &T, Scalar) -> Option<(Scalar, R)>,
+    ) -> Option<(Scalar, R)> {
+        if self.root == NULL_NODE {
+            return None;
+        }
+
+        let mut best_cost = Scalar::MAX;
+        let mut best = None;
+
+        let mut queue = BinaryHeap::with_capacity(64);
+        if let Some(cost) = node_cost(&self.nodes[self.root as usize].aabb) {
+            queue.push(WeightedNode {
+                cost,
+                index: self.root,
+            });
+        }
+
+        while let Some(WeightedNode { cost, index }) = queue.pop() {
+            // The remaining nodes can't contain a better result.
+            if cost >= best_cost {
+                break;
+            }
+
+            let node = &self.nodes[index as usize];
+
+            if node.is_leaf() {
+                let data = node.data.as_ref().expect("leaf has no data");
+                if let Some((cost, result)) = leaf_cost(ProxyId(index), data, best_cost) {
+                    if cost < best_cost {
+                        best_cost = cost;
+                        best = Some((cost, result));
+                    }
+                }
+            } else {
+                for child in [node.child1, node.child2] {
+                    if let Some(cost) = node_cost(&self.nodes[child as usize].aabb) {
+                        if cost < best_cost {
+                            queue.push(WeightedNode { cost, index: child });
+                        }
+                    }
+                }
+            }
+        }
+
+        best
+    }
+
+    fn allocate_node(&mut self) -> u32 {
+        if self.free_list == NULL_NODE {
+            self.nodes.push(TreeNode {
+                aabb: ColliderAabb::default(),
+                parent_or_next: NULL_NODE,
+                child1: NULL_NODE,
+                child2: NULL_NODE,
+                height: 0,
+                data: None,
+            });
+            return self.nodes.len() as u32 - 1;
+        }
+
+        let index = self.free_list;
+        let node = &mut self.nodes[index as usize];
+        self.free_list = node.parent_or_next;
+        node.parent_or_next = NULL_NODE;
+        node.child1 = NULL_NODE;
+        node.child2 = NULL_NODE;
+        node.height = 0;
+        index
+    }
+
+    fn free_node(&mut self, index: u32) {
+        let node = &mut self.nodes[index as usize];
+        node.parent_or_next = self.free_list;
+        node.child1 = NULL_NODE;
+        node.child2 = NULL_NODE;
+        node.height = -1;
+        node.data = None;
+        self.free_list = index;
+    }
+
+    fn insert_leaf(&mut self, leaf: u32) {
+        if self.root == NULL_NODE {
+            self.root = leaf;
+            self.nodes[leaf as usize].parent_or_next = NULL_NODE;
+            return;
+        }
+
+        // Find the best sibling for the leaf using the surface area heuristic.
+        let leaf_aabb = self.nodes[leaf as usize].aabb;
+        let mut index = self.root;
+
+        while !self.nodes[index as usize].is_leaf() {
+            let node = &self.nodes[index as usize];
+            let child1 = node.child1;
+            let child2 = node.child2;
+
+            let area = cost(&node.aabb);
+            let combined_area = cost(&node.aabb.merged(leaf_aabb));
+
+            // The cost of creating a new parent for this node and the new leaf.
+            let cost_here = 2.0 * combined_area;
+
+            // The minimum cost of pushing the leaf further down the tree.
+            let inheritance_cost = 2.0 * (combined_area - area);
+
+            let child_cost = |child: u32| {
+                let child_node = &self.nodes[child as usize];
+                let merged = cost(&leaf_aabb.merged(child_node.aabb));
+                if child_node.is_leaf() {
+                    merged + inheritance_cost
+                } else {
+                    merged - cost(&child_node.aabb) + inheritance_cost
+                }
+            };
+
+            let cost1 = child_cost(child1);
+            let cost2 = child_cost(child2);
+
+            if cost_here < cost1 && cost_here < cost2 {
+                break;
+            }
+
+            index = if cost1 < cost2 { child1 } else { child2 };
+        }
+
+        let sibling = index;
+
+        // Create a new parent for the sibling and the leaf.
+        let old_parent = self.nodes[sibling as usize].parent_or_next;
+        let new_parent = self.allocate_node();
+        {
+            let sibling_node = &self.nodes[sibling as usize];
+            let aabb = leaf_aabb.merged(sibling_node.aabb);
+            let height = sibling_node.height + 1;
+            let node = &mut self.nodes[new_parent as usize];
+            node.parent_or_next = old_parent;
+            node.aabb = aabb;
+            node.height = height;
+            node.data = None;
+            node.child1 = sibling;
+            node.child2 = leaf;
+        }
+        self.nodes[sibling as usize].parent_or_next = new_parent;
+        self.nodes[leaf as usize].parent_or_next = new_parent;
+
+        if old_parent == NULL_NODE {
+            // The sibling was the root.
+            self.root = new_parent;
+        } else if self.nodes[old_parent as usize].child1 == sibling {
+            self.nodes[old_parent as usize].child1 = new_parent;
+        } else {
+            self.nodes[old_parent as usize].child2 = new_parent;
+        }
+
+        // Walk back up the tree, fixing heights and AABBs.
+        self.refit_ancestors(new_parent);
+    }
+
+    fn remove_leaf(&mut self, leaf: u32) {
+        if leaf == self.root {
+            self.root = NULL_NODE;
+            return;
+        }
+
+        let parent = self.nodes[leaf as usize].parent_or_next;
+        let grandparent = self.nodes[parent as usize].parent_or_next;
+        let sibling = if self.nodes[parent as usize].child1 == leaf {
+            self.nodes[parent as usize].child2
+        } else {
+            self.nodes[parent as usize].child1
+        };
+
+        if grandparent == NULL_NODE {
+            self.root = sibling;
+            self.nodes[sibling as usize].parent_or_next = NULL_NODE;
+        } else {
+            // Destroy the parent and connect the sibling to the grandparent.
+            if self.nodes[grandparent as usize].child1 == parent {
+                self.nodes[grandparent as usize].child1 = sibling;
+            } else {
+                self.nodes[grandparent as usize].child2 = sibling;
+            }
+            self.nodes[sibling as usize].parent_or_next = grandparent;
+            self.refit_ancestors(grandparent);
+        }
+
+        self.free_node(parent);
+        self.nodes[leaf as usize].parent_or_next = NULL_NODE;
+    }
+
+    /// Walks up the tree from the given node, balancing it and recomputing heights and AABBs.
+    fn refit_ancestors(&mut self, mut index: u32) {
+        while index != NULL_NODE {
+            index = self.balance(index);
+
+            let node = &self.nodes[index as usize];
+            let child1 = &self.nodes[node.child1 as usize];
+            let child2 = &self.nodes[node.child2 as usize];
+            let height = 1 + child1.height.max(child2.height);
+            let aabb = child1.aabb.merged(child2.aabb);
+
+            let node = &mut self.nodes[index as usize];
+            node.height = height;
+            node.aabb = aabb;
+
+            index = node.parent_or_next;
+        }
+    }
+
+    /// Performs a left or right rotation if node `a` is imbalanced.
+    /// Returns the new root index of the subtree.
+    fn balance(&mut self, a: u32) -> u32 {
+        let node_a = &self.nodes[a as usize];
+        if node_a.is_leaf() || node_a.height < 2 {
+            return a;
+        }
+
+        let b = node_a.child1;
+        let c = node_a.child2;
+        let balance = self.nodes[c as usize].height - self.nodes[b as usize].height;
+
+        if balance > 1 {
+            // Rotate C up.
+            self.rotate_up(a, c, b)
+        } else if balance < -1 {
+            // Rotate B up.
+            self.rotate_up(a, b, c)
+        } else {
+            a
+        }
+    }
+
+    /// Rotates the taller child `up` of node `a` above it. `other` is the other child of `a`.
+    ///
+    /// ```text
+    ///       a             up
+    ///      / \           /  \
+    ///  other  up   ->   a    f/g
+    ///        /  \      / \
+    ///       f    g  other g/f
+    /// ```
+    fn rotate_up(&mut self, a: u32, up: u32, other: u32) -> u32 {
+        let f = self.nodes[up as usize].child1;
+        let g = self.nodes[up as usize].child2;
+        let a_parent = self.nodes[a as usize].parent_or_next;
+
+        // Swap A and the child.
+        self.nodes[up as usize].child1 = a;
+        self.nodes[up as usize].parent_or_next = a_parent;
+        self.nodes[a as usize].parent_or_next = up;
+
+        // A's old parent should point to the child.
+        if a_parent == NULL_NODE {
+            self.root = up;
+        } else if self.nodes[a_parent as usize].child1 == a {
+            self.nodes[a_parent as usize].child1 = up;
+        } else {
+            self.nodes[a_parent as usize].child2 = up;
+        }
+
+        // Keep the taller grandchild under the rotated node, and give the shorter one to A.
+        let (keep, give) = if self.nodes[f as usize].height > self.nodes[g as usize].height {
+            (f, g)
+        } else {
+            (g, f)
+        };
+
+        self.nodes[up as usize].child2 = keep;
+        if self.nodes[a as usize].child1 == up {
+            self.nodes[a as usize].child1 = give;
+        } else {
+            self.nodes[a as usize].child2 = give;
+        }
+        self.nodes[give as usize].parent_or_next = a;
+
+        let other_node = &self.nodes[other as usize];
+        let give_node = &self.nodes[give as usize];
+        let a_aabb = other_node.aabb.merged(give_node.aabb);
+        let a_height = 1 + other_node.height.max(give_node.height);
+        self.nodes[a as usize].aabb = a_aabb;
+        self.nodes[a as usize].height = a_height;
+
+        let keep_node = &self.nodes[keep as usize];
+        let up_aabb = a_aabb.merged(keep_node.aabb);
+        let up_height = 1 + a_height.max(keep_node.height);
+        self.nodes[up as usize].aabb = up_aabb;
+        self.nodes[up as usize].height = up_height;
+
+        up
+    }
+}
+
+/// A node in the priority queue of [`DynamicAabbTree::traverse_best_first`].
+/// Ordered such that the node with the smallest cost is popped first.
+#[derive(Clone, Copy, Debug)]
+struct WeightedNode {
+    cost: Scalar,
+    index: u32,
+}
+
+impl PartialEq for WeightedNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for WeightedNode {}
+
+impl PartialOrd for WeightedNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for WeightedNode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.total_cmp(&self.cost)
+    }
+}
+
+/// The cost of a node used by the surface area heuristic:
+/// the perimeter in 2D, and the surface area in 3D.
+#[inline(always)]
+fn cost(aabb: &ColliderAabb) -> Scalar {
+    let size = aabb.size();
+    #[cfg(feature = "2d")]
+    {
+        2.0 * (size.x + size.y)
+    }
+    #[cfg(feature = "3d")]
+    {
+        2.0 * (size.x * size.y + size.y * size.z + size.z * size.x)
+    }
+}
+
+/// Returns `true` if `outer` fully contains `inner`.
+#[inline(always)]
+fn contains(outer: &ColliderAabb, inner: &ColliderAabb) -> bool {
+    outer.min.cmple(inner.min).all() && outer.max.cmpge(inner.max).all()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    impl<T> DynamicAabbTree<T> {
+        /// Checks that the parent links, heights and AABBs of the tree are consistent.
+        fn validate(&self) {
+            if self.root == NULL_NODE {
+                return;
+            }
+            assert_eq!(self.nodes[self.root as usize].parent_or_next, NULL_NODE);
+
+            let mut leaves = 0;
+            let mut stack = vec![self.root];
+            while let Some(index) = stack.pop() {
+                let node = &self.nodes[index as usize];
+                if node.is_leaf() {
+                    assert_eq!(node.height, 0);
+                    assert!(node.data.is_some());
+                    leaves += 1;
+                    continue;
+                }
+
+                let child1 = &self.nodes[node.child1 as usize];
+                let child2 = &self.nodes[node.child2 as usize];
+                assert_eq!(child1.parent_or_next, index);
+                assert_eq!(child2.parent_or_next, index);
+                assert_eq!(node.height, 1 + child1.height.max(child2.height));
+                assert!(contains(&node.aabb, &child1.aabb));
+                assert!(contains(&node.aabb, &child2.aabb));
+                assert!((child1.height - child2.height).abs() <= 1);
+                stack.push(node.child1);
+                stack.push(node.child2);
+            }
+            assert_eq!(leaves, self.proxy_count);
+        }
+    }
+
+    fn aabb_at(x: Scalar) -> ColliderAabb {
+        ColliderAabb::new(Vector::X * x, Vector::splat(0.5))
+    }
+
+    #[test]
+    fn insert_move_remove() {
+        let mut tree = DynamicAabbTree::new();
+        let proxies: Vec<ProxyId> = (0..100)
+            .map(|i| tree.insert(aabb_at(i as Scalar), 0.1, i))
+            .collect();
+        tree.validate();
+        assert_eq!(tree.len(), 100);
+
+        // A balanced tree with 100 leaves should have a small height.
+        assert!(tree.height() <= 10);
+
+        // Small movements within the margin don't change the tree.
+        assert!(!tree.move_proxy(proxies[10], aabb_at(10.05), 0.1));
+
+        // Larger movements do.
+        for (i, proxy) in proxies.iter().enumerate() {
+            assert!(tree.move_proxy(*proxy, aabb_at(200.0 - i as Scalar), 0.1));
+        }
+        tree.validate();
+
+        tree.rebalance(50);
+        tree.validate();
+
+        for proxy in proxies.iter().step_by(2) {
+            assert!(tree.remove(*proxy).is_some());
+        }
+        tree.validate();
+        assert_eq!(tree.len(), 50);
+        assert!(tree.remove(proxies[0]).is_none());
+    }
+
+    #[test]
+    fn query_matches_brute_force() {
+        let mut tree = DynamicAabbTree::new();
+        let aabbs: Vec<ColliderAabb> = (0..200)
+            .map(|i| {
+                let x = ((i * 37) % 101) as Scalar;
+                ColliderAabb::new(Vector::X * x, Vector::splat(1.0 + (i % 3) as Scalar))
+            })
+            .collect();
+        for (i, aabb) in aabbs.iter().enumerate() {
+            tree.insert(*aabb, 0.0, i);
+        }
+
+        let query_aabb = ColliderAabb::new(Vector::X * 50.0, Vector::splat(5.0));
+        let mut found = vec![];
+        tree.query_aabb(query_aabb, |_, i| {
+            found.push(*i);
+            true
+        });
+        found.sort_unstable();
+
+        let expected: Vec<usize> = aabbs
+            .iter()
+            .enumerate()
+            .filter(|(_, aabb)| aabb.intersects(&query_aabb))
+            .map(|(i, _)| i)
+            .collect();
+
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn best_first_finds_closest() {
+        let mut tree = DynamicAabbTree::new();
+        for i in 0..100 {
+            tree.insert(aabb_at(i as Scalar), 0.1, i);
+        }
+
+        let point = 37.3;
+        let closest = tree.traverse_best_first(
+            |aabb| Some((aabb.min.x - point).max(point - aabb.max.x).max(0.0)),
+            |_, i, _| Some(((*i as Scalar - point).abs(), *i)),
+        );
+
+        assert_eq!(closest.map(|(_, i)| i), Some(37));
+    }
+}
@@ -0,0 +1,652 @@
+//! Collects pairs of potentially colliding entities into [`BroadCollisionPairs`] using
+//! [AABB](ColliderAabb) intersection checks.
+//!
+//! See [`BroadPhasePlugin`].
+
+pub mod dynamic_tree;
+
+use crate::prelude::*;
+use bevy::{
+    ecs::entity::{EntityHashMap, EntityHashSet, EntityMapper, MapEntities},
+    prelude::*,
+};
+use dynamic_tree::{DynamicAabbTree, ProxyId};
+
+/// Collects pairs of potentially colliding entities into [`BroadCollisionPairs`] using
+/// [AABB](ColliderAabb) intersection checks. This speeds up narrow phase collision detection,
+/// as the number of precise collision checks required is greatly reduced.
+///
+/// The algorithm used for finding the pairs can be selected with the [`BroadPhaseConfig`] resource:
+///
+/// - [`BroadPhaseMethod::SweepAndPrune`]: [Sweep and prune](https://en.wikipedia.org/wiki/Sweep_and_prune)
+///   along a single axis. This is very fast when objects are spread out along the sweep axis,
+///   but degrades when many objects overlap on that axis.
+/// - [`BroadPhaseMethod::DynamicAabbTree`]: A persistent [`DynamicAabbTree`] that is updated incrementally.
+///   This scales well regardless of how objects are distributed, which makes it a good choice for large open worlds.
+///
+/// The plugin also maintains the [`ColliderTree`], which is shared with [spatial queries](spatial_query)
+/// regardless of the method used for finding pairs.
+///
+/// The broad phase systems run in [`PhysicsStepSet::BroadPhase`].
+pub struct BroadPhasePlugin;
+
+impl Plugin for BroadPhasePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BroadCollisionPairs>()
+            .init_resource::<BroadPhaseConfig>()
+            .init_resource::<AabbIntervals>()
+            .init_resource::<ColliderTree>();
+
+        app.configure_sets(
+            PhysicsSchedule,
+            (
+                BroadPhaseSet::First,
+                BroadPhaseSet::UpdateStructures,
+                BroadPhaseSet::CollectCollisions,
+                BroadPhaseSet::Last,
+            )
+                .chain()
+                .in_set(PhysicsStepSet::BroadPhase),
+        );
+
+        let physics_schedule = app
+            .get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first");
+
+        // The intervals are cheap to keep up to date, and keeping them in sync
+        // allows switching back to sweep and prune at any time.
+        physics_schedule.add_systems(
+            (update_aabb_intervals, add_new_aabb_intervals)
+                .chain()
+                .in_set(BroadPhaseSet::UpdateStructures),
+        );
+
+        // The collider tree is also used for spatial queries, so it is updated regardless of the method.
+        physics_schedule.add_systems(update_collider_tree.in_set(BroadPhaseSet::UpdateStructures));
+
+        physics_schedule.add_systems(
+            (
+                collect_collision_pairs.run_if(uses_method(BroadPhaseMethod::SweepAndPrune)),
+                collect_collision_pairs_tree.run_if(uses_method(BroadPhaseMethod::DynamicAabbTree)),
+            )
+                .chain()
+                .in_set(BroadPhaseSet::CollectCollisions),
+        );
+    }
+}
+
+/// A resource for configuring the [broad phase](BroadPhasePlugin).
+#[derive(Resource, Reflect, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Resource, PartialEq)]
+pub struct BroadPhaseConfig {
+    /// The algorithm used for finding pairs of potentially colliding entities.
+    ///
+    /// Default: [`BroadPhaseMethod::SweepAndPrune`]
+    pub method: BroadPhaseMethod,
+
+    /// The margin by which AABBs are enlarged when they are inserted into
+    /// the [`ColliderTree`]. Proxies are only moved in the tree once their AABB
+    /// leaves this enlarged AABB, so a larger margin reduces the number of tree updates
+    /// for slowly moving objects, but results in looser bounds.
+    ///
+    /// This is implicitly scaled by the [`PhysicsLengthUnit`].
+    ///
+    /// Default: `0.1`
+    pub tree_aabb_margin: Scalar,
+
+    /// The maximum number of proxies that are reinserted into the [`ColliderTree`]
+    /// each step to incrementally improve the quality of the tree.
+    ///
+    /// Default: `4`
+    pub tree_rebalance_iterations: usize,
+}
+
+impl Default for BroadPhaseConfig {
+    fn default() -> Self {
+        Self {
+            method: BroadPhaseMethod::default(),
+            tree_aabb_margin: 0.1,
+            tree_rebalance_iterations: 4,
+        }
+    }
+}
+
+/// The algorithm used by the [broad phase](BroadPhasePlugin) for finding pairs of potentially colliding entities.
+///
+/// Configured with [`BroadPhaseConfig::method`].
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub enum BroadPhaseMethod {
+    /// Sorts AABBs along the x-axis every step, and checks for overlap between
+    /// AABBs whose intervals along the axis intersect.
+    ///
+    /// This exploits temporal coherence and is very fast when objects are spread out
+    /// along the x-axis, but degrades when many objects overlap on that axis,
+    /// for example in large worlds where objects are spread along the other axes.
+    #[default]
+    SweepAndPrune,
+    /// Queries the [`ColliderTree`], a persistent [`DynamicAabbTree`] of fattened AABBs
+    /// that is updated incrementally, for each moving collider.
+    ///
+    /// This performs well regardless of how objects are distributed in the world.
+    DynamicAabbTree,
+}
+
+/// Returns a run condition that is `true` if the broad phase uses the given method.
+fn uses_method(method: BroadPhaseMethod) -> impl Fn(Res<BroadPhaseConfig>) -> bool {
+    move |config: Res<BroadPhaseConfig>| config.method == method
+}
+
+/// System sets for systems running in [`PhysicsStepSet::BroadPhase`].
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BroadPhaseSet {
+    /// Runs at the start of the broad phase. Empty by default.
+    First,
+    /// Updates acceleration structures and other data needed for broad phase collision detection.
+    UpdateStructures,
+    /// Detects potential intersections between entities and adds them to the [`BroadCollisionPairs`] resource.
+    CollectCollisions,
+    /// Runs at the end of the broad phase. Empty by default.
+    Last,
+}
+
+/// A list of entity pairs for potential collisions collected during the broad phase.
+#[derive(Reflect, Resource, Debug, Default, Deref, DerefMut)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Resource)]
+pub struct BroadCollisionPairs(pub Vec<(Entity, Entity)>);
+
+/// Contains the entities whose AABBs intersect the AABB of this entity.
+/// Updated automatically during broad phase collision detection.
+///
+/// Note that this component is only added to bodies with [`SweptCcd`] by default,
+/// but can be added to any entity.
+#[derive(Component, Clone, Debug, Default, Deref, DerefMut, Reflect)]
+#[reflect(Component)]
+pub struct AabbIntersections(pub Vec<Entity>);
+
+/// True if the rigid body should store [`AabbIntersections`].
+type StoreAabbIntersections = bool;
+
+/// True if the rigid body hasn't moved.
+type IsBodyInactive = bool;
+
+/// Entities with [`ColliderAabb`]s sorted along an axis by their extents.
+#[derive(Resource, Default)]
+struct AabbIntervals(
+    Vec<(
+        Entity,
+        ColliderParent,
+        ColliderAabb,
+        CollisionLayers,
+        StoreAabbIntersections,
+        IsBodyInactive,
+    )>,
+);
+
+impl MapEntities for AabbIntervals {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        for interval in self.0.iter_mut() {
+            interval.0 = entity_mapper.map_entity(interval.0);
+        }
+    }
+}
+
+/// Updates [`AabbIntervals`] to keep them in sync with the [`ColliderAabb`]s.
+#[allow(clippy::type_complexity)]
+fn update_aabb_intervals(
+    aabbs: Query<(
+        &ColliderAabb,
+        Option<&ColliderParent>,
+        Option<&CollisionLayers>,
+        Has<AabbIntersections>,
+        Has<Sleeping>,
+    )>,
+    rbs: Query<&RigidBody>,
+    mut intervals: ResMut<AabbIntervals>,
+) {
+    intervals.0.retain_mut(
+        |(collider_entity, collider_parent, aabb, layers, store_intersections, is_inactive)| {
+            if let Ok((new_aabb, new_parent, new_layers, new_store_intersections, is_sleeping)) =
+                aabbs.get(*collider_entity)
+            {
+                if !new_aabb.min.is_finite() || !new_aabb.max.is_finite() {
+                    return false;
+                }
+
+                *aabb = *new_aabb;
+                *collider_parent = new_parent.map_or(ColliderParent(*collider_entity), |p| *p);
+                *layers = new_layers.map_or(CollisionLayers::default(), |layers| *layers);
+
+                let is_static =
+                    new_parent.is_some_and(|p| rbs.get(p.get()).is_ok_and(RigidBody::is_static));
+                *is_inactive = is_static || is_sleeping;
+                *store_intersections = new_store_intersections;
+
+                true
+            } else {
+                false
+            }
+        },
+    );
+}
+
+/// Adds new [`ColliderAabb`]s to [`AabbIntervals`].
+#[allow(clippy::type_complexity)]
+fn add_new_aabb_intervals(
+    aabbs: Query<
+        (
+            Entity,
+            Option<&ColliderParent>,
+            &ColliderAabb,
+            Option<&RigidBody>,
+            Option<&CollisionLayers>,
+            Has<AabbIntersections>,
+        ),
+        Added<ColliderAabb>,
+    >,
+    mut intervals: ResMut<AabbIntervals>,
+) {
+    let aabbs = aabbs
+        .iter()
+        .map(|(ent, parent, aabb, rb, layers, store_intersections)| {
+            (
+                ent,
+                parent.map_or(ColliderParent(ent), |p| *p),
+                *aabb,
+                layers.map_or(CollisionLayers::default(), |layers| *layers),
+                store_intersections,
+                // Default to treating collider as immovable/static for filtering unnecessary collision checks
+                rb.map_or(false, |rb| rb.is_static()),
+            )
+        });
+    intervals.0.extend(aabbs);
+}
+
+/// Collects bodies that are potentially colliding.
+fn collect_collision_pairs(
+    intervals: ResMut<AabbIntervals>,
+    mut broad_collision_pairs: ResMut<BroadCollisionPairs>,
+    mut aabb_intersection_query: Query<&mut AabbIntersections>,
+    joint_collision_filter: Option<Res<JointCollisionFilter>>,
+) {
+    for mut intersections in &mut aabb_intersection_query {
+        intersections.clear();
+    }
+
+    sweep_and_prune(
+        intervals,
+        &mut broad_collision_pairs.0,
+        &mut aabb_intersection_query,
+        joint_collision_filter.as_deref(),
+    );
+}
+
+/// Sorts the entities by their minimum extents along an axis and collects the entity pairs that have intersecting AABBs.
+///
+/// Sweep and prune exploits temporal coherence, as bodies are unlikely to move significantly between two simulation steps. Insertion sort is used, as it is good at sorting nearly sorted lists efficiently.
+fn sweep_and_prune(
+    mut intervals: ResMut<AabbIntervals>,
+    broad_collision_pairs: &mut Vec<(Entity, Entity)>,
+    aabb_intersection_query: &mut Query<&mut AabbIntersections>,
+    joint_collision_filter: Option<&JointCollisionFilter>,
+) {
+    // Sort bodies along the x-axis using insertion sort, a sorting algorithm great for sorting nearly sorted lists.
+    insertion_sort(&mut intervals.0, |a, b| a.2.min.x > b.2.min.x);
+
+    // Clear broad phase collisions from previous iteration.
+    broad_collision_pairs.clear();
+
+    // Find potential collisions by checking for AABB intersections along all axes.
+    for (i, (ent1, parent1, aabb1, layers1, store_intersections1, inactive1)) in
+        intervals.0.iter().enumerate()
+    {
+        for (ent2, parent2, aabb2, layers2, store_intersections2, inactive2) in
+            intervals.0.iter().skip(i + 1)
+        {
+            // x doesn't intersect; check this first so we can discard as soon as possible
+            if aabb2.min.x > aabb1.max.x {
+                break;
+            }
+
+            // No collisions between bodies that haven't moved or colliders with incompatible layers or colliders with the same parent
+            if (*inactive1 && *inactive2) || !layers1.interacts_with(*layers2) || parent1 == parent2
+            {
+                continue;
+            }
+
+            // y doesn't intersect
+            if aabb1.min.y > aabb2.max.y || aabb1.max.y < aabb2.min.y {
+                continue;
+            }
+
+            #[cfg(feature = "3d")]
+            // z doesn't intersect
+            if aabb1.min.z > aabb2.max.z || aabb1.max.z < aabb2.min.z {
+                continue;
+            }
+
+            // No contacts between bodies connected by a joint with collisions disabled
+            if !joint_collision_filter
+                .is_some_and(|filter| filter.contains(parent1.get(), parent2.get()))
+            {
+                if *ent1 < *ent2 {
+                    broad_collision_pairs.push((*ent1, *ent2));
+                } else {
+                    broad_collision_pairs.push((*ent2, *ent1));
+                }
+            }
+
+            if *store_intersections1 {
+                if let Ok(mut intersections) = aabb_intersection_query.get_mut(*ent1) {
+                    intersections.push(*ent2);
+                }
+            }
+            if *store_intersections2 {
+                if let Ok(mut intersections) = aabb_intersection_query.get_mut(*ent2) {
+                    intersections.push(*ent1);
+                }
+            }
+        }
+    }
+}
+
+/// Sorts a list iteratively using comparisons. In an ascending sort order, when a smaller value is encountered, it is moved lower in the list until it is larger than the item before it.
+///
+/// This is relatively slow for large lists, but very efficient in cases where the list is already mostly sorted.
+fn insertion_sort<T>(items: &mut [T], comparison: fn(&T, &T) -> bool) {
+    for i in 1..items.len() {
+        let mut j = i;
+        while j > 0 && comparison(&items[j - 1], &items[j]) {
+            items.swap(j - 1, j);
+            j -= 1;
+        }
+    }
+}
+
+/// The data stored for each collider in the [`ColliderTree`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ColliderTreeProxy {
+    /// The collider entity.
+    pub entity: Entity,
+    /// The rigid body that the collider is attached to,
+    /// or the collider entity itself if it has no [`ColliderParent`].
+    pub parent: ColliderParent,
+    /// The [`ColliderAabb`] of the collider when the proxy was last updated.
+    pub aabb: ColliderAabb,
+    /// The [`CollisionLayers`] of the collider.
+    pub layers: CollisionLayers,
+    /// True if the collider should store [`AabbIntersections`].
+    pub store_intersections: bool,
+    /// True if the rigid body is static or sleeping.
+    pub is_inactive: bool,
+}
+
+/// A [`DynamicAabbTree`] containing every collider in the world,
+/// along with a mapping from collider entities to their proxies.
+///
+/// The tree is the acceleration structure shared by the [broad phase](BroadPhasePlugin)
+/// and [spatial queries](spatial_query). It is updated incrementally by the [`BroadPhasePlugin`]
+/// in [`BroadPhaseSet::UpdateStructures`], and refit for colliders that have moved since then
+/// when the [`SpatialQueryPipeline`](spatial_query::SpatialQueryPipeline) is updated.
+///
+/// Leaves store fattened AABBs, so the AABBs in the tree are conservative bounds.
+/// Exact tests should use the actual collider shapes.
+#[derive(Resource, Clone, Debug)]
+pub struct ColliderTree {
+    tree: DynamicAabbTree<ColliderTreeProxy>,
+    proxies: EntityHashMap<ProxyId>,
+    /// The margin by which AABBs are enlarged when they are inserted or moved.
+    /// Set from [`BroadPhaseConfig::tree_aabb_margin`] by the broad phase.
+    aabb_margin: Scalar,
+}
+
+impl Default for ColliderTree {
+    fn default() -> Self {
+        Self {
+            tree: DynamicAabbTree::new(),
+            proxies: EntityHashMap::default(),
+            aabb_margin: BroadPhaseConfig::default().tree_aabb_margin,
+        }
+    }
+}
+
+impl ColliderTree {
+    /// Returns a reference to the underlying [`DynamicAabbTree`].
+    #[inline]
+    pub fn tree(&self) -> &DynamicAabbTree<ColliderTreeProxy> {
+        &self.tree
+    }
+
+    /// Returns the number of colliders in the tree.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    /// Returns `true` if the tree contains no colliders.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    /// Returns the margin by which AABBs are enlarged when they are inserted or moved.
+    #[inline]
+    pub fn aabb_margin(&self) -> Scalar {
+        self.aabb_margin
+    }
+
+    /// Returns the proxy of the given collider entity, or `None` if it is not in the tree.
+    #[inline]
+    pub fn proxy(&self, entity: Entity) -> Option<ProxyId> {
+        self.proxies.get(&entity).copied()
+    }
+
+    /// Returns the data stored for the given collider entity, or `None` if it is not in the tree.
+    #[inline]
+    pub fn get(&self, entity: Entity) -> Option<&ColliderTreeProxy> {
+        self.tree.get(self.proxy(entity)?)
+    }
+
+    /// Inserts a collider into the tree, or moves it if it is already in the tree,
+    /// and updates its stored data.
+    pub fn insert_or_update(&mut self, data: ColliderTreeProxy) -> ProxyId {
+        if let Some(proxy) = self.proxy(data.entity) {
+            self.tree.move_proxy(proxy, data.aabb, self.aabb_margin);
+            *self.tree.get_mut(proxy).expect("proxy should be in tree") = data;
+            proxy
+        } else {
+            let proxy = self.tree.insert(data.aabb, self.aabb_margin, data);
+            self.proxies.insert(data.entity, proxy);
+            proxy
+        }
+    }
+
+    /// Removes the given collider entity from the tree and returns its data,
+    /// or `None` if it is not in the tree.
+    pub fn remove(&mut self, entity: Entity) -> Option<ColliderTreeProxy> {
+        let proxy = self.proxies.remove(&entity)?;
+        self.tree.remove(proxy)
+    }
+
+    /// Calls the given `callback` for each collider whose fattened AABB intersects `aabb`.
+    ///
+    /// The traversal is stopped if the callback returns `false`.
+    pub fn query_aabb(
+        &self,
+        aabb: ColliderAabb,
+        mut callback: impl FnMut(&ColliderTreeProxy) -> bool,
+    ) {
+        self.tree.query_aabb(aabb, |_, data| callback(data));
+    }
+}
+
+impl MapEntities for ColliderTree {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        let proxies = std::mem::take(&mut self.proxies);
+        for (entity, proxy) in proxies {
+            let entity = entity_mapper.map_entity(entity);
+            if let Some(data) = self.tree.get_mut(proxy) {
+                data.entity = entity;
+                data.parent = ColliderParent(entity_mapper.map_entity(data.parent.get()));
+            }
+            self.proxies.insert(entity, proxy);
+        }
+    }
+}
+
+/// Inserts, moves, and removes proxies in the [`ColliderTree`] to keep it in sync with the [`ColliderAabb`]s.
+///
+/// Only colliders that have been added, changed, or removed since the last step are updated,
+/// so colliders that haven't moved don't add any per-step cost.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+fn update_collider_tree(
+    aabbs: Query<(
+        &ColliderAabb,
+        Option<&ColliderParent>,
+        Option<&CollisionLayers>,
+        Has<AabbIntersections>,
+        Has<Sleeping>,
+    )>,
+    changed_aabbs: Query<
+        Entity,
+        Or<(
+            Changed<ColliderAabb>,
+            Changed<ColliderParent>,
+            Changed<CollisionLayers>,
+            Added<AabbIntersections>,
+            Added<Sleeping>,
+        )>,
+    >,
+    changed_rbs: Query<(Entity, Ref<RigidBody>), Changed<RigidBody>>,
+    colliders: Query<(Entity, &ColliderParent)>,
+    rbs: Query<&RigidBody>,
+    mut removed_aabbs: RemovedComponents<ColliderAabb>,
+    mut removed_layers: RemovedComponents<CollisionLayers>,
+    mut removed_intersections: RemovedComponents<AabbIntersections>,
+    mut removed_sleeping: RemovedComponents<Sleeping>,
+    mut tree: ResMut<ColliderTree>,
+    config: Res<BroadPhaseConfig>,
+    length_unit: Res<PhysicsLengthUnit>,
+) {
+    tree.aabb_margin = config.tree_aabb_margin * length_unit.0;
+
+    for entity in removed_aabbs.read() {
+        tree.remove(entity);
+    }
+
+    // Colliders of rigid bodies whose type has changed need to be updated, as static bodies are inactive.
+    // Colliders of newly added bodies are already handled as added colliders.
+    let changed_rbs: EntityHashSet = changed_rbs
+        .iter()
+        .filter_map(|(entity, rb)| (!rb.is_added()).then_some(entity))
+        .collect();
+    let colliders_of_changed_rbs = (!changed_rbs.is_empty())
+        .then(|| {
+            colliders
+                .iter()
+                .filter(|(_, parent)| changed_rbs.contains(&parent.get()))
+                .map(|(entity, _)| entity)
+        })
+        .into_iter()
+        .flatten();
+
+    let changed_entities = changed_aabbs
+        .iter()
+        .chain(removed_layers.read())
+        .chain(removed_intersections.read())
+        .chain(removed_sleeping.read())
+        .chain(colliders_of_changed_rbs);
+
+    for entity in changed_entities {
+        let Ok((aabb, parent, layers, store_intersections, is_sleeping)) = aabbs.get(entity) else {
+            continue;
+        };
+
+        // Remove proxies for colliders that no longer have a valid AABB.
+        if !aabb.min.is_finite() || !aabb.max.is_finite() {
+            tree.remove(entity);
+            continue;
+        }
+
+        let parent = parent.map_or(ColliderParent(entity), |p| *p);
+        let is_static = rbs.get(parent.get()).is_ok_and(RigidBody::is_static);
+        tree.insert_or_update(ColliderTreeProxy {
+            entity,
+            parent,
+            aabb: *aabb,
+            layers: layers.map_or(CollisionLayers::default(), |layers| *layers),
+            store_intersections,
+            is_inactive: is_static || is_sleeping,
+        });
+    }
+
+    tree.tree.rebalance(config.tree_rebalance_iterations);
+}
+
+/// Collects bodies that are potentially colliding by querying the [`ColliderTree`].
+fn collect_collision_pairs_tree(
+    tree: Res<ColliderTree>,
+    mut broad_collision_pairs: ResMut<BroadCollisionPairs>,
+    mut aabb_intersection_query: Query<&mut AabbIntersections>,
+    joint_collision_filter: Option<Res<JointCollisionFilter>>,
+) {
+    for mut intersections in &mut aabb_intersection_query {
+        intersections.clear();
+    }
+
+    broad_collision_pairs.clear();
+
+    for (proxy1, data1) in tree.tree.iter() {
+        // Pairs between an inactive and an active body are found when querying for the active body.
+        if data1.is_inactive {
+            continue;
+        }
+
+        tree.tree.query_aabb(data1.aabb, |proxy2, data2| {
+            // Each pair of active bodies should only be collected once.
+            if proxy1 == proxy2 || (!data2.is_inactive && proxy2 < proxy1) {
+                return true;
+            }
+
+            // No collisions between colliders with incompatible layers or colliders with the same parent,
+            // or if the actual AABBs don't intersect.
+            if !data1.layers.interacts_with(data2.layers)
+                || data1.parent == data2.parent
+                || !data1.aabb.intersects(&data2.aabb)
+            {
+                return true;
+            }
+
+            // No contacts between bodies connected by a joint with collisions disabled
+            if !joint_collision_filter
+                .as_ref()
+                .is_some_and(|filter| filter.contains(data1.parent.get(), data2.parent.get()))
+            {
+                if data1.entity < data2.entity {
+                    broad_collision_pairs.push((data1.entity, data2.entity));
+                } else {
+                    broad_collision_pairs.push((data2.entity, data1.entity));
+                }
+            }
+
+            if data1.store_intersections {
+                if let Ok(mut intersections) = aabb_intersection_query.get_mut(data1.entity) {
+                    intersections.push(data2.entity);
+                }
+            }
+            if data2.store_intersections {
+                if let Ok(mut intersections) = aabb_intersection_query.get_mut(data2.entity) {
+                    intersections.push(data1.entity);
+                }
+            }
+
+            true
+        });
+    }
+}
@@ -429,6 +429,39 @@ impl Default for ColliderAabb {
 #[doc(alias = "ContactSkin")]
 pub struct CollisionMargin(pub Scalar);
 
+/// The velocity of the surface of a collider relative to the collider itself, in its local space.
+///
+/// Friction drives bodies touching the collider towards this velocity along the contact surface,
+/// without the collider itself moving. This can be used for conveyor belts, treadmills and escalators.
+/// The component along the contact normal is ignored.
+///
+/// The surface velocities of two colliders are combined into the [`ContactManifold::tangent_velocity`],
+/// which can be further modified with [`CollisionHooks`](crate::collision::hooks::CollisionHooks).
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::{math::Vector, prelude::*};")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::{math::Vector, prelude::*};")]
+/// use bevy::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     // A conveyor belt that moves bodies along the x-axis at 2 units per second.
+///     commands.spawn((
+///         RigidBody::Static,
+#[cfg_attr(feature = "2d", doc = "        Collider::rectangle(10.0, 0.5),")]
+#[cfg_attr(feature = "3d", doc = "        Collider::cuboid(10.0, 0.5, 2.0),")]
+///         SurfaceVelocity(Vector::X * 2.0),
+///     ));
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, Deref, DerefMut, PartialEq, From)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+#[doc(alias = "ConveyorBelt")]
+pub struct SurfaceVelocity(pub Vector);
+
 /// A component that stores the entities that are colliding with an entity.
 ///
 /// This component is automatically added for all entities with a [`Collider`],
@@ -658,6 +658,107 @@ impl Collider {
         )
     }
 
+    /// Computes the volume and the center of volume of the part of `self` transformed by `translation`
+    /// and `rotation` that is below a plane, for example the part of a body that is submerged in water.
+    /// The plane is defined by a point on the plane and its `up` normal. In 2D, the volume is an area.
+    ///
+    /// Curved shapes like balls and capsules are approximated with polygonal boundaries,
+    /// and the rounded borders of round shapes are ignored in 3D.
+    ///
+    /// Returns `None` if the shape isn't supported. Supported shapes are balls, cuboids, capsules,
+    /// triangles and convex polygons in 2D, cylinders, cones, convex polyhedra and closed triangle meshes in 3D,
+    /// and compound shapes consisting of supported shapes.
+    pub fn volume_below_plane(
+        &self,
+        translation: impl Into<Position>,
+        rotation: impl Into<Rotation>,
+        plane_point: Vector,
+        up: Vector,
+    ) -> Option<(Scalar, Vector)> {
+        let translation: Position = translation.into();
+        let isometry = make_isometry(translation, rotation);
+
+        let mut boundary = vec![];
+        if !collect_boundary(self.shape_scaled().as_ref(), &isometry, &mut boundary) {
+            return None;
+        }
+
+        // The volume is computed as a sum of signed simplices between the clipped boundary
+        // and an apex on the plane. The cap of the clipped shape lies on the plane,
+        // so it doesn't contribute to the volume and can be ignored.
+        let up = up.normalize_or_zero();
+        let apex = translation.0 - up * (translation.0 - plane_point).dot(up);
+        let height = |point: Vector| (point - plane_point).dot(up);
+
+        let mut volume = 0.0;
+        let mut weighted_center = Vector::ZERO;
+
+        #[cfg(feature = "2d")]
+        for segment in boundary {
+            if let Some([a, b]) = clip_segment_below_plane(segment, height) {
+                let simplex_volume = (a - apex).perp_dot(b - apex) / 2.0;
+                volume += simplex_volume;
+                weighted_center += simplex_volume * (apex + a + b) / 3.0;
+            }
+        }
+        #[cfg(feature = "3d")]
+        for triangle in boundary {
+            let polygon = clip_triangle_below_plane(triangle, height);
+            for i in 1..polygon.len().saturating_sub(1) {
+                let (a, b, c) = (polygon[0], polygon[i], polygon[i + 1]);
+                let simplex_volume = (a - apex).dot((b - apex).cross(c - apex)) / 6.0;
+                volume += simplex_volume;
+                weighted_center += simplex_volume * (apex + a + b + c) / 4.0;
+            }
+        }
+
+        if volume.abs() <= Scalar::EPSILON {
+            return Some((0.0, apex));
+        }
+
+        // The boundary can be oriented either way, so the sign of the volume is ignored.
+        Some((volume.abs(), weighted_center / volume))
+    }
+
+    /// Computes the area of the shadow cast by `self` rotated by `rotation` on a plane perpendicular
+    /// to `direction`, for example the cross-sectional area of a body facing the airflow.
+    /// In 2D, the area is a length.
+    ///
+    /// The area is computed from the boundary of the shape, so it is exact for convex shapes,
+    /// and overestimated for concave shapes and overlapping parts of compound shapes.
+    /// Curved shapes are approximated like in [`Collider::volume_below_plane`].
+    ///
+    /// Returns `None` if the shape isn't supported. The same shapes are supported
+    /// as in [`Collider::volume_below_plane`].
+    pub fn projected_area(
+        &self,
+        rotation: impl Into<Rotation>,
+        direction: Vector,
+    ) -> Option<Scalar> {
+        let isometry = make_isometry(Position::default(), rotation);
+
+        let mut boundary = vec![];
+        if !collect_boundary(self.shape_scaled().as_ref(), &isometry, &mut boundary) {
+            return None;
+        }
+
+        // Each part of the boundary casts a shadow proportional to how much it faces the direction.
+        // The boundary of a closed shape covers the shadow twice, once from the front and once from the back.
+        let direction = direction.normalize_or_zero();
+        #[cfg(feature = "2d")]
+        let area: Scalar = boundary
+            .iter()
+            .map(|[a, b]| (*b - *a).perp_dot(direction).abs())
+            .sum();
+        #[cfg(feature = "3d")]
+        let area: Scalar = boundary
+            .iter()
+            .map(|[a, b, c]| (*b - *a).cross(*c - *a).dot(direction).abs() / 2.0)
+            .sum();
+
+        Some(area / 2.0)
+    }
+
     /// Creates a collider with a compound shape defined by a given vector of colliders with a position and a rotation.
     ///
     /// Especially for dynamic rigid bodies, compound shape colliders should be preferred over triangle meshes and polylines,
@@ -1466,3 +1567,226 @@ fn scale_shape(
         }
     }
 }
+
+/// The number of subdivisions used for approximating curved shapes with polygonal boundaries.
+const BOUNDARY_SUBDIVISIONS: u32 = 16;
+
+/// Collects the boundary of a shape transformed by `isometry` as segments.
+/// Returns `false` if the shape isn't supported.
+#[cfg(feature = "2d")]
+fn collect_boundary(
+    shape: &dyn parry::shape::Shape,
+    isometry: &parry::math::Isometry<Scalar>,
+    boundary: &mut Vec<[Vector; 2]>,
+) -> bool {
+    let points = match shape.as_typed_shape() {
+        TypedShape::Ball(ball) => ball.to_polyline(BOUNDARY_SUBDIVISIONS),
+        TypedShape::Cuboid(cuboid) => cuboid.to_polyline(),
+        TypedShape::Capsule(capsule) => capsule.to_polyline(BOUNDARY_SUBDIVISIONS),
+        TypedShape::Triangle(triangle) => triangle.vertices().to_vec(),
+        TypedShape::ConvexPolygon(polygon) => polygon.points().to_vec(),
+        TypedShape::RoundCuboid(cuboid) => cuboid.to_polyline(BOUNDARY_SUBDIVISIONS),
+        TypedShape::RoundConvexPolygon(polygon) => polygon.to_polyline(BOUNDARY_SUBDIVISIONS),
+        TypedShape::Compound(compound) => {
+            return compound.shapes().iter().all(|(sub_isometry, sub_shape)| {
+                collect_boundary(sub_shape.as_ref(), &(isometry * sub_isometry), boundary)
+            });
+        }
+        _ => return false,
+    };
+
+    let points: Vec<Vector> = points
+        .iter()
+        .map(|point| (isometry * point).into())
+        .collect();
+    boundary.extend(
+        points
+            .iter()
+            .zip(points.iter().cycle().skip(1))
+            .map(|(a, b)| [*a, *b]),
+    );
+    true
+}
+
+/// Collects the boundary of a shape transformed by `isometry` as triangles.
+/// Returns `false` if the shape isn't supported.
+#[cfg(feature = "3d")]
+fn collect_boundary(
+    shape: &dyn parry::shape::Shape,
+    isometry: &parry::math::Isometry<Scalar>,
+    boundary: &mut Vec<[Vector; 3]>,
+) -> bool {
+    let (vertices, indices) = match shape.as_typed_shape() {
+        TypedShape::Ball(ball) => ball.to_trimesh(BOUNDARY_SUBDIVISIONS, BOUNDARY_SUBDIVISIONS / 2),
+        TypedShape::Cuboid(cuboid) => cuboid.to_trimesh(),
+        TypedShape::Capsule(capsule) => {
+            capsule.to_trimesh(BOUNDARY_SUBDIVISIONS, BOUNDARY_SUBDIVISIONS / 2)
+        }
+        TypedShape::Cylinder(cylinder) => cylinder.to_trimesh(BOUNDARY_SUBDIVISIONS),
+        TypedShape::Cone(cone) => cone.to_trimesh(BOUNDARY_SUBDIVISIONS),
+        TypedShape::ConvexPolyhedron(polyhedron) => polyhedron.to_trimesh(),
+        TypedShape::TriMesh(trimesh) => (trimesh.vertices().to_vec(), trimesh.indices().to_vec()),
+        TypedShape::RoundCuboid(cuboid) => cuboid.inner_shape.to_trimesh(),
+        TypedShape::RoundCylinder(cylinder) => {
+            cylinder.inner_shape.to_trimesh(BOUNDARY_SUBDIVISIONS)
+        }
+        TypedShape::RoundCone(cone) => cone.inner_shape.to_trimesh(BOUNDARY_SUBDIVISIONS),
+        TypedShape::RoundConvexPolyhedron(polyhedron) => polyhedron.inner_shape.to_trimesh(),
+        TypedShape::Compound(compound) => {
+            return compound.shapes().iter().all(|(sub_isometry, sub_shape)| {
+                collect_boundary(sub_shape.as_ref(), &(isometry * sub_isometry), boundary)
+            });
+        }
+        _ => return false,
+    };
+
+    boundary.extend(
+        indices
+            .iter()
+            .map(|triangle| triangle.map(|index| (isometry * vertices[index as usize]).into())),
+    );
+    true
+}
+
+/// Clips the part of the segment that is above a plane, where `height` is the signed distance to the plane.
+#[cfg(feature = "2d")]
+fn clip_segment_below_plane(
+    [a, b]: [Vector; 2],
+    height: impl Fn(Vector) -> Scalar,
+) -> Option<[Vector; 2]> {
+    let (height_a, height_b) = (height(a), height(b));
+    let intersection = || a + (b - a) * (height_a / (height_a - height_b));
+
+    match (height_a <= 0.0, height_b <= 0.0) {
+        (true, true) => Some([a, b]),
+        (true, false) => Some([a, intersection()]),
+        (false, true) => Some([intersection(), b]),
+        (false, false) => None,
+    }
+}
+
+/// Clips the part of the triangle that is above a plane, where `height` is the signed distance to the plane.
+/// The remaining part is a polygon with up to four vertices.
+#[cfg(feature = "3d")]
+fn clip_triangle_below_plane(
+    triangle: [Vector; 3],
+    height: impl Fn(Vector) -> Scalar,
+) -> Vec<Vector> {
+    let mut polygon = Vec::with_capacity(4);
+
+    for i in 0..3 {
+        let (a, b) = (triangle[i], triangle[(i + 1) % 3]);
+        let (height_a, height_b) = (height(a), height(b));
+
+        if height_a <= 0.0 {
+            polygon.push(a);
+        }
+        if (height_a <= 0.0) != (height_b <= 0.0) {
+            polygon.push(a + (b - a) * (height_a / (height_a - height_b)));
+        }
+    }
+
+    polygon
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn volume_below_plane_of_cuboid() {
+        #[cfg(feature = "2d")]
+        let (collider, full_volume) = (Collider::rectangle(2.0, 2.0), 4.0);
+        #[cfg(feature = "3d")]
+        let (collider, full_volume) = (Collider::cuboid(2.0, 2.0, 2.0), 8.0);
+
+        // Partially submerged
+        let (volume, center) = collider
+            .volume_below_plane(
+                Vector::ZERO,
+                Rotation::default(),
+                Vector::Y * 0.5,
+                Vector::Y,
+            )
+            .unwrap();
+        assert_relative_eq!(volume, full_volume * 0.75, epsilon = 0.0001);
+        assert_relative_eq!(center, Vector::NEG_Y * 0.25, epsilon = 0.0001);
+
+        // Fully submerged
+        let (volume, center) = collider
+            .volume_below_plane(Vector::X, Rotation::default(), Vector::Y * 5.0, Vector::Y)
+            .unwrap();
+        assert_relative_eq!(volume, full_volume, epsilon = 0.0001);
+        assert_relative_eq!(center, Vector::X, epsilon = 0.0001);
+
+        // Above the plane
+        let (volume, _) = collider
+            .volume_below_plane(
+                Vector::ZERO,
+                Rotation::default(),
+                Vector::NEG_Y * 2.0,
+                Vector::Y,
+            )
+            .unwrap();
+        assert_eq!(volume, 0.0);
+    }
+
+    #[test]
+    fn projected_area_of_cuboid() {
+        #[cfg(feature = "2d")]
+        let collider = Collider::rectangle(2.0, 1.0);
+        #[cfg(feature = "3d")]
+        let collider = Collider::cuboid(2.0, 1.0, 1.0);
+
+        let area = collider
+            .projected_area(Rotation::default(), Vector::X)
+            .unwrap();
+        assert_relative_eq!(area, 1.0, epsilon = 0.0001);
+        let area = collider
+            .projected_area(Rotation::default(), Vector::NEG_Y)
+            .unwrap();
+        assert_relative_eq!(area, 2.0, epsilon = 0.0001);
+
+        // Rotated by 90 degrees, the long side faces the direction.
+        #[cfg(feature = "2d")]
+        let rotation = Rotation::degrees(90.0);
+        #[cfg(feature = "3d")]
+        let rotation = Rotation::from(Quaternion::from_rotation_z(PI / 2.0));
+        let area = collider.projected_area(rotation, Vector::X).unwrap();
+        assert_relative_eq!(area, 2.0, epsilon = 0.0001);
+    }
+
+    #[test]
+    fn volume_below_plane_of_compound() {
+        // Two unit squares or cubes next to each other, one of them rotated.
+        let collider = Collider::compound(vec![
+            (
+                Vector::NEG_X,
+                Rotation::default(),
+                #[cfg(feature = "2d")]
+                Collider::rectangle(1.0, 1.0),
+                #[cfg(feature = "3d")]
+                Collider::cuboid(1.0, 1.0, 1.0),
+            ),
+            (
+                Vector::X,
+                #[cfg(feature = "2d")]
+                Rotation::degrees(90.0),
+                #[cfg(feature = "3d")]
+                Rotation::from(Quaternion::from_rotation_z(PI / 2.0)),
+                #[cfg(feature = "2d")]
+                Collider::rectangle(1.0, 1.0),
+                #[cfg(feature = "3d")]
+                Collider::cuboid(1.0, 1.0, 1.0),
+            ),
+        ]);
+
+        // The bottom half of both shapes is submerged.
+        let (volume, center) = collider
+            .volume_below_plane(Vector::ZERO, Rotation::default(), Vector::ZERO, Vector::Y)
+            .unwrap();
+        assert_relative_eq!(volume, 1.0, epsilon = 0.0001);
+        assert_relative_eq!(center, Vector::NEG_Y * 0.25, epsilon = 0.0001);
+    }
+}
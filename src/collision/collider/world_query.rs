@@ -22,6 +22,11 @@ pub struct ColliderQuery<C: AnyCollider> {
     pub is_sensor: Has<Sensor>,
     pub friction: Option<&'static Friction>,
     pub restitution: Option<&'static Restitution>,
+    pub anisotropic_friction: Option<&'static AnisotropicFriction>,
+    pub surface_velocity: Option<&'static SurfaceVelocity>,
+    #[cfg(feature = "physics-material")]
+    pub material: Option<&'static Handle<PhysicsMaterial>>,
+    pub active_hooks: Option<&'static ActiveCollisionHooks>,
     pub shape: &'static C,
 }
 
@@ -0,0 +1,238 @@
+//! Collision hooks for filtering and modifying contacts in the narrow phase.
+//!
+//! See [`CollisionHooks`].
+
+use crate::prelude::*;
+use bevy::{ecs::system::ReadOnlySystemParam, prelude::*};
+
+/// A trait for user-defined hooks that can filter collision pairs and modify contacts.
+///
+/// Collision hooks are called by the [narrow phase](NarrowPhasePlugin) for each collision pair
+/// inside of its parallel loop. Unlike systems in the [`PostProcessCollisions`] schedule, they don't
+/// require an extra pass over all [`Collisions`], which makes them well suited for per-pair logic
+/// like one-way platforms and conveyor belts.
+///
+/// Hooks are only called for pairs where at least one of the colliders has the
+/// [`ActiveCollisionHooks`] component with the corresponding flag enabled.
+///
+/// The hooks are a [`ReadOnlySystemParam`], so they can access queries and resources.
+/// Structural changes and other deferred work can be queued with the given [`Commands`].
+///
+/// # Usage
+///
+/// Collision hooks can be added to the simulation with [`PhysicsPlugins::with_collision_hooks`].
+///
+/// ```no_run
+#[cfg_attr(feature = "2d", doc = "use avian2d::{math::Vector, prelude::*};")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::{math::Vector, prelude::*};")]
+/// use bevy::{ecs::system::SystemParam, prelude::*};
+///
+/// /// A conveyor belt that moves bodies touching it.
+/// #[derive(Component)]
+/// struct ConveyorBelt(Vector);
+///
+/// /// A marker component for colliders that never collide with each other.
+/// #[derive(Component)]
+/// struct Ghost;
+///
+/// #[derive(SystemParam)]
+/// struct MyHooks<'w, 's> {
+///     belts: Query<'w, 's, &'static ConveyorBelt>,
+///     ghosts: Query<'w, 's, (), With<Ghost>>,
+/// }
+///
+/// impl CollisionHooks for MyHooks<'_, '_> {
+///     fn filter_pairs(&self, collider1: Entity, collider2: Entity, _commands: &mut Commands) -> bool {
+///         // Ghosts pass through each other.
+///         !(self.ghosts.contains(collider1) && self.ghosts.contains(collider2))
+///     }
+///
+///     fn modify_contacts(&self, contacts: &mut Contacts, _commands: &mut Commands) -> bool {
+///         // Move bodies along the conveyor belt.
+///         let tangent_velocity = if let Ok(belt) = self.belts.get(contacts.entity1) {
+///             belt.0
+///         } else if let Ok(belt) = self.belts.get(contacts.entity2) {
+///             -belt.0
+///         } else {
+///             return true;
+///         };
+///
+///         for manifold in contacts.manifolds.iter_mut() {
+///             manifold.tangent_velocity = tangent_velocity;
+///         }
+///
+///         true
+///     }
+/// }
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((
+///             DefaultPlugins,
+///             PhysicsPlugins::default().with_collision_hooks::<MyHooks>(),
+///         ))
+///         .run();
+/// }
+/// ```
+pub trait CollisionHooks: ReadOnlySystemParam + Send + Sync {
+    /// Called for each pair of colliders in [`BroadCollisionPairs`] before contacts are computed.
+    /// Returning `false` skips the pair, and no contacts are computed for it.
+    ///
+    /// This is only called if at least one of the colliders has [`ActiveCollisionHooks::FILTER_PAIRS`].
+    #[allow(unused_variables)]
+    fn filter_pairs(&self, collider1: Entity, collider2: Entity, commands: &mut Commands) -> bool {
+        true
+    }
+
+    /// Called for each pair of colliders after contacts have been computed, and before
+    /// they are added to [`Collisions`]. The normals, [`Friction`], [`Restitution`] and tangent velocity
+    /// of each [`ContactManifold`] can be modified freely.
+    /// Returning `false` discards the contacts.
+    ///
+    /// This is only called if at least one of the colliders has [`ActiveCollisionHooks::MODIFY_CONTACTS`].
+    #[allow(unused_variables)]
+    fn modify_contacts(&self, contacts: &mut Contacts, commands: &mut Commands) -> bool {
+        true
+    }
+}
+
+impl CollisionHooks for () {}
+
+bitflags::bitflags! {
+    /// Flags that determine which [`CollisionHooks`] are called for a collider.
+    ///
+    /// A hook is called for a collision pair if either of the colliders has the corresponding flag.
+    /// Colliders without this component don't trigger any hooks.
+    #[repr(transparent)]
+    #[derive(Component, Hash, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "serialize", reflect_value(Serialize, Deserialize))]
+    #[reflect_value(Component, Hash, PartialEq, Debug)]
+    pub struct ActiveCollisionHooks: u8 {
+        /// Enables [`CollisionHooks::filter_pairs`].
+        const FILTER_PAIRS = 0b0000_0001;
+        /// Enables [`CollisionHooks::modify_contacts`].
+        const MODIFY_CONTACTS = 0b0000_0010;
+    }
+}
+
+#[cfg(test)]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+mod tests {
+    use super::*;
+    use crate::tests::{create_app_with_physics, tick_60_fps};
+    use bevy::ecs::system::SystemParam;
+
+    #[derive(Component)]
+    struct Ghost;
+
+    #[derive(Component)]
+    struct ConveyorBelt(Vector);
+
+    #[derive(SystemParam)]
+    struct TestHooks<'w, 's> {
+        ghosts: Query<'w, 's, (), With<Ghost>>,
+        belts: Query<'w, 's, &'static ConveyorBelt>,
+    }
+
+    impl CollisionHooks for TestHooks<'_, '_> {
+        fn filter_pairs(&self, collider1: Entity, collider2: Entity, _: &mut Commands) -> bool {
+            !self.ghosts.contains(collider1) && !self.ghosts.contains(collider2)
+        }
+
+        fn modify_contacts(&self, contacts: &mut Contacts, _: &mut Commands) -> bool {
+            let tangent_velocity = if let Ok(belt) = self.belts.get(contacts.entity1) {
+                belt.0
+            } else if let Ok(belt) = self.belts.get(contacts.entity2) {
+                -belt.0
+            } else {
+                return true;
+            };
+
+            for manifold in contacts.manifolds.iter_mut() {
+                manifold.tangent_velocity = tangent_velocity;
+            }
+
+            true
+        }
+    }
+
+    fn create_app() -> App {
+        create_app_with_physics(
+            PhysicsPlugins::default()
+                .with_collision_hooks::<TestHooks>()
+                .build()
+                .disable::<ColliderHierarchyPlugin>(),
+        )
+    }
+
+    fn spawn_ground(app: &mut App) -> Entity {
+        app.world_mut()
+            .spawn((
+                RigidBody::Static,
+                #[cfg(feature = "2d")]
+                Collider::rectangle(20.0, 1.0),
+                #[cfg(feature = "3d")]
+                Collider::cuboid(20.0, 1.0, 20.0),
+                Position::default(),
+            ))
+            .id()
+    }
+
+    fn spawn_box(app: &mut App, x: Scalar) -> Entity {
+        app.world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                #[cfg(feature = "2d")]
+                Collider::rectangle(1.0, 1.0),
+                #[cfg(feature = "3d")]
+                Collider::cuboid(1.0, 1.0, 1.0),
+                Position(Vector::X * x + Vector::Y),
+            ))
+            .id()
+    }
+
+    #[test]
+    fn filter_pairs_skips_contacts() {
+        let mut app = create_app();
+
+        spawn_ground(&mut app);
+        let solid = spawn_box(&mut app, -5.0);
+        let ghost = spawn_box(&mut app, 5.0);
+        app.world_mut()
+            .entity_mut(ghost)
+            .insert((Ghost, ActiveCollisionHooks::FILTER_PAIRS));
+
+        for _ in 0..60 {
+            tick_60_fps(&mut app);
+        }
+
+        // The solid box rests on the ground, while the ghost falls through it.
+        assert!(app.world().get::<Position>(solid).unwrap().y > 0.9);
+        assert!(app.world().get::<Position>(ghost).unwrap().y < 0.0);
+    }
+
+    #[test]
+    fn modify_contacts_sets_tangent_velocity() {
+        let mut app = create_app();
+
+        let ground = spawn_ground(&mut app);
+        app.world_mut().entity_mut(ground).insert((
+            ConveyorBelt(Vector::X * 2.0),
+            ActiveCollisionHooks::MODIFY_CONTACTS,
+        ));
+        let body = spawn_box(&mut app, 0.0);
+
+        for _ in 0..60 {
+            tick_60_fps(&mut app);
+        }
+
+        // Friction drags the box along the conveyor belt.
+        let velocity = app.world().get::<LinearVelocity>(body).unwrap();
+        assert!(velocity.x > 1.0);
+        assert!(app.world().get::<Position>(body).unwrap().x > 0.5);
+    }
+}
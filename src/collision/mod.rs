@@ -11,6 +11,8 @@
 //! - [`NarrowPhasePlugin`]: Computes [`Contacts`] for each pair in [`BroadCollisionPairs`], adding them to [`Collisions`].
 //! - [`ContactReportingPlugin`] (optional): Sends collision events and updates [`CollidingEntities`] based on [`Collisions`].
 //!
+//! Collision pairs can be filtered and their contacts modified inside the narrow phase using [`CollisionHooks`].
+//!
 //! Spatial queries are handled separately by the [`SpatialQueryPlugin`].
 //!
 //! You can also find several utility methods for computing contacts in [`contact_query`].
@@ -22,7 +24,10 @@ pub mod broad_phase;
 ))]
 pub mod contact_query;
 pub mod contact_reporting;
+pub mod hooks;
 pub mod narrow_phase;
+#[cfg(feature = "physics-material")]
+pub mod physics_material;
 
 pub mod collider;
 pub use collider::*;
@@ -346,9 +351,61 @@ pub struct ContactManifold {
     pub normal2: Vector,
     /// The index of the manifold in the collision.
     pub index: usize,
+    /// The combined [`Friction`] of the colliders, or the rigid bodies they are attached to.
+    ///
+    /// This is computed by the narrow phase, and can be modified using [`CollisionHooks`].
+    pub friction: Friction,
+    /// The combined [`Restitution`] of the colliders, or the rigid bodies they are attached to.
+    ///
+    /// This is computed by the narrow phase, and can be modified using [`CollisionHooks`].
+    pub restitution: Restitution,
+    /// The [`AnisotropicFriction`] of the first collider, with the direction in world space.
+    ///
+    /// If both colliders have anisotropic friction, the smaller scale along each tangent direction is used,
+    /// like with [`CoefficientCombine::Min`].
+    ///
+    /// This is computed by the narrow phase, and can be modified using [`CollisionHooks`].
+    ///
+    /// Default: `None`
+    pub anisotropic_friction1: Option<AnisotropicFriction>,
+    /// The [`AnisotropicFriction`] of the second collider, with the direction in world space.
+    ///
+    /// See [`anisotropic_friction1`](Self::anisotropic_friction1).
+    ///
+    /// Default: `None`
+    pub anisotropic_friction2: Option<AnisotropicFriction>,
+    /// The target relative velocity of the second entity with respect to the first entity
+    /// along the contact surface, in world space.
+    ///
+    /// Friction drives the relative tangential velocity at the contact points towards this value.
+    /// The component along the contact normal is ignored. This can be used for effects like conveyor belts.
+    ///
+    /// This is computed by the narrow phase from the [`SurfaceVelocity`] of the colliders,
+    /// and can be modified using [`CollisionHooks`].
+    ///
+    /// Default: `Vector::ZERO`
+    pub tangent_velocity: Vector,
 }
 
 impl ContactManifold {
+    /// Creates a new [`ContactManifold`] with the given contacts and local contact normals.
+    ///
+    /// The [`Friction`] and [`Restitution`] are set to their default values,
+    /// there is no anisotropic friction, and the tangent velocity is zero.
+    pub fn new(contacts: Vec<ContactData>, normal1: Vector, normal2: Vector, index: usize) -> Self {
+        Self {
+            contacts,
+            normal1,
+            normal2,
+            index,
+            friction: Friction::default(),
+            restitution: Restitution::default(),
+            anisotropic_friction1: None,
+            anisotropic_friction2: None,
+            tangent_velocity: Vector::ZERO,
+        }
+    }
+
     /// Returns the world-space contact normal pointing towards the exterior of the first entity.
     pub fn global_normal1(&self, rotation: &Rotation) -> Vector {
         rotation * self.normal1
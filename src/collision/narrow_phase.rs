@@ -16,7 +16,7 @@ use bevy::{
     ecs::{
         intern::Interned,
         schedule::{ExecutorKind, LogLevel, ScheduleBuildSettings, ScheduleLabel},
-        system::SystemParam,
+        system::{StaticSystemParam, SystemParam, SystemParamItem},
     },
     prelude::*,
 };
@@ -33,17 +33,21 @@ use bevy::{
 /// The plugin takes a collider type. This should be [`Collider`] for
 /// the vast majority of applications, but for custom collisión backends
 /// you may use any collider that implements the [`AnyCollider`] trait.
-pub struct NarrowPhasePlugin<C: AnyCollider> {
+///
+/// The plugin can also take a type implementing [`CollisionHooks`] for filtering
+/// collision pairs and modifying contacts inside the narrow phase.
+/// By default, no hooks are used.
+pub struct NarrowPhasePlugin<C: AnyCollider, H: CollisionHooks = ()> {
     schedule: Interned<dyn ScheduleLabel>,
     /// If `true`, the narrow phase will generate [`ContactConstraint`]s
     /// and add them to the [`ContactConstraints`] resource.
     ///
     /// Contact constraints are used by the [`SolverPlugin`] for solving contacts.
     generate_constraints: bool,
-    _phantom: PhantomData<C>,
+    _phantom: PhantomData<(C, H)>,
 }
 
-impl<C: AnyCollider> NarrowPhasePlugin<C> {
+impl<C: AnyCollider, H: CollisionHooks> NarrowPhasePlugin<C, H> {
     /// Creates a [`NarrowPhasePlugin`] with the schedule used for running its systems
     /// and whether it should generate [`ContactConstraint`]s for the [`ContactConstraints`] resource.
     ///
@@ -59,13 +63,16 @@ impl<C: AnyCollider> NarrowPhasePlugin<C> {
     }
 }
 
-impl<C: AnyCollider> Default for NarrowPhasePlugin<C> {
+impl<C: AnyCollider, H: CollisionHooks> Default for NarrowPhasePlugin<C, H> {
     fn default() -> Self {
         Self::new(PhysicsSchedule, true)
     }
 }
 
-impl<C: AnyCollider> Plugin for NarrowPhasePlugin<C> {
+impl<C: AnyCollider, H: CollisionHooks + 'static> Plugin for NarrowPhasePlugin<C, H>
+where
+    for<'w, 's> SystemParamItem<'w, 's, H>: CollisionHooks,
+{
     fn build(&self, app: &mut App) {
         // For some systems, we only want one instance, even if there are multiple
         // NarrowPhasePlugin instances with different collider types.
@@ -112,9 +119,7 @@ impl<C: AnyCollider> Plugin for NarrowPhasePlugin<C> {
                 self.schedule,
                 (
                     // Reset collision states.
-                    reset_collision_states
-                        .after(NarrowPhaseSet::First)
-                        .before(NarrowPhaseSet::CollectCollisions),
+                    reset_collision_states.in_set(NarrowPhaseSet::First),
                     // Remove ended collisions after contact reporting
                     remove_ended_collisions
                         .after(PhysicsStepSet::ReportContacts)
@@ -127,7 +132,7 @@ impl<C: AnyCollider> Plugin for NarrowPhasePlugin<C> {
         // Collect contacts into `Collisions`.
         app.add_systems(
             self.schedule,
-            collect_collisions::<C>
+            collect_collisions::<C, H>
                 .in_set(NarrowPhaseSet::CollectCollisions)
                 // Allowing ambiguities is required so that it's possible
                 // to have multiple collision backends at the same time.
@@ -255,12 +260,19 @@ pub enum NarrowPhaseSet {
     Last,
 }
 
-fn collect_collisions<C: AnyCollider>(
+fn collect_collisions<C: AnyCollider, H: CollisionHooks>(
     mut narrow_phase: NarrowPhase<C>,
     broad_collision_pairs: Res<BroadCollisionPairs>,
+    hooks: StaticSystemParam<H>,
     time: Res<Time>,
-) {
-    narrow_phase.update(&broad_collision_pairs, time.delta_seconds_adjusted());
+) where
+    for<'w, 's> SystemParamItem<'w, 's, H>: CollisionHooks,
+{
+    narrow_phase.update(
+        &broad_collision_pairs,
+        &hooks.into_inner(),
+        time.delta_seconds_adjusted(),
+    );
 }
 
 // TODO: It'd be nice to generate the constraint in the same parallel loop as `collect_collisions`
@@ -274,13 +286,13 @@ fn generate_constraints<C: AnyCollider>(
 ) {
     let delta_secs = time.delta_seconds_adjusted();
 
-    // TODO: Parallelize.
-    for contacts in narrow_phase.collisions.get_internal().values() {
+    // Generates the contact constraints for the given contacts and adds them to `constraints`.
+    let generate = |contacts: &Contacts, constraints: &mut Vec<ContactConstraint>| {
         let Ok([collider1, collider2]) = narrow_phase
             .collider_query
             .get_many([contacts.entity1, contacts.entity2])
         else {
-            continue;
+            return;
         };
 
         let body1_bundle = collider1
@@ -296,7 +308,7 @@ fn generate_constraints<C: AnyCollider>(
             // At least one of the bodies must be dynamic for contact constraints
             // to be generated.
             if !body1.rb.is_dynamic() && !body2.rb.is_dynamic() {
-                continue;
+                return;
             }
 
             // Use the collider's own collision margin if specified, and fall back to the body's
@@ -318,7 +330,7 @@ fn generate_constraints<C: AnyCollider>(
             // and add them to `constraints`.
             narrow_phase.generate_constraints(
                 contacts,
-                &mut constraints,
+                constraints,
                 &body1,
                 &body2,
                 &collider1,
@@ -328,6 +340,32 @@ fn generate_constraints<C: AnyCollider>(
                 delta_secs,
             );
         }
+    };
+
+    #[cfg(feature = "parallel")]
+    {
+        // The constraints of each chunk are appended in order, so the resulting order
+        // is the same as when generating the constraints serially.
+        narrow_phase
+            .collisions
+            .get_internal()
+            .values()
+            .collect::<Vec<_>>()
+            .par_splat_map(ComputeTaskPool::get(), None, |_i, chunks| {
+                let mut new_constraints = Vec::<ContactConstraint>::with_capacity(chunks.len());
+                for contacts in chunks {
+                    generate(contacts, &mut new_constraints);
+                }
+                new_constraints
+            })
+            .into_iter()
+            .for_each(|new_constraints| constraints.extend(new_constraints));
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        for contacts in narrow_phase.collisions.get_internal().values() {
+            generate(contacts, &mut constraints);
+        }
     }
 }
 
@@ -353,6 +391,10 @@ pub struct NarrowPhase<'w, 's, C: AnyCollider> {
     pub collisions: ResMut<'w, Collisions>,
     /// Configuration options for the narrow phase.
     pub config: Res<'w, NarrowPhaseConfig>,
+    #[cfg(feature = "physics-material")]
+    materials: Option<Res<'w, Assets<PhysicsMaterial>>>,
+    #[cfg(feature = "physics-material")]
+    material_pairs: Option<Res<'w, PhysicsMaterialPairs>>,
     length_unit: Res<'w, PhysicsLengthUnit>,
     // These are scaled by the length unit.
     default_speculative_margin: Local<'s, Scalar>,
@@ -362,7 +404,12 @@ pub struct NarrowPhase<'w, 's, C: AnyCollider> {
 impl<'w, 's, C: AnyCollider> NarrowPhase<'w, 's, C> {
     /// Updates the narrow phase by computing [`Contacts`] based on [`BroadCollisionPairs`]
     /// and adding them to [`Collisions`].
-    fn update(&mut self, broad_collision_pairs: &[(Entity, Entity)], delta_secs: Scalar) {
+    fn update(
+        &mut self,
+        broad_collision_pairs: &[(Entity, Entity)],
+        hooks: &impl CollisionHooks,
+        delta_secs: Scalar,
+    ) {
         // TODO: These scaled versions could be in their own resource
         //       and updated just before physics every frame.
         // Cache default margins scaled by the length unit.
@@ -384,7 +431,7 @@ impl<'w, 's, C: AnyCollider> NarrowPhase<'w, 's, C> {
                     // contact constraints for them.
                     for &(entity1, entity2) in chunks {
                         if let Some(contacts) =
-                            self.handle_entity_pair(entity1, entity2, delta_secs)
+                            self.handle_entity_pair_with_hooks(entity1, entity2, hooks, delta_secs)
                         {
                             new_collisions.push(contacts);
                         }
@@ -403,7 +450,9 @@ impl<'w, 's, C: AnyCollider> NarrowPhase<'w, 's, C> {
             // Compute contacts for this intersection pair and generate
             // contact constraints for them.
             for &(entity1, entity2) in broad_collision_pairs {
-                if let Some(contacts) = self.handle_entity_pair(entity1, entity2, delta_secs) {
+                if let Some(contacts) =
+                    self.handle_entity_pair_with_hooks(entity1, entity2, hooks, delta_secs)
+                {
                     self.collisions.insert_collision_pair(contacts);
                 }
             }
@@ -413,17 +462,50 @@ impl<'w, 's, C: AnyCollider> NarrowPhase<'w, 's, C> {
     /// Returns the [`Contacts`] between `entity1` and `entity2` if they are intersecting
     /// or expected to start intersecting within the next frame. This includes
     /// [speculative collision](dynamics::ccd#speculative-collision).
-    #[allow(clippy::too_many_arguments)]
+    ///
+    /// No [`CollisionHooks`] are called. See [`handle_entity_pair_with_hooks`](Self::handle_entity_pair_with_hooks).
     pub fn handle_entity_pair(
         &self,
         entity1: Entity,
         entity2: Entity,
         delta_secs: Scalar,
+    ) -> Option<Contacts> {
+        self.handle_entity_pair_with_hooks(entity1, entity2, &(), delta_secs)
+    }
+
+    /// Returns the [`Contacts`] between `entity1` and `entity2` if they are intersecting
+    /// or expected to start intersecting within the next frame. This includes
+    /// [speculative collision](dynamics::ccd#speculative-collision).
+    ///
+    /// The given [`CollisionHooks`] are used to filter the pair and modify the computed contacts
+    /// if either of the colliders has [`ActiveCollisionHooks`].
+    pub fn handle_entity_pair_with_hooks(
+        &self,
+        entity1: Entity,
+        entity2: Entity,
+        hooks: &impl CollisionHooks,
+        delta_secs: Scalar,
     ) -> Option<Contacts> {
         let Ok([collider1, collider2]) = self.collider_query.get_many([entity1, entity2]) else {
             return None;
         };
 
+        let active_hooks = collider1
+            .active_hooks
+            .map_or(ActiveCollisionHooks::empty(), |hooks| *hooks)
+            | collider2
+                .active_hooks
+                .map_or(ActiveCollisionHooks::empty(), |hooks| *hooks);
+
+        // Let user-defined hooks filter the pair before computing contacts.
+        if active_hooks.contains(ActiveCollisionHooks::FILTER_PAIRS)
+            && !self
+                .parallel_commands
+                .command_scope(|mut commands| hooks.filter_pairs(entity1, entity2, &mut commands))
+        {
+            return None;
+        }
+
         let body1_bundle = collider1
             .parent
             .and_then(|p| self.body_query.get(p.get()).ok());
@@ -514,7 +596,134 @@ impl<'w, 's, C: AnyCollider> NarrowPhase<'w, 's, C> {
         let max_contact_distance =
             effective_speculative_margin.max(*self.contact_tolerance) + collision_margin_sum;
 
-        self.compute_contact_pair(&collider1, &collider2, max_contact_distance)
+        let mut contacts =
+            self.compute_contact_pair(&collider1, &collider2, max_contact_distance)?;
+
+        // Get combined friction and restitution coefficients of the colliders
+        // or the bodies they are attached to.
+        let body1 = body1_bundle.as_ref().map(|(body, ..)| body);
+        let body2 = body2_bundle.as_ref().map(|(body, ..)| body);
+        let (friction1, restitution1) = self.coefficients(&collider1, body1);
+        let (friction2, restitution2) = self.coefficients(&collider2, body2);
+        let friction = friction1.combine(friction2);
+        let restitution = restitution1.combine(restitution2);
+
+        // Apply overrides for specific pairs of materials, like rubber on ice.
+        #[cfg(feature = "physics-material")]
+        let (friction, restitution) = match self.material_pair(&collider1, body1, &collider2, body2)
+        {
+            Some(pair) => (
+                pair.friction.unwrap_or(friction),
+                pair.restitution.unwrap_or(restitution),
+            ),
+            None => (friction, restitution),
+        };
+
+        // Transform anisotropic friction directions and surface velocities to world space.
+        let anisotropic_friction1 =
+            collider1
+                .anisotropic_friction
+                .map(|friction| AnisotropicFriction {
+                    direction: *collider1.rotation * friction.direction,
+                    ..*friction
+                });
+        let anisotropic_friction2 =
+            collider2
+                .anisotropic_friction
+                .map(|friction| AnisotropicFriction {
+                    direction: *collider2.rotation * friction.direction,
+                    ..*friction
+                });
+        let surface_velocity1 = collider1
+            .surface_velocity
+            .map_or(Vector::ZERO, |velocity| *collider1.rotation * velocity.0);
+        let surface_velocity2 = collider2
+            .surface_velocity
+            .map_or(Vector::ZERO, |velocity| *collider2.rotation * velocity.0);
+
+        for manifold in contacts.manifolds.iter_mut() {
+            manifold.friction = friction;
+            manifold.restitution = restitution;
+            manifold.anisotropic_friction1 = anisotropic_friction1;
+            manifold.anisotropic_friction2 = anisotropic_friction2;
+            // The second body is driven towards the surface velocity of the first body and vice versa.
+            manifold.tangent_velocity = surface_velocity1 - surface_velocity2;
+        }
+
+        // Let user-defined hooks modify or discard the contacts.
+        if active_hooks.contains(ActiveCollisionHooks::MODIFY_CONTACTS)
+            && !self
+                .parallel_commands
+                .command_scope(|mut commands| hooks.modify_contacts(&mut contacts, &mut commands))
+        {
+            return None;
+        }
+
+        Some(contacts)
+    }
+
+    /// Returns the [`Friction`] and [`Restitution`] of the given collider, falling back to the ones
+    /// of the rigid body it is attached to.
+    ///
+    /// Child colliders can have their own materials and coefficients that override the ones of the rigid body.
+    /// Physics materials take priority over the components of the same entity.
+    fn coefficients(
+        &self,
+        collider: &ColliderQueryItem<C>,
+        body: Option<&RigidBodyQueryReadOnlyItem>,
+    ) -> (Friction, Restitution) {
+        #[cfg(feature = "physics-material")]
+        let (collider_material, body_material) = (
+            self.material(collider.material),
+            body.and_then(|body| self.material(body.material)),
+        );
+        #[cfg(not(feature = "physics-material"))]
+        let (collider_material, body_material) = (None::<(Friction, Restitution)>, None);
+
+        let friction = collider_material
+            .map(|(friction, _)| friction)
+            .or(collider.friction.copied())
+            .or(body_material.map(|(friction, _)| friction))
+            .or(body.map(|body| *body.friction))
+            .unwrap_or_default();
+        let restitution = collider_material
+            .map(|(_, restitution)| restitution)
+            .or(collider.restitution.copied())
+            .or(body_material.map(|(_, restitution)| restitution))
+            .or(body.map(|body| *body.restitution))
+            .unwrap_or_default();
+
+        (friction, restitution)
+    }
+
+    /// Returns the friction and restitution of the [`PhysicsMaterial`] with the given handle,
+    /// if it has been loaded.
+    #[cfg(feature = "physics-material")]
+    fn material(
+        &self,
+        handle: Option<&Handle<PhysicsMaterial>>,
+    ) -> Option<(Friction, Restitution)> {
+        let material = self.materials.as_ref()?.get(handle?)?;
+        Some((material.friction, material.restitution))
+    }
+
+    /// Returns the [`PhysicsMaterialPair`] for the materials of the given colliders,
+    /// falling back to the materials of the rigid bodies they are attached to.
+    #[cfg(feature = "physics-material")]
+    fn material_pair(
+        &self,
+        collider1: &ColliderQueryItem<C>,
+        body1: Option<&RigidBodyQueryReadOnlyItem>,
+        collider2: &ColliderQueryItem<C>,
+        body2: Option<&RigidBodyQueryReadOnlyItem>,
+    ) -> Option<&PhysicsMaterialPair> {
+        let handle1 = collider1
+            .material
+            .or(body1.and_then(|body| body.material))?;
+        let handle2 = collider2
+            .material
+            .or(body2.and_then(|body| body.material))?;
+        self.material_pairs.as_ref()?.get(handle1, handle2)
     }
 
     /// Computes contacts between `collider1` and `collider2`.
@@ -641,17 +850,6 @@ impl<'w, 's, C: AnyCollider> NarrowPhase<'w, 's, C> {
             }
         });
 
-        // Get combined friction and restitution coefficients of the colliders
-        // or the bodies they are attached to.
-        let friction = collider1
-            .friction
-            .unwrap_or(body1.friction)
-            .combine(*collider2.friction.unwrap_or(body2.friction));
-        let restitution = collider1
-            .restitution
-            .unwrap_or(body1.restitution)
-            .combine(*collider2.restitution.unwrap_or(body2.restitution));
-
         let contact_softness = if !body1.rb.is_dynamic() || !body2.rb.is_dynamic() {
             contact_softness.non_dynamic
         } else {
@@ -672,8 +870,6 @@ impl<'w, 's, C: AnyCollider> NarrowPhase<'w, 's, C> {
                 collision_margin,
                 // TODO: Shouldn't this be the effective speculative margin?
                 *self.default_speculative_margin,
-                friction,
-                restitution,
                 contact_softness,
                 self.config.match_contacts,
                 delta_secs,
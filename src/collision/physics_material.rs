@@ -0,0 +1,378 @@
+//! [`PhysicsMaterial`] assets that bundle the surface and mass properties of colliders.
+//! Requires the `physics-material` feature, which is enabled by default.
+//!
+//! See [`PhysicsMaterialPlugin`].
+
+use crate::{prelude::*, prepare::PrepareSet};
+#[cfg(feature = "serialize")]
+use bevy::asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext};
+use bevy::{
+    ecs::{intern::Interned, schedule::ScheduleLabel},
+    prelude::*,
+    utils::HashMap,
+};
+
+/// A plugin for loading and resolving [`PhysicsMaterial`] assets.
+///
+/// The plugin registers the [`PhysicsMaterial`] asset type, initializes the [`PhysicsMaterialPairs`] resource,
+/// and keeps the [`ColliderDensity`] of colliders in sync with their materials. With the `serialize` feature,
+/// it also registers the [`PhysicsMaterialLoader`] for [RON](https://github.com/ron-rs/ron) files
+/// with the `.physics_material.ron` extension.
+///
+/// The [`Friction`] and [`Restitution`] of materials are resolved by the [narrow phase](NarrowPhasePlugin)
+/// when computing contacts.
+///
+/// Materials require the `AssetPlugin`. Without it, this plugin does nothing.
+pub struct PhysicsMaterialPlugin {
+    schedule: Interned<dyn ScheduleLabel>,
+}
+
+impl PhysicsMaterialPlugin {
+    /// Creates a [`PhysicsMaterialPlugin`] with the schedule that is used for running the [`PhysicsSchedule`].
+    ///
+    /// The default schedule is `PostUpdate`.
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+        }
+    }
+}
+
+impl Default for PhysicsMaterialPlugin {
+    fn default() -> Self {
+        Self::new(PostUpdate)
+    }
+}
+
+impl Plugin for PhysicsMaterialPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PhysicsMaterialPairs>()
+            .register_type::<PhysicsMaterial>();
+    }
+
+    fn finish(&self, app: &mut App) {
+        // The asset server is only guaranteed to exist once all plugins have been built.
+        if !app.world().contains_resource::<AssetServer>() {
+            return;
+        }
+
+        app.init_asset::<PhysicsMaterial>();
+
+        #[cfg(feature = "serialize")]
+        app.register_asset_loader(PhysicsMaterialLoader);
+
+        app.add_systems(
+            self.schedule,
+            update_material_density.in_set(PrepareSet::PreInit),
+        );
+    }
+}
+
+/// An asset that bundles the [`Friction`], [`Restitution`] and density of a surface.
+///
+/// Materials are assigned to colliders by adding a `Handle<PhysicsMaterial>` component.
+/// They can be created in code, or loaded from [RON](https://github.com/ron-rs/ron) files
+/// with the `.physics_material.ron` extension if the `serialize` feature is enabled.
+///
+/// The material takes priority over the [`Friction`] and [`Restitution`] components
+/// of the same entity, and the [`ColliderDensity`] of the collider is set to the density of the material.
+/// A material added to a rigid body is also used by its child colliders, unless they have
+/// their own material or [`Friction`] and [`Restitution`] components.
+///
+/// The coefficients of two materials are combined using their combine rules, unless
+/// an override is specified for the pair in [`PhysicsMaterialPairs`].
+///
+/// ## Example
+///
+/// A `rubber.physics_material.ron` file:
+///
+/// ```ron
+/// (
+///     friction: (dynamic_coefficient: 0.8, static_coefficient: 1.0, combine_rule: Max),
+///     restitution: (coefficient: 0.7),
+///     density: 1100.0,
+/// )
+/// ```
+///
+/// The material can then be loaded and added to colliders:
+///
+/// ```no_run
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+///     commands.spawn((
+///         RigidBody::Dynamic,
+#[cfg_attr(feature = "2d", doc = "        Collider::circle(0.5),")]
+#[cfg_attr(feature = "3d", doc = "        Collider::sphere(0.5),")]
+///         asset_server.load::<PhysicsMaterial>("rubber.physics_material.ron"),
+///     ));
+/// }
+/// ```
+#[derive(Asset, Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct PhysicsMaterial {
+    /// The friction coefficients and their combine rule.
+    pub friction: Friction,
+    /// The coefficient of restitution and its combine rule.
+    pub restitution: Restitution,
+    /// The density of the material, used as the [`ColliderDensity`] of colliders.
+    pub density: Scalar,
+}
+
+impl PhysicsMaterial {
+    /// Creates a new [`PhysicsMaterial`] with the given friction, restitution and density.
+    pub fn new(
+        friction: impl Into<Friction>,
+        restitution: impl Into<Restitution>,
+        density: Scalar,
+    ) -> Self {
+        Self {
+            friction: friction.into(),
+            restitution: restitution.into(),
+            density,
+        }
+    }
+}
+
+impl Default for PhysicsMaterial {
+    fn default() -> Self {
+        Self {
+            friction: Friction::default(),
+            restitution: Restitution::default(),
+            density: 1.0,
+        }
+    }
+}
+
+/// Overrides for the combined [`Friction`] and [`Restitution`] of a specific pair of [`PhysicsMaterial`]s.
+///
+/// See [`PhysicsMaterialPairs`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PhysicsMaterialPair {
+    /// The friction used for contacts between the materials.
+    /// If `None`, the friction of the materials is combined normally.
+    pub friction: Option<Friction>,
+    /// The restitution used for contacts between the materials.
+    /// If `None`, the restitution of the materials is combined normally.
+    pub restitution: Option<Restitution>,
+}
+
+impl PhysicsMaterialPair {
+    /// Creates a new [`PhysicsMaterialPair`] that overrides the friction of the materials.
+    pub fn with_friction(&self, friction: impl Into<Friction>) -> Self {
+        Self {
+            friction: Some(friction.into()),
+            ..*self
+        }
+    }
+
+    /// Creates a new [`PhysicsMaterialPair`] that overrides the restitution of the materials.
+    pub fn with_restitution(&self, restitution: impl Into<Restitution>) -> Self {
+        Self {
+            restitution: Some(restitution.into()),
+            ..*self
+        }
+    }
+}
+
+/// A resource that stores overrides for the combined [`Friction`] and [`Restitution`]
+/// of specific pairs of [`PhysicsMaterial`]s, like rubber on ice.
+///
+/// The order of the materials in a pair doesn't matter.
+///
+/// ## Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn setup(mut materials: ResMut<Assets<PhysicsMaterial>>, mut pairs: ResMut<PhysicsMaterialPairs>) {
+///     let rubber = materials.add(PhysicsMaterial::new(0.9, 0.7, 1100.0));
+///     let ice = materials.add(PhysicsMaterial::new(0.05, 0.1, 900.0));
+///
+///     // Rubber grips ice better than the combined coefficients would suggest.
+///     pairs.insert(&rubber, &ice, PhysicsMaterialPair::default().with_friction(0.15));
+/// }
+/// ```
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+pub struct PhysicsMaterialPairs(
+    HashMap<(AssetId<PhysicsMaterial>, AssetId<PhysicsMaterial>), PhysicsMaterialPair>,
+);
+
+impl PhysicsMaterialPairs {
+    /// Inserts an override for the given pair of materials, returning the previous override if there was one.
+    pub fn insert(
+        &mut self,
+        material1: impl Into<AssetId<PhysicsMaterial>>,
+        material2: impl Into<AssetId<PhysicsMaterial>>,
+        pair: PhysicsMaterialPair,
+    ) -> Option<PhysicsMaterialPair> {
+        self.0
+            .insert(Self::key(material1.into(), material2.into()), pair)
+    }
+
+    /// Returns the override for the given pair of materials, if there is one.
+    pub fn get(
+        &self,
+        material1: impl Into<AssetId<PhysicsMaterial>>,
+        material2: impl Into<AssetId<PhysicsMaterial>>,
+    ) -> Option<&PhysicsMaterialPair> {
+        self.0.get(&Self::key(material1.into(), material2.into()))
+    }
+
+    /// Removes the override for the given pair of materials, returning it if there was one.
+    pub fn remove(
+        &mut self,
+        material1: impl Into<AssetId<PhysicsMaterial>>,
+        material2: impl Into<AssetId<PhysicsMaterial>>,
+    ) -> Option<PhysicsMaterialPair> {
+        self.0
+            .remove(&Self::key(material1.into(), material2.into()))
+    }
+
+    /// Orders the pair so that the lookup doesn't depend on the order of the materials.
+    fn key(
+        material1: AssetId<PhysicsMaterial>,
+        material2: AssetId<PhysicsMaterial>,
+    ) -> (AssetId<PhysicsMaterial>, AssetId<PhysicsMaterial>) {
+        if material1 <= material2 {
+            (material1, material2)
+        } else {
+            (material2, material1)
+        }
+    }
+}
+
+/// An [`AssetLoader`] for [`PhysicsMaterial`]s stored in [RON](https://github.com/ron-rs/ron) files
+/// with the `.physics_material.ron` extension.
+#[cfg(feature = "serialize")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PhysicsMaterialLoader;
+
+/// An error that can occur when loading a [`PhysicsMaterial`] with the [`PhysicsMaterialLoader`].
+#[cfg(feature = "serialize")]
+#[derive(Debug)]
+pub enum PhysicsMaterialLoaderError {
+    /// The file could not be read.
+    Io(std::io::Error),
+    /// The file is not a valid RON representation of a [`PhysicsMaterial`].
+    Ron(ron::error::SpannedError),
+}
+
+#[cfg(feature = "serialize")]
+impl std::fmt::Display for PhysicsMaterialLoaderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "could not read physics material: {error}"),
+            Self::Ron(error) => write!(f, "could not parse physics material: {error}"),
+        }
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl std::error::Error for PhysicsMaterialLoaderError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Ron(error) => Some(error),
+        }
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl From<std::io::Error> for PhysicsMaterialLoaderError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl From<ron::error::SpannedError> for PhysicsMaterialLoaderError {
+    fn from(error: ron::error::SpannedError) -> Self {
+        Self::Ron(error)
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl AssetLoader for PhysicsMaterialLoader {
+    type Asset = PhysicsMaterial;
+    type Settings = ();
+    type Error = PhysicsMaterialLoaderError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a Self::Settings,
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["physics_material.ron"]
+    }
+}
+
+/// Sets the [`ColliderDensity`] of colliders to the density of their [`PhysicsMaterial`]
+/// when the handle is changed or the asset is loaded or modified.
+fn update_material_density(
+    mut commands: Commands,
+    colliders: Query<(
+        Entity,
+        Ref<Handle<PhysicsMaterial>>,
+        Option<&ColliderDensity>,
+    )>,
+    materials: Res<Assets<PhysicsMaterial>>,
+    mut asset_events: EventReader<AssetEvent<PhysicsMaterial>>,
+) {
+    let changed_materials: Vec<AssetId<PhysicsMaterial>> = asset_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Added { id } | AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+
+    for (entity, handle, density) in &colliders {
+        if !handle.is_changed() && !changed_materials.contains(&handle.id()) {
+            continue;
+        }
+        let Some(material) = materials.get(&*handle) else {
+            continue;
+        };
+        // The density is inserted before colliders are initialized,
+        // so that their initial mass properties use the density of the material.
+        if density != Some(&ColliderDensity(material.density)) {
+            commands
+                .entity(entity)
+                .try_insert(ColliderDensity(material.density));
+        }
+    }
+}
+
+#[cfg(all(test, feature = "serialize"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_partial_material() {
+        let material: PhysicsMaterial = ron::de::from_str(
+            "(friction: (dynamic_coefficient: 0.8, combine_rule: Max), density: 1100.0)",
+        )
+        .unwrap();
+
+        assert_eq!(material.friction.dynamic_coefficient, 0.8);
+        assert_eq!(material.friction.static_coefficient, 0.3);
+        assert_eq!(material.friction.combine_rule, CoefficientCombine::Max);
+        assert_eq!(material.restitution, Restitution::default());
+        assert_eq!(material.density, 1100.0);
+    }
+}
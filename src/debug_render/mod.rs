@@ -128,6 +128,7 @@ impl Plugin for PhysicsDebugPlugin {
                     debug_render_joints::<RevoluteJoint>,
                     #[cfg(feature = "3d")]
                     debug_render_joints::<SphericalJoint>,
+                    debug_render_joints::<GenericJoint>,
                     debug_render_raycasts,
                     #[cfg(all(
                         feature = "default-collider",
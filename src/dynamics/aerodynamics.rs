@@ -0,0 +1,460 @@
+//! Aerodynamic drag and lift for bodies moving through air.
+//!
+//! [`LinearDamping`] and [`AngularDamping`] slow bodies down exponentially, which is simple and stable,
+//! but doesn't depend on the size or shape of the body. The [`Aerodynamics`] component instead computes
+//! [drag](https://en.wikipedia.org/wiki/Drag_equation) that grows with the square of the velocity
+//! and the cross-sectional area of the [`Collider`] facing the airflow, and [lift](https://en.wikipedia.org/wiki/Lift_(force))
+//! from [lift surfaces](LiftSurface) like wings, fins and rudders.
+//!
+//! The forces are computed every substep from the velocity of the body relative to the [wind](Atmosphere::wind),
+//! stored in the [`AerodynamicForces`] component, and applied together with the [`ExternalForce`]
+//! and [`ExternalTorque`] of the body when integrating velocities.
+//!
+//! ## Example
+//!
+//! ```
+#![cfg_attr(feature = "2d", doc = "use avian2d::{math::*, prelude::*};")]
+#![cfg_attr(feature = "3d", doc = "use avian3d::{math::*, prelude::*};")]
+//! use bevy::prelude::*;
+//!
+//! fn setup(mut commands: Commands) {
+//!     // A glider with a main wing and a horizontal stabilizer behind it.
+//!     commands.spawn((
+//!         RigidBody::Dynamic,
+#![cfg_attr(feature = "2d", doc = "        Collider::rectangle(4.0, 0.5),")]
+#![cfg_attr(feature = "3d", doc = "        Collider::cuboid(4.0, 0.5, 0.5),")]
+//!         ColliderDensity(0.1),
+//!         Aerodynamics::new(0.3)
+//!             .with_lift_surface(LiftSurface::new(Vector::X * 0.3, Vector::X, Vector::Y, 2.0))
+//!             .with_lift_surface(LiftSurface::new(Vector::NEG_X * 1.8, Vector::X, Vector::Y, 0.5)),
+//!         LinearVelocity(Vector::X * 10.0),
+//!     ));
+//! }
+//! ```
+
+use crate::{
+    dynamics::{integrator::IntegrationSet, solver::SubstepSolverSet},
+    prelude::*,
+    prepare::PrepareSet,
+};
+use bevy::{
+    ecs::{intern::Interned, schedule::ScheduleLabel},
+    prelude::*,
+};
+
+/// A plugin for applying aerodynamic drag and lift to bodies with the [`Aerodynamics`] component.
+///
+/// The forces are computed every substep in [`IntegrationSet::Velocity`], before the velocities
+/// of bodies are integrated.
+pub struct AerodynamicsPlugin {
+    schedule: Interned<dyn ScheduleLabel>,
+}
+
+impl AerodynamicsPlugin {
+    /// Creates an [`AerodynamicsPlugin`] with the schedule that is used for running the [`PhysicsSchedule`].
+    ///
+    /// The default schedule is `PostUpdate`.
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+        }
+    }
+}
+
+impl Default for AerodynamicsPlugin {
+    fn default() -> Self {
+        Self::new(PostUpdate)
+    }
+}
+
+impl Plugin for AerodynamicsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Atmosphere>()
+            .register_type::<Atmosphere>()
+            .register_type::<Aerodynamics>()
+            .register_type::<LiftSurface>()
+            .register_type::<LiftCurve>()
+            .register_type::<AerodynamicForces>();
+
+        app.add_systems(self.schedule, init_aerodynamics.in_set(PrepareSet::PreInit));
+
+        // Get the `SubstepSchedule`, and panic if it doesn't exist.
+        let substeps = app
+            .get_schedule_mut(SubstepSchedule)
+            .expect("add SubstepSchedule first");
+
+        substeps.add_systems(
+            compute_aerodynamic_forces
+                .in_set(IntegrationSet::Velocity)
+                .before(super::integrator::integrate_velocities)
+                .before(SubstepSolverSet::WarmStart),
+        );
+    }
+}
+
+/// A resource for the air that [aerodynamic](Aerodynamics) bodies move through.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Resource, PartialEq)]
+pub struct Atmosphere {
+    /// The density of the air.
+    ///
+    /// `1.225` by default, which is the density of air in kg/m^3 at sea level.
+    /// Note that the default [`ColliderDensity`] is `1.0`, which is much lighter than most real objects,
+    /// so drag will slow bodies with the default density down very quickly.
+    pub density: Scalar,
+    /// The velocity of the air.
+    ///
+    /// Zero by default.
+    pub wind: Vector,
+}
+
+impl Default for Atmosphere {
+    fn default() -> Self {
+        Self {
+            density: 1.225,
+            wind: Vector::ZERO,
+        }
+    }
+}
+
+/// Enables aerodynamic drag and lift for a [rigid body](RigidBody). See the [module-level documentation](self).
+///
+/// The drag force is `0.5 * density * speed^2 * drag_coefficient * area`, where `speed` is the speed
+/// of the body relative to the air, and `area` is the [reference area](Self::reference_area),
+/// or the area of the [`Collider`] of the body projected along the direction of the airflow.
+#[derive(Component, Clone, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub struct Aerodynamics {
+    /// The drag coefficient of the body. Typical values are `0.47` for a sphere and `1.05` for a cube.
+    ///
+    /// `0.5` by default.
+    pub drag_coefficient: Scalar,
+    /// The area facing the airflow. In 2D, the area is a length.
+    ///
+    /// If `None`, the area is computed from the [`Collider`] on the entity of the body,
+    /// which matches the shape of the body more closely, but is more expensive.
+    /// Bodies without a collider on the same entity only have drag if the area is set.
+    pub reference_area: Option<Scalar>,
+    /// The surfaces of the body that generate lift, like wings and fins.
+    pub lift_surfaces: Vec<LiftSurface>,
+}
+
+impl Default for Aerodynamics {
+    fn default() -> Self {
+        Self {
+            drag_coefficient: 0.5,
+            reference_area: None,
+            lift_surfaces: vec![],
+        }
+    }
+}
+
+impl Aerodynamics {
+    /// Creates a new [`Aerodynamics`] component with the given drag coefficient.
+    pub fn new(drag_coefficient: Scalar) -> Self {
+        Self {
+            drag_coefficient,
+            ..default()
+        }
+    }
+
+    /// Sets the area facing the airflow, instead of computing it from the [`Collider`] of the body.
+    pub fn with_reference_area(mut self, area: Scalar) -> Self {
+        self.reference_area = Some(area);
+        self
+    }
+
+    /// Adds a surface that generates lift, like a wing or a fin.
+    pub fn with_lift_surface(mut self, surface: LiftSurface) -> Self {
+        self.lift_surfaces.push(surface);
+        self
+    }
+}
+
+/// A surface of an [aerodynamic](Aerodynamics) body that generates lift, like a wing, a fin or a rudder.
+///
+/// The surface is defined in the local space of the body by its [chord](Self::chord),
+/// the forward direction from the trailing edge to the leading edge,
+/// and its [normal](Self::normal), the direction of lift at a positive angle of attack.
+/// The angle of attack is the angle between the chord and the airflow, and the [lift curve](Self::lift_curve)
+/// maps it to coefficients for lift and drag.
+///
+/// Lift is perpendicular to the airflow, and the drag of the surface is opposite to the velocity of the surface.
+/// Both are applied at the [position](Self::position) of the surface, so surfaces behind the center of mass
+/// also turn the body into the airflow, like the tail of an airplane or the fins of an arrow.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct LiftSurface {
+    /// The position of the surface in the local space of the body.
+    pub position: Vector,
+    /// The forward direction of the surface in the local space of the body.
+    pub chord: Vector,
+    /// The direction of lift at a positive angle of attack in the local space of the body.
+    /// Should be perpendicular to the [chord](Self::chord).
+    pub normal: Vector,
+    /// The area of the surface. In 2D, the area is a length.
+    pub area: Scalar,
+    /// The curve mapping the angle of attack to coefficients for lift and drag.
+    pub lift_curve: LiftCurve,
+}
+
+impl LiftSurface {
+    /// Creates a new [`LiftSurface`] with the given position, chord direction, normal, and area,
+    /// using the default [`LiftCurve`].
+    pub fn new(position: Vector, chord: Vector, normal: Vector, area: Scalar) -> Self {
+        Self {
+            position,
+            chord: chord.normalize_or_zero(),
+            normal: normal.normalize_or_zero(),
+            area,
+            lift_curve: LiftCurve::default(),
+        }
+    }
+
+    /// Sets the curve mapping the angle of attack to coefficients for lift and drag.
+    pub fn with_lift_curve(mut self, lift_curve: LiftCurve) -> Self {
+        self.lift_curve = lift_curve;
+        self
+    }
+}
+
+/// A curve mapping the angle of attack of a [`LiftSurface`] to coefficients for lift and drag.
+///
+/// Below the [stall angle](Self::stall_angle), the lift coefficient grows linearly with the angle of attack.
+/// After the stall angle, the surface loses lift, and behaves like a flat plate once the angle of attack
+/// is twice the stall angle. The drag coefficient is the [parasitic drag](Self::parasitic_drag)
+/// plus the drag of a flat plate, so it is lowest when the airflow is parallel to the surface.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct LiftCurve {
+    /// The growth of the lift coefficient per radian of the angle of attack before the stall.
+    ///
+    /// `2.0 * PI` by default, which is the theoretical value for a thin airfoil.
+    pub lift_slope: Scalar,
+    /// The angle of attack in radians at which the surface stalls.
+    ///
+    /// `0.26` by default, which is about 15 degrees.
+    pub stall_angle: Scalar,
+    /// The drag coefficient when the airflow is parallel to the surface.
+    ///
+    /// `0.02` by default.
+    pub parasitic_drag: Scalar,
+}
+
+impl Default for LiftCurve {
+    fn default() -> Self {
+        Self {
+            lift_slope: 2.0 * PI,
+            stall_angle: 0.26,
+            parasitic_drag: 0.02,
+        }
+    }
+}
+
+impl LiftCurve {
+    /// Creates a new [`LiftCurve`] with the given lift slope, stall angle and parasitic drag.
+    pub const fn new(lift_slope: Scalar, stall_angle: Scalar, parasitic_drag: Scalar) -> Self {
+        Self {
+            lift_slope,
+            stall_angle,
+            parasitic_drag,
+        }
+    }
+
+    /// Evaluates the lift and drag coefficients at the given angle of attack in radians,
+    /// in the range `[-PI, PI]`.
+    pub fn evaluate(&self, angle_of_attack: Scalar) -> (Scalar, Scalar) {
+        let (sin, cos) = angle_of_attack.sin_cos();
+        let flat_plate_lift = 2.0 * sin * cos;
+        let drag = self.parasitic_drag + 2.0 * sin * sin;
+
+        let angle = angle_of_attack.abs();
+        let lift = if angle <= self.stall_angle {
+            self.lift_slope * angle_of_attack
+        } else if angle <= 2.0 * self.stall_angle {
+            // Blend from the lift at the stall angle to the lift of a flat plate.
+            let t = (angle - self.stall_angle) / self.stall_angle;
+            let stall_lift = self.lift_slope * self.stall_angle * angle_of_attack.signum();
+            stall_lift + (flat_plate_lift - stall_lift) * t
+        } else {
+            flat_plate_lift
+        };
+
+        (lift, drag)
+    }
+}
+
+/// The aerodynamic forces applied to a body with [`Aerodynamics`] in the last substep.
+/// Added automatically for bodies with [`Aerodynamics`].
+///
+/// The forces are applied together with the [`ExternalForce`] and [`ExternalTorque`] of the body,
+/// so this shouldn't be modified directly.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub struct AerodynamicForces {
+    /// The total drag force, including the drag of the lift surfaces.
+    pub drag: Vector,
+    /// The total lift force.
+    pub lift: Vector,
+    /// The torque caused by forces applied at the lift surfaces.
+    pub torque: Torque,
+}
+
+impl AerodynamicForces {
+    /// Returns the sum of the drag and lift forces.
+    pub fn force(&self) -> Vector {
+        self.drag + self.lift
+    }
+
+    /// Applies the force at the given world-space point, also adding torque.
+    fn apply_at_point(
+        &mut self,
+        drag: Vector,
+        lift: Vector,
+        point: Vector,
+        center_of_mass: Vector,
+    ) {
+        self.drag += drag;
+        self.lift += lift;
+        #[cfg(feature = "2d")]
+        {
+            self.torque += (point - center_of_mass).perp_dot(drag + lift);
+        }
+        #[cfg(feature = "3d")]
+        {
+            self.torque += (point - center_of_mass).cross(drag + lift);
+        }
+    }
+}
+
+fn init_aerodynamics(
+    mut commands: Commands,
+    bodies: Query<Entity, (Added<Aerodynamics>, Without<AerodynamicForces>)>,
+) {
+    for entity in &bodies {
+        commands.entity(entity).insert(AerodynamicForces::default());
+    }
+}
+
+/// Computes the [`AerodynamicForces`] of bodies with [`Aerodynamics`].
+#[allow(clippy::type_complexity)]
+#[cfg_attr(not(feature = "default-collider"), allow(unused_variables))]
+fn compute_aerodynamic_forces(
+    mut bodies: Query<
+        (
+            Entity,
+            &RigidBody,
+            &Aerodynamics,
+            &mut AerodynamicForces,
+            &Position,
+            &Rotation,
+            &CenterOfMass,
+            &LinearVelocity,
+            &AngularVelocity,
+            &InverseMass,
+        ),
+        Without<Sleeping>,
+    >,
+    #[cfg(feature = "default-collider")] colliders: Query<&Collider>,
+    atmosphere: Res<Atmosphere>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for (
+        entity,
+        rb,
+        aerodynamics,
+        mut aerodynamic_forces,
+        position,
+        rotation,
+        center_of_mass,
+        lin_vel,
+        ang_vel,
+        inv_mass,
+    ) in &mut bodies
+    {
+        let mut forces = AerodynamicForces::default();
+
+        if !rb.is_dynamic() {
+            *aerodynamic_forces = forces;
+            continue;
+        }
+
+        let world_center_of_mass = position.0 + *rotation * center_of_mass.0;
+
+        // Quadratic drag for the body as a whole, opposite to the velocity relative to the air.
+        let relative_velocity = lin_vel.0 - atmosphere.wind;
+        let speed = relative_velocity.length();
+        if speed > Scalar::EPSILON {
+            let direction = relative_velocity / speed;
+            #[cfg(feature = "default-collider")]
+            let area = aerodynamics.reference_area.or_else(|| {
+                colliders
+                    .get(entity)
+                    .ok()
+                    .and_then(|collider| collider.projected_area(*rotation, direction))
+            });
+            #[cfg(not(feature = "default-collider"))]
+            let area = aerodynamics.reference_area;
+            if let Some(area) = area {
+                let mut drag =
+                    0.5 * atmosphere.density * speed * speed * aerodynamics.drag_coefficient * area;
+
+                // Drag can only slow the body down. Clamp it so that it can't reverse the velocity
+                // in a single substep for light bodies moving at high speeds.
+                if inv_mass.0 > 0.0 {
+                    drag = drag.min(speed / (inv_mass.0 * delta_secs));
+                }
+
+                forces.drag -= direction * drag;
+            }
+        }
+
+        // Lift and drag for each lift surface, applied at the position of the surface.
+        for surface in aerodynamics.lift_surfaces.iter() {
+            let point = position.0 + *rotation * surface.position;
+            #[cfg(feature = "2d")]
+            let velocity = lin_vel.0 + ang_vel.0 * (point - world_center_of_mass).perp();
+            #[cfg(feature = "3d")]
+            let velocity = lin_vel.0 + ang_vel.0.cross(point - world_center_of_mass);
+
+            let chord = *rotation * surface.chord;
+            let normal = *rotation * surface.normal;
+
+            // Ignore the velocity along the span of the surface, which doesn't generate lift.
+            let relative_velocity = velocity - atmosphere.wind;
+            let forward_speed = relative_velocity.dot(chord);
+            let normal_speed = relative_velocity.dot(normal);
+            let planar_velocity = chord * forward_speed + normal * normal_speed;
+            let speed_squared = planar_velocity.length_squared();
+            if speed_squared <= Scalar::EPSILON {
+                continue;
+            }
+
+            let direction = planar_velocity / speed_squared.sqrt();
+            let angle_of_attack = (-normal_speed).atan2(forward_speed);
+            let (lift_coefficient, drag_coefficient) = surface.lift_curve.evaluate(angle_of_attack);
+            let dynamic_pressure = 0.5 * atmosphere.density * speed_squared * surface.area;
+
+            // Lift is perpendicular to the airflow, on the side of the normal.
+            let lift_direction = (normal - direction * normal.dot(direction)).normalize_or_zero();
+            let lift = lift_direction * lift_coefficient * dynamic_pressure;
+            let drag = -direction * drag_coefficient * dynamic_pressure;
+
+            forces.apply_at_point(drag, lift, point, world_center_of_mass);
+        }
+
+        *aerodynamic_forces = forces;
+    }
+}
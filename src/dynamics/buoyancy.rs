@@ -0,0 +1,243 @@
+//! Buoyancy and drag for bodies submerged in fluids like water.
+//!
+//! A [`FluidVolume`] turns a [`Sensor`] collider into a body of fluid. Every physics step, the submerged volume
+//! of each collider overlapping the fluid and the center of the submerged volume are computed
+//! using [`Collider::volume_below_plane`]. The rigid body of the collider is then pushed up by
+//! [buoyancy](https://en.wikipedia.org/wiki/Buoyancy) equal to the weight of the displaced fluid,
+//! and slowed down by drag relative to the [current](FluidVolume::current) of the fluid.
+//! The forces are applied at the center of the submerged volume, so floating bodies also right themselves.
+//! Buoyancy opposes the [`LocalGravity`] of each body, so it also works with
+//! [gravity fields](super::integrator::gravity_fields).
+//!
+//! The surface of the fluid is at the top of the fluid collider's bounding box, opposite to the [`LocalGravity`]
+//! of the body, and the fluid is treated as extending down from the surface. This works well for box-shaped volumes of water,
+//! but the fluid collider only determines which colliders are affected, not how much of them is submerged
+//! below the surface.
+//!
+//! Sleeping bodies are woken up when they start overlapping a fluid volume, or when the fluid has a
+//! [current](FluidVolume::current) that would push them. Bodies floating at rest in still fluid can fall asleep.
+//!
+//! ## Example
+//!
+//! ```
+#![cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#![cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+//! use bevy::prelude::*;
+//!
+//! fn setup(mut commands: Commands) {
+//!     // A pool of water with a surface at y = 0.
+//!     commands.spawn((
+//!         FluidVolume::new(1.0).with_drag(0.5, 0.5),
+#![cfg_attr(feature = "2d", doc = "        Collider::rectangle(20.0, 10.0),")]
+#![cfg_attr(feature = "3d", doc = "        Collider::cuboid(20.0, 10.0, 20.0),")]
+//!         Sensor,
+//!         Transform::from_xyz(0.0, -5.0, 0.0),
+//!     ));
+//!
+//!     // A crate that floats half submerged, because its density is half of the density of water.
+//!     commands.spawn((
+//!         RigidBody::Dynamic,
+#![cfg_attr(feature = "2d", doc = "        Collider::rectangle(1.0, 1.0),")]
+#![cfg_attr(feature = "3d", doc = "        Collider::cuboid(1.0, 1.0, 1.0),")]
+//!         ColliderDensity(0.5),
+//!         Transform::from_xyz(0.0, 2.0, 0.0),
+//!     ));
+//! }
+//! ```
+
+use crate::{collision::narrow_phase::NarrowPhaseSet, prelude::*};
+use bevy::prelude::*;
+
+/// A plugin for applying buoyancy and drag to bodies submerged in a [`FluidVolume`].
+///
+/// The forces are computed at the end of the narrow phase in [`NarrowPhaseSet::Last`],
+/// once the [`Collisions`] with the fluid volumes are known.
+pub struct BuoyancyPlugin;
+
+impl Plugin for BuoyancyPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<FluidVolume>();
+
+        // Get the `PhysicsSchedule`, and panic if it doesn't exist.
+        let physics = app
+            .get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first");
+
+        physics.add_systems(
+            apply_buoyancy
+                .in_set(NarrowPhaseSet::Last)
+                .after(GravityFieldSet),
+        );
+    }
+}
+
+/// A volume of fluid, like water, that applies buoyancy and drag to rigid bodies submerged in it.
+/// See the [module-level documentation](self).
+///
+/// The entity should also have a [`Collider`] and the [`Sensor`] component.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub struct FluidVolume {
+    /// The density of the fluid. Bodies with a lower [density](ColliderDensity) float,
+    /// and bodies with a higher density sink.
+    ///
+    /// `1.0` by default, which is the same as the default [`ColliderDensity`].
+    pub density: Scalar,
+    /// The linear drag coefficient. The drag force is the product of this coefficient,
+    /// the mass of the displaced fluid, and the velocity of the body relative to the fluid.
+    ///
+    /// `1.0` by default.
+    pub linear_drag: Scalar,
+    /// The angular drag coefficient. The drag torque is the product of this coefficient,
+    /// the mass of the displaced fluid, and the angular velocity of the body.
+    ///
+    /// `1.0` by default.
+    pub angular_drag: Scalar,
+    /// The velocity of the fluid, like the current of a river. Drag pushes submerged bodies
+    /// towards this velocity.
+    ///
+    /// Zero by default.
+    pub current: Vector,
+}
+
+impl Default for FluidVolume {
+    fn default() -> Self {
+        Self {
+            density: 1.0,
+            linear_drag: 1.0,
+            angular_drag: 1.0,
+            current: Vector::ZERO,
+        }
+    }
+}
+
+impl FluidVolume {
+    /// Creates a new [`FluidVolume`] with the given density.
+    pub fn new(density: Scalar) -> Self {
+        Self {
+            density,
+            ..default()
+        }
+    }
+
+    /// Sets the linear and angular drag coefficients.
+    pub fn with_drag(mut self, linear_drag: Scalar, angular_drag: Scalar) -> Self {
+        self.linear_drag = linear_drag;
+        self.angular_drag = angular_drag;
+        self
+    }
+
+    /// Sets the velocity of the fluid.
+    pub fn with_current(mut self, current: Vector) -> Self {
+        self.current = current;
+        self
+    }
+}
+
+/// Applies buoyancy and drag to the rigid bodies of colliders overlapping [fluid volumes](FluidVolume).
+#[allow(clippy::type_complexity)]
+fn apply_buoyancy(
+    fluids: Query<(Entity, &FluidVolume, &Collider, &Position, &Rotation)>,
+    colliders: Query<(&Collider, &Position, &Rotation, &ColliderParent), Without<FluidVolume>>,
+    mut bodies: Query<(
+        &RigidBody,
+        &Position,
+        &Rotation,
+        &CenterOfMass,
+        &LinearVelocity,
+        &AngularVelocity,
+        &mut ExternalImpulse,
+        &mut ExternalAngularImpulse,
+        &LocalGravity,
+        Option<&GravityScale>,
+        &mut TimeSleeping,
+        Has<Sleeping>,
+    )>,
+    collisions: Res<Collisions>,
+    mut commands: Commands,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for (fluid_entity, fluid, fluid_collider, fluid_position, fluid_rotation) in &fluids {
+        let aabb = fluid_collider.aabb(fluid_position.0, *fluid_rotation);
+
+        for contacts in collisions.collisions_with_entity(fluid_entity) {
+            let entity = if contacts.entity1 == fluid_entity {
+                contacts.entity2
+            } else {
+                contacts.entity1
+            };
+            let Ok((collider, position, rotation, collider_parent)) = colliders.get(entity) else {
+                continue;
+            };
+            let Ok((
+                rb,
+                body_position,
+                body_rotation,
+                center_of_mass,
+                lin_vel,
+                ang_vel,
+                mut impulse,
+                mut angular_impulse,
+                local_gravity,
+                gravity_scale,
+                mut time_sleeping,
+                is_sleeping,
+            )) = bodies.get_mut(collider_parent.get())
+            else {
+                continue;
+            };
+
+            if !rb.is_dynamic() {
+                continue;
+            }
+
+            if is_sleeping {
+                // Wake up the body if it just entered the fluid or the current would push it.
+                if contacts.during_previous_frame && fluid.current == Vector::ZERO {
+                    continue;
+                }
+                commands.entity(collider_parent.get()).remove::<Sleeping>();
+                time_sleeping.0 = 0.0;
+            }
+
+            // The surface of the fluid is opposite to the gravity of the body.
+            let gravity = local_gravity.0 * gravity_scale.map_or(1.0, |scale| scale.0);
+            let up = (-gravity).normalize_or_zero();
+            let surface_point = aabb.center() + up * (aabb.size() / 2.0).dot(up.abs());
+
+            let Some((volume, center)) =
+                collider.volume_below_plane(*position, *rotation, surface_point, up)
+            else {
+                continue;
+            };
+            if volume <= 0.0 {
+                continue;
+            }
+
+            let displaced_mass = fluid.density * volume;
+
+            // Compute the velocity of the body at the center of the submerged volume.
+            let world_center_of_mass = body_position.0 + *body_rotation * center_of_mass.0;
+            let offset = center - world_center_of_mass;
+            #[cfg(feature = "2d")]
+            let velocity = lin_vel.0 + ang_vel.0 * offset.perp();
+            #[cfg(feature = "3d")]
+            let velocity = lin_vel.0 + ang_vel.0.cross(offset);
+
+            let buoyancy = -gravity * displaced_mass;
+            let drag = -fluid.linear_drag * displaced_mass * (velocity - fluid.current);
+
+            impulse.apply_impulse_at_point(
+                (buoyancy + drag) * delta_secs,
+                center,
+                world_center_of_mass,
+            );
+            angular_impulse
+                .apply_impulse(-fluid.angular_drag * displaced_mass * ang_vel.0 * delta_secs);
+        }
+    }
+}
@@ -0,0 +1,603 @@
+//! A kinematic character controller.
+//!
+//! A [`CharacterController`] moves a [kinematic](RigidBody::Kinematic) body with a [`Collider`]
+//! according to its [`CharacterVelocity`], sliding along obstacles instead of passing through them.
+//!
+//! The controller supports:
+//!
+//! - Move-and-slide with a configurable number of iterations
+//! - Walking up slopes up to a [maximum angle](CharacterController::max_slope_angle)
+//! - [Stepping up](CharacterController::max_step_height) onto small obstacles like stairs
+//! - [Snapping to the ground](CharacterController::snap_to_ground) when moving down slopes and stairs
+//! - Moving along with the platform the character is standing on
+//! - Resolving overlap with other colliders
+//!
+//! The results of the movement, like whether the character is grounded and the normals
+//! of the surfaces it collided with, are stored in the [`CharacterControllerState`] component.
+//!
+//! The movement is computed at the start of each physics step using [shapecasts](spatial_query#shapecasting)
+//! against the colliders in the world. The controller doesn't move the body directly, but sets its
+//! [`LinearVelocity`] so that the body reaches the computed position by the end of the step.
+//! This way, dynamic bodies still receive an accurate collision response when the character pushes them.
+//!
+//! Gravity and other forces are not applied by the controller, so they should be included
+//! in the [`CharacterVelocity`].
+//!
+//! ## Example
+//!
+//! ```
+#![cfg_attr(feature = "2d", doc = "use avian2d::{math::*, prelude::*};")]
+#![cfg_attr(feature = "3d", doc = "use avian3d::{math::*, prelude::*};")]
+//! use bevy::prelude::*;
+//!
+//! fn setup(mut commands: Commands) {
+//!     commands.spawn((
+//!         RigidBody::Kinematic,
+//!         Collider::capsule(0.4, 1.0),
+//!         CharacterController::default().with_max_step_height(0.3),
+//!     ));
+//! }
+//!
+//! fn apply_gravity(
+//!     mut characters: Query<(&mut CharacterVelocity, &CharacterControllerState)>,
+//!     time: Res<Time>,
+//! ) {
+//!     for (mut velocity, state) in &mut characters {
+//!         if state.grounded {
+//!             // Stop falling when standing on the ground.
+//!             velocity.y = velocity.y.max(0.0);
+//!         } else {
+//!             velocity.y -= 9.81 * time.delta_seconds_f64().adjust_precision();
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::{prelude::*, prepare::PrepareSet};
+use bevy::{
+    ecs::{intern::Interned, schedule::ScheduleLabel},
+    prelude::*,
+};
+
+/// A plugin for moving [character controllers](self).
+pub struct CharacterControllerPlugin {
+    schedule: Interned<dyn ScheduleLabel>,
+}
+
+impl CharacterControllerPlugin {
+    /// Creates a [`CharacterControllerPlugin`] with the schedule that is used for running the [`PhysicsSchedule`].
+    ///
+    /// The default schedule is `PostUpdate`.
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+        }
+    }
+}
+
+impl Default for CharacterControllerPlugin {
+    fn default() -> Self {
+        Self::new(PostUpdate)
+    }
+}
+
+impl Plugin for CharacterControllerPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<CharacterController>()
+            .register_type::<CharacterVelocity>()
+            .register_type::<CharacterControllerState>()
+            .register_type::<CharacterCollision>();
+
+        app.add_systems(
+            self.schedule,
+            init_character_controllers.in_set(PrepareSet::PreInit),
+        );
+
+        // Get the `PhysicsSchedule`, and panic if it doesn't exist.
+        let physics = app
+            .get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first");
+
+        physics.configure_sets(CharacterControllerSet::Move.in_set(PhysicsStepSet::First));
+        physics.add_systems(move_characters.in_set(CharacterControllerSet::Move));
+    }
+}
+
+/// System sets for the [`CharacterControllerPlugin`].
+///
+/// Systems that update the velocities of bodies that characters can stand on,
+/// like moving platforms, should run before [`CharacterControllerSet::Move`].
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CharacterControllerSet {
+    /// Moves the [character controllers](CharacterController). Runs in [`PhysicsStepSet::First`].
+    Move,
+}
+
+/// A kinematic [character controller](self) that moves the body according to its [`CharacterVelocity`]
+/// while sliding along obstacles.
+///
+/// The entity should also have a [kinematic](RigidBody::Kinematic) [`RigidBody`] and a [`Collider`].
+/// The [`CharacterVelocity`] and [`CharacterControllerState`] components are added automatically.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub struct CharacterController {
+    /// The up direction of the character. Used for determining which surfaces are walkable ground.
+    ///
+    /// The y-axis by default.
+    pub up: Vector,
+    /// The maximum angle in radians between the [up direction](Self::up) and the normal of a surface
+    /// for the surface to be considered walkable ground.
+    ///
+    /// 45 degrees by default.
+    pub max_slope_angle: Scalar,
+    /// The maximum height of obstacles that the character can step onto while grounded.
+    /// Zero disables stepping.
+    ///
+    /// `0.0` by default.
+    pub max_step_height: Scalar,
+    /// The maximum distance that the character is moved down to stay on the ground
+    /// when it was grounded during the previous step and isn't moving upwards.
+    /// Zero disables snapping.
+    ///
+    /// `0.2` by default.
+    pub snap_to_ground: Scalar,
+    /// The gap that is kept between the collider of the character and other colliders,
+    /// preventing the character from getting stuck due to numerical errors.
+    ///
+    /// `0.01` by default.
+    pub skin_width: Scalar,
+    /// The maximum number of times that the movement is redirected along obstacles in a single step.
+    ///
+    /// `4` by default.
+    pub max_iterations: usize,
+    /// If `true`, the character moves along with the body it is standing on, like a moving platform.
+    ///
+    /// `true` by default.
+    pub move_with_platforms: bool,
+}
+
+impl Default for CharacterController {
+    fn default() -> Self {
+        Self {
+            up: Vector::Y,
+            max_slope_angle: PI / 4.0,
+            max_step_height: 0.0,
+            snap_to_ground: 0.2,
+            skin_width: 0.01,
+            max_iterations: 4,
+            move_with_platforms: true,
+        }
+    }
+}
+
+impl CharacterController {
+    /// Sets the [up direction](Self::up) of the character.
+    pub fn with_up(mut self, up: Vector) -> Self {
+        self.up = up;
+        self
+    }
+
+    /// Sets the maximum angle in radians of walkable slopes.
+    pub fn with_max_slope_angle(mut self, angle: Scalar) -> Self {
+        self.max_slope_angle = angle;
+        self
+    }
+
+    /// Sets the maximum height of obstacles that the character can step onto.
+    pub fn with_max_step_height(mut self, height: Scalar) -> Self {
+        self.max_step_height = height;
+        self
+    }
+
+    /// Sets the maximum distance that the character is moved down to stay on the ground.
+    pub fn with_snap_to_ground(mut self, distance: Scalar) -> Self {
+        self.snap_to_ground = distance;
+        self
+    }
+
+    /// Sets the gap that is kept between the collider of the character and other colliders.
+    pub fn with_skin_width(mut self, skin_width: Scalar) -> Self {
+        self.skin_width = skin_width;
+        self
+    }
+
+    /// Sets the maximum number of times that the movement is redirected along obstacles in a single step.
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Returns `true` if a surface with the given `normal` is walkable ground.
+    pub fn is_walkable(&self, normal: Vector) -> bool {
+        normal.normalize_or_zero().dot(self.up) >= self.max_slope_angle.cos()
+    }
+}
+
+/// The desired velocity of a [character controller](self).
+///
+/// The character moves with this velocity unless it collides with an obstacle,
+/// in which case it slides along the obstacle.
+#[derive(Component, Clone, Copy, Debug, Default, Deref, DerefMut, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub struct CharacterVelocity(pub Vector);
+
+/// The results of moving a [character controller](self) during the last physics step.
+#[derive(Component, Clone, Debug, Default, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub struct CharacterControllerState {
+    /// `true` if the character is standing on walkable ground.
+    pub grounded: bool,
+    /// The ground that the character is standing on, if it is [grounded](Self::grounded).
+    pub ground: Option<CharacterCollision>,
+    /// The obstacles that the character collided with while moving.
+    pub collisions: Vec<CharacterCollision>,
+    /// The velocity of the platform that the character is standing on at the position of the character.
+    pub platform_velocity: Vector,
+    /// The velocity that the character moves with after resolving collisions.
+    pub velocity: Vector,
+}
+
+/// A collision between a [character controller](self) and another collider.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct CharacterCollision {
+    /// The collider entity that was hit.
+    pub entity: Entity,
+    /// The world-space contact point on the collider that was hit.
+    pub point: Vector,
+    /// The world-space outward normal of the collider that was hit.
+    pub normal: Vector,
+}
+
+fn init_character_controllers(
+    mut commands: Commands,
+    characters: Query<
+        (
+            Entity,
+            Has<CharacterVelocity>,
+            Has<CharacterControllerState>,
+        ),
+        Added<CharacterController>,
+    >,
+) {
+    for (entity, has_velocity, has_state) in &characters {
+        let mut entity_commands = commands.entity(entity);
+        if !has_velocity {
+            entity_commands.try_insert(CharacterVelocity::default());
+        }
+        if !has_state {
+            entity_commands.try_insert(CharacterControllerState::default());
+        }
+    }
+}
+
+/// Shapecasts and overlap tests for moving the collider of a single character.
+struct CharacterShape<'a, 'w, 's> {
+    spatial_query: &'a SpatialQuery<'w, 's>,
+    sensors: &'a Query<'w, 's, (), With<Sensor>>,
+    collider: &'a Collider,
+    rotation: Rotation,
+    filter: SpatialQueryFilter,
+}
+
+impl CharacterShape<'_, '_, '_> {
+    /// Casts the collider from `origin` in the given `direction`, returning the first solid
+    /// collider hit and the distance to it. Colliders that are already being touched
+    /// are only hit if moving towards them.
+    fn cast(
+        &self,
+        origin: Vector,
+        direction: Vector,
+        max_distance: Scalar,
+    ) -> Option<(CharacterCollision, Scalar)> {
+        let mut filter = self.filter.clone();
+
+        loop {
+            let hit = self.spatial_query.cast_shape(
+                self.collider,
+                origin,
+                #[cfg(feature = "2d")]
+                self.rotation.as_radians(),
+                #[cfg(feature = "3d")]
+                self.rotation.0,
+                Dir::new_unchecked(direction.f32()),
+                max_distance,
+                true,
+                filter.clone(),
+            )?;
+
+            // Skip sensors, but keep looking for solid colliders behind them.
+            if self.sensors.contains(hit.entity) {
+                filter = filter.with_excluded_entities([hit.entity]);
+                continue;
+            }
+
+            let collision = CharacterCollision {
+                entity: hit.entity,
+                point: hit.point1,
+                normal: hit.normal1,
+            };
+            return Some((collision, hit.time_of_impact));
+        }
+    }
+
+    /// Returns the normal of the surface that the character is touching at the given collision.
+    ///
+    /// Shapecasts against edges and corners return normals that point towards the center of the character,
+    /// so a short ray is cast at the contact point to find the normal of the actual surface, like the top of a step.
+    fn surface_normal(&self, collision: &CharacterCollision, up: Vector, skin: Scalar) -> Vector {
+        let inwards = (collision.normal - up * collision.normal.dot(up)).normalize_or_zero();
+        self.spatial_query
+            .cast_ray_predicate(
+                collision.point + (up - inwards) * skin,
+                Dir::new_unchecked((-up).f32()),
+                2.0 * skin,
+                true,
+                self.filter.clone(),
+                &|entity| !self.sensors.contains(entity),
+            )
+            .map_or(collision.normal, |hit| hit.normal)
+    }
+
+    /// Computes the translation required to resolve overlap between the collider at `position`
+    /// and other solid colliders.
+    fn depenetration(
+        &self,
+        position: Vector,
+        colliders: &Query<(&Collider, &Position, &Rotation), Without<Sensor>>,
+    ) -> Vector {
+        let mut translation = Vector::ZERO;
+
+        self.spatial_query.shape_intersections_callback(
+            self.collider,
+            position,
+            #[cfg(feature = "2d")]
+            self.rotation.as_radians(),
+            #[cfg(feature = "3d")]
+            self.rotation.0,
+            self.filter.clone(),
+            |entity| {
+                let Ok((collider, other_position, other_rotation)) = colliders.get(entity) else {
+                    return true;
+                };
+                if let Ok(Some(contact)) = contact_query::contact(
+                    self.collider,
+                    position + translation,
+                    self.rotation,
+                    collider,
+                    *other_position,
+                    *other_rotation,
+                    0.0,
+                ) {
+                    if contact.penetration > 0.0 {
+                        translation -= self.rotation * contact.normal1 * contact.penetration;
+                    }
+                }
+                true
+            },
+        );
+
+        translation
+    }
+}
+
+/// Moves [character controllers](self) according to their [`CharacterVelocity`],
+/// sliding along obstacles, and updates their [`CharacterControllerState`].
+#[allow(clippy::type_complexity)]
+fn move_characters(
+    mut characters: Query<
+        (
+            Entity,
+            &CharacterController,
+            &CharacterVelocity,
+            &mut CharacterControllerState,
+            &Collider,
+            &Position,
+            &Rotation,
+            &mut LinearVelocity,
+            Option<&CollisionLayers>,
+        ),
+        With<RigidBody>,
+    >,
+    colliders: Query<(&Collider, &Position, &Rotation), Without<Sensor>>,
+    collider_parents: Query<&ColliderParent>,
+    platforms: Query<
+        (
+            &Position,
+            &Rotation,
+            &CenterOfMass,
+            &LinearVelocity,
+            &AngularVelocity,
+        ),
+        Without<CharacterController>,
+    >,
+    sensors: Query<(), With<Sensor>>,
+    spatial_query: SpatialQuery,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    if delta_secs == 0.0 {
+        return;
+    }
+
+    for (
+        entity,
+        controller,
+        velocity,
+        mut state,
+        collider,
+        position,
+        rotation,
+        mut linear_velocity,
+        layers,
+    ) in &mut characters
+    {
+        let up = controller.up.normalize_or_zero();
+        let skin = controller.skin_width;
+        let was_grounded = state.grounded;
+
+        let mut filter = SpatialQueryFilter::from_excluded_entities([entity]);
+        if let Some(layers) = layers {
+            filter = filter.with_mask(layers.filters);
+        }
+        let shape = CharacterShape {
+            spatial_query: &spatial_query,
+            sensors: &sensors,
+            collider,
+            rotation: *rotation,
+            filter,
+        };
+
+        // Resolve overlap with other colliders.
+        let mut new_position = position.0 + shape.depenetration(position.0, &colliders);
+
+        // Move along with the platform that the character is standing on.
+        let platform_velocity = state
+            .ground
+            .filter(|_| controller.move_with_platforms && was_grounded)
+            .and_then(|ground| collider_parents.get(ground.entity).ok())
+            .and_then(|parent| platforms.get(parent.get()).ok())
+            .map_or(
+                Vector::ZERO,
+                |(platform_position, platform_rotation, center_of_mass, lin_vel, ang_vel)| {
+                    let r = new_position
+                        - (platform_position.0 + *platform_rotation * center_of_mass.0);
+                    #[cfg(feature = "2d")]
+                    {
+                        lin_vel.0 + ang_vel.0 * Vector::new(-r.y, r.x)
+                    }
+                    #[cfg(feature = "3d")]
+                    {
+                        lin_vel.0 + ang_vel.0.cross(r)
+                    }
+                },
+            );
+
+        // Move and slide along obstacles.
+        let mut collisions = vec![];
+        let mut remaining = (velocity.0 + platform_velocity) * delta_secs;
+
+        for _ in 0..controller.max_iterations {
+            let distance = remaining.length();
+            if distance <= Scalar::EPSILON {
+                break;
+            }
+            let direction = remaining / distance;
+
+            let Some((collision, hit_distance)) =
+                shape.cast(new_position, direction, distance + skin)
+            else {
+                new_position += remaining;
+                remaining = Vector::ZERO;
+                break;
+            };
+
+            let travel = (hit_distance - skin).clamp(0.0, distance);
+            new_position += direction * travel;
+            remaining -= direction * travel;
+            collisions.push(collision);
+
+            let walkable = controller.is_walkable(collision.normal);
+
+            // Try to step onto small obstacles.
+            if !walkable && was_grounded && controller.max_step_height > 0.0 {
+                if let Some(stepped_position) =
+                    step_up(&shape, controller, new_position, remaining, up)
+                {
+                    new_position = stepped_position;
+                    remaining = Vector::ZERO;
+                    break;
+                }
+            }
+
+            // Don't slide up steep slopes and walls while grounded.
+            let mut normal = collision.normal;
+            if !walkable && was_grounded {
+                normal = (normal - up * normal.dot(up)).normalize_or_zero();
+            }
+
+            // Remove the part of the movement going into the obstacle.
+            let into_obstacle = remaining.dot(normal);
+            if into_obstacle < 0.0 {
+                remaining -= normal * into_obstacle;
+            }
+        }
+
+        // Detect the ground, snapping to it if the character was grounded and isn't moving upwards.
+        let moving_up = velocity.0.dot(up) > Scalar::EPSILON;
+        let ground_distance = if was_grounded && !moving_up {
+            controller.snap_to_ground.max(skin) + skin
+        } else {
+            2.0 * skin
+        };
+        let ground = shape
+            .cast(new_position, -up, ground_distance)
+            .map(|(mut collision, hit_distance)| {
+                collision.normal = shape.surface_normal(&collision, up, skin);
+                (collision, hit_distance)
+            })
+            .filter(|(collision, _)| !moving_up && controller.is_walkable(collision.normal));
+
+        if let Some((_, hit_distance)) = ground {
+            new_position -= up * (hit_distance - skin).max(0.0);
+        }
+
+        let movement = new_position - position.0;
+        linear_velocity.0 = movement / delta_secs;
+
+        state.grounded = ground.is_some();
+        state.ground = ground.map(|(collision, _)| collision);
+        state.collisions = collisions;
+        state.platform_velocity = platform_velocity;
+        state.velocity = linear_velocity.0;
+    }
+}
+
+/// Tries to move the character up onto an obstacle of at most [`CharacterController::max_step_height`],
+/// returning the new position if there is walkable ground on top of the obstacle.
+fn step_up(
+    shape: &CharacterShape,
+    controller: &CharacterController,
+    position: Vector,
+    remaining: Vector,
+    up: Vector,
+) -> Option<Vector> {
+    let skin = controller.skin_width;
+
+    let horizontal = remaining - up * remaining.dot(up);
+    let distance = horizontal.length();
+    if distance <= Scalar::EPSILON {
+        return None;
+    }
+    let direction = horizontal / distance;
+
+    // Move up, forward, and back down onto the obstacle.
+    let up_distance = shape
+        .cast(position, up, controller.max_step_height + skin)
+        .map_or(controller.max_step_height, |(_, hit_distance)| {
+            (hit_distance - skin).max(0.0)
+        });
+    let raised = position + up * up_distance;
+
+    let forward_distance = shape
+        .cast(raised, direction, distance + skin)
+        .map_or(distance, |(_, hit_distance)| (hit_distance - skin).max(0.0));
+    if forward_distance <= Scalar::EPSILON {
+        return None;
+    }
+    let moved = raised + direction * forward_distance;
+
+    let (ground, hit_distance) = shape.cast(moved, -up, up_distance + skin)?;
+    controller
+        .is_walkable(shape.surface_normal(&ground, up, skin))
+        .then(|| moved - up * (hit_distance - skin).max(0.0))
+}
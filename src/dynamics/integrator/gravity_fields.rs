@@ -0,0 +1,335 @@
+//! Gravity that varies between bodies and regions of space.
+//!
+//! By default, every dynamic body is affected by the global [`Gravity`]. The gravity affecting
+//! individual bodies can be changed with:
+//!
+//! - [`RadialGravity`]: Pulls bodies towards the position of the entity, like a planet.
+//!   Multiple sources are added together.
+//! - [`GravityZone`]: Replaces the global gravity for bodies overlapping a [`Sensor`] collider,
+//!   like a zero-g section of a space station.
+//! - [`GravityOverride`]: Replaces all other gravity for a single body.
+//!
+//! The resulting gravity of each body is computed by the [`GravityFieldPlugin`] and stored in its [`LocalGravity`]
+//! component, which is used for integrating the velocity of the body. It is still scaled by [`GravityScale`].
+//! Sleeping bodies are woken up when their gravity changes.
+
+use crate::{collision::narrow_phase::NarrowPhaseSet, prelude::*};
+use bevy::{prelude::*, utils::HashMap};
+
+/// A plugin for computing the [`LocalGravity`] of [rigid bodies](RigidBody) from the global [`Gravity`]
+/// and [gravity fields](self).
+///
+/// The gravity is computed in the [`GravityFieldSet`] at the end of the narrow phase in [`NarrowPhaseSet::Last`],
+/// once the overlapping [gravity zones](GravityZone) are known. The [`Collisions`] with the zones
+/// are only needed if the [`NarrowPhasePlugin`] is enabled.
+///
+/// The gravity of bodies is only recomputed when gravity fields exist, or when the global [`Gravity`]
+/// or the set of dynamic bodies changes, so the plugin has no per-body cost when gravity fields are not used.
+pub struct GravityFieldPlugin;
+
+impl Plugin for GravityFieldPlugin {
+    fn build(&self, app: &mut App) {
+        // Get the `PhysicsSchedule`, and panic if it doesn't exist.
+        let physics = app
+            .get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first");
+
+        physics.configure_sets(GravityFieldSet.in_set(NarrowPhaseSet::Last));
+        physics.add_systems(
+            update_local_gravity
+                .run_if(local_gravity_may_have_changed)
+                .in_set(GravityFieldSet),
+        );
+    }
+}
+
+/// A system set for computing the [`LocalGravity`] of bodies. Runs in [`NarrowPhaseSet::Last`].
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GravityFieldSet;
+
+/// The gravitational acceleration affecting a [rigid body](RigidBody), before it is scaled by [`GravityScale`].
+///
+/// This is computed every physics step from the global [`Gravity`], [gravity zones](GravityZone),
+/// [radial gravity sources](RadialGravity), and the [`GravityOverride`] of the body,
+/// so it shouldn't be modified directly. See the [module-level documentation](self).
+#[derive(Component, Clone, Copy, Debug, Default, Deref, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub struct LocalGravity(pub(crate) Vector);
+
+impl LocalGravity {
+    /// Returns the gravitational acceleration.
+    pub fn get(&self) -> Vector {
+        self.0
+    }
+}
+
+/// Replaces the gravity of a [rigid body](RigidBody), ignoring the global [`Gravity`],
+/// [gravity zones](GravityZone) and [radial gravity sources](RadialGravity).
+///
+/// The gravity is still scaled by the [`GravityScale`] of the body.
+///
+/// ## Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::{math::*, prelude::*};")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::{math::*, prelude::*};")]
+/// use bevy::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     // A body that falls upwards.
+///     commands.spawn((RigidBody::Dynamic, GravityOverride(Vector::Y * 9.81)));
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug, Default, Deref, DerefMut, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub struct GravityOverride(pub Vector);
+
+/// A source of gravity that pulls [rigid bodies](RigidBody) towards the [`Position`] of its entity,
+/// like a planet.
+///
+/// Outside of the [radius](Self::radius), the acceleration follows the inverse-square law,
+/// decreasing with the square of the distance. Inside of the radius, it decreases linearly towards the center,
+/// like inside of a planet with a uniform density.
+///
+/// Radial gravity is added to the global [`Gravity`] or the gravity of a [`GravityZone`], so for planetoids,
+/// the global gravity should typically be set to zero.
+///
+/// ## Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// // The global gravity doesn't point towards the planet, so it is disabled.
+/// fn disable_gravity(mut gravity: ResMut<Gravity>) {
+///     *gravity = Gravity::ZERO;
+/// }
+///
+/// fn setup(mut commands: Commands) {
+///     // A planet with a surface gravity of 9.81.
+///     commands.spawn((
+///         RigidBody::Static,
+#[cfg_attr(feature = "2d", doc = "        Collider::circle(50.0),")]
+#[cfg_attr(feature = "3d", doc = "        Collider::sphere(50.0),")]
+///         RadialGravity::new(9.81, 50.0),
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, PartialEq)]
+pub struct RadialGravity {
+    /// The magnitude of the gravitational acceleration at the [radius](Self::radius).
+    pub strength: Scalar,
+    /// The distance from the center at which the acceleration is equal to the [strength](Self::strength),
+    /// like the surface of a planet.
+    pub radius: Scalar,
+    /// The distance from the center beyond which bodies are not affected.
+    ///
+    /// Infinite by default.
+    pub max_distance: Scalar,
+}
+
+impl RadialGravity {
+    /// Creates a new [`RadialGravity`] with the given acceleration at the given radius.
+    pub fn new(strength: Scalar, radius: Scalar) -> Self {
+        Self {
+            strength,
+            radius,
+            max_distance: Scalar::INFINITY,
+        }
+    }
+
+    /// Sets the distance from the center beyond which bodies are not affected.
+    pub fn with_max_distance(mut self, max_distance: Scalar) -> Self {
+        self.max_distance = max_distance;
+        self
+    }
+
+    /// Computes the gravitational acceleration at the given offset from the center.
+    pub fn acceleration_at(&self, offset: Vector) -> Vector {
+        let distance = offset.length();
+
+        if distance <= Scalar::EPSILON || distance > self.max_distance {
+            return Vector::ZERO;
+        }
+
+        let magnitude = if distance < self.radius {
+            self.strength * distance / self.radius
+        } else {
+            self.strength * (self.radius / distance).powi(2)
+        };
+
+        -offset / distance * magnitude
+    }
+}
+
+/// A region of space defined by a [`Sensor`] collider, in which the global [`Gravity`] is replaced
+/// by the gravity of the zone.
+///
+/// If a body overlaps multiple zones, the zone with the highest [priority](Self::priority) is used.
+///
+/// ## Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     // A zero-g room.
+///     commands.spawn((
+///         GravityZone::ZERO,
+#[cfg_attr(feature = "2d", doc = "        Collider::rectangle(10.0, 10.0),")]
+#[cfg_attr(feature = "3d", doc = "        Collider::cuboid(10.0, 10.0, 10.0),")]
+///         Sensor,
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub struct GravityZone {
+    /// The gravitational acceleration inside of the zone.
+    pub gravity: Vector,
+    /// The priority of the zone. If a body overlaps multiple zones, the zone with the highest priority is used.
+    ///
+    /// `0` by default.
+    pub priority: i32,
+}
+
+impl GravityZone {
+    /// A gravity zone with zero gravity.
+    pub const ZERO: Self = Self::new(Vector::ZERO);
+
+    /// Creates a new [`GravityZone`] with the given gravitational acceleration.
+    pub const fn new(gravity: Vector) -> Self {
+        Self {
+            gravity,
+            priority: 0,
+        }
+    }
+
+    /// Sets the priority of the zone.
+    pub const fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+}
+
+/// Returns `true` if the [`LocalGravity`] of any body could have changed since the previous physics step.
+#[allow(clippy::type_complexity)]
+fn local_gravity_may_have_changed(
+    fields: Query<
+        (),
+        Or<(
+            With<RadialGravity>,
+            With<GravityZone>,
+            With<GravityOverride>,
+        )>,
+    >,
+    changed_bodies: Query<(), Or<(Added<LocalGravity>, Changed<RigidBody>)>>,
+    gravity: Res<Gravity>,
+    mut had_fields: Local<bool>,
+) -> bool {
+    // Run once more after the last gravity field is removed to restore the global gravity.
+    let has_fields = !fields.is_empty();
+    let had_fields = std::mem::replace(&mut *had_fields, has_fields);
+
+    has_fields || had_fields || gravity.is_changed() || !changed_bodies.is_empty()
+}
+
+/// Computes the [`LocalGravity`] of dynamic bodies, and wakes up sleeping bodies whose gravity has changed.
+#[allow(clippy::type_complexity)]
+fn update_local_gravity(
+    mut commands: Commands,
+    mut bodies: Query<(
+        Entity,
+        &RigidBody,
+        &Position,
+        &mut LocalGravity,
+        Option<&GravityOverride>,
+        &mut TimeSleeping,
+        Has<Sleeping>,
+    )>,
+    zones: Query<(Entity, &GravityZone)>,
+    sources: Query<(Entity, &RadialGravity, &Position)>,
+    collider_parents: Query<&ColliderParent>,
+    collisions: Option<Res<Collisions>>,
+    gravity: Res<Gravity>,
+) {
+    // Find the zone with the highest priority for each body overlapping a zone.
+    let mut zone_gravity = HashMap::<Entity, GravityZone>::new();
+    for (zone_entity, zone) in &zones {
+        // Overlapping zones are found using collisions, so zones require the narrow phase.
+        let Some(collisions) = collisions.as_deref() else {
+            break;
+        };
+        for contacts in collisions.collisions_with_entity(zone_entity) {
+            let collider = if contacts.entity1 == zone_entity {
+                contacts.entity2
+            } else {
+                contacts.entity1
+            };
+            let Ok(body) = collider_parents.get(collider).map(ColliderParent::get) else {
+                continue;
+            };
+            zone_gravity
+                .entry(body)
+                .and_modify(|current| {
+                    if zone.priority > current.priority {
+                        *current = *zone;
+                    }
+                })
+                .or_insert(*zone);
+        }
+    }
+
+    for (
+        entity,
+        rb,
+        position,
+        mut local_gravity,
+        gravity_override,
+        mut time_sleeping,
+        is_sleeping,
+    ) in &mut bodies
+    {
+        if !rb.is_dynamic() {
+            continue;
+        }
+
+        let new_gravity = if let Some(gravity_override) = gravity_override {
+            gravity_override.0
+        } else {
+            let base = zone_gravity
+                .get(&entity)
+                .map_or(gravity.0, |zone| zone.gravity);
+            sources
+                .iter()
+                .filter(|(source_entity, ..)| *source_entity != entity)
+                .fold(base, |acceleration, (_, source, source_position)| {
+                    acceleration + source.acceleration_at(position.0 - source_position.0)
+                })
+        };
+
+        if local_gravity.0 == new_gravity {
+            continue;
+        }
+
+        // Wake up sleeping bodies if their gravity changed noticeably.
+        if is_sleeping && (local_gravity.0 - new_gravity).length_squared() > Scalar::EPSILON {
+            commands.entity(entity).remove::<Sleeping>();
+            time_sleeping.0 = 0.0;
+        }
+
+        local_gravity.0 = new_gravity;
+    }
+}
@@ -3,6 +3,7 @@
 //!
 //! See [`IntegratorPlugin`].
 
+pub mod gravity_fields;
 #[doc(alias = "symplectic_euler")]
 pub mod semi_implicit_euler;
 
@@ -21,6 +22,7 @@ use bevy::{
 /// is supported. It is the standard for game physics, being simple, efficient, and sufficiently accurate.
 ///
 /// The plugin adds systems in the [`IntegrationSet::Velocity`] and [`IntegrationSet::Position`] system sets.
+/// Gravity is applied using the [`LocalGravity`] of each body, which is computed by the [`GravityFieldPlugin`].
 pub struct IntegratorPlugin {
     schedule: Interned<dyn ScheduleLabel>,
 }
@@ -58,15 +60,17 @@ impl Plugin for IntegratorPlugin {
                 integrate_positions.in_set(IntegrationSet::Position),
             ));
 
-        app.get_schedule_mut(PhysicsSchedule)
-            .expect("add PhysicsSchedule first")
-            .add_systems(
-                (
-                    apply_impulses.before(SolverSet::Substep),
-                    clear_forces_and_impulses.after(SolverSet::Substep),
-                )
-                    .in_set(PhysicsStepSet::Solver),
-            );
+        let physics = app
+            .get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first");
+
+        physics.add_systems(
+            (
+                apply_impulses.before(SolverSet::Substep),
+                clear_forces_and_impulses.after(SolverSet::Substep),
+            )
+                .in_set(PhysicsStepSet::Solver),
+        );
     }
 }
 
@@ -89,6 +93,9 @@ pub enum IntegrationSet {
 /// You can also control how gravity affects a specific [rigid body](RigidBody) using the [`GravityScale`]
 /// component. The magnitude of the gravity will be multiplied by this scaling factor.
 ///
+/// For gravity that varies between bodies and regions of space, like the gravity of planetoids
+/// or zero-g sections, see [`gravity_fields`].
+///
 /// ## Example
 ///
 /// ```no_run
@@ -133,7 +140,7 @@ impl Gravity {
 }
 
 #[allow(clippy::type_complexity)]
-fn integrate_velocities(
+pub(crate) fn integrate_velocities(
     mut bodies: Query<
         (
             &RigidBody,
@@ -142,13 +149,13 @@ fn integrate_velocities(
             &Rotation,
             &mut LinearVelocity,
             &mut AngularVelocity,
-            &ExternalForce,
-            &ExternalTorque,
+            (&ExternalForce, &ExternalTorque, Option<&AerodynamicForces>),
             &InverseMass,
             &InverseInertia,
             Option<&LinearDamping>,
             Option<&AngularDamping>,
             Option<&GravityScale>,
+            Option<&LocalGravity>,
             Option<&LockedAxes>,
         ),
         Without<Sleeping>,
@@ -166,13 +173,13 @@ fn integrate_velocities(
             rot,
             mut lin_vel,
             mut ang_vel,
-            force,
-            torque,
+            (force, torque, aerodynamic_forces),
             inv_mass,
             inv_inertia,
             lin_damping,
             ang_damping,
             gravity_scale,
+            local_gravity,
             locked_axes,
         )| {
             if let Some(mut previous_position) = prev_pos {
@@ -209,9 +216,14 @@ fn integrate_velocities(
                 }
             }
 
-            let external_force = force.force();
-            let external_torque = torque.torque() + force.torque();
-            let gravity = gravity.0 * gravity_scale.map_or(1.0, |scale| scale.0);
+            let mut external_force = force.force();
+            let mut external_torque = torque.torque() + force.torque();
+            if let Some(aerodynamic_forces) = aerodynamic_forces {
+                external_force += aerodynamic_forces.force();
+                external_torque += aerodynamic_forces.torque;
+            }
+            let gravity = local_gravity.map_or(gravity.0, |local_gravity| local_gravity.0)
+                * gravity_scale.map_or(1.0, |scale| scale.0);
 
             semi_implicit_euler::integrate_velocity(
                 &mut lin_vel.0,
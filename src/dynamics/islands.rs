@@ -0,0 +1,416 @@
+//! Simulation islands, groups of dynamic bodies connected by contacts and joints.
+//!
+//! Bodies in an island can only affect each other through the contacts and joints connecting them,
+//! so an island can be simulated independently of the rest of the world. This is used for sleeping:
+//! an island only falls [asleep](Sleeping) once all of its bodies have come to rest, and the whole island
+//! is woken up when any of its bodies is woken up. This prevents stacks from having some bodies asleep
+//! while others are still moving.
+//!
+//! Contact constraints are solved in parallel using [graph coloring](dynamics::solver::graph_coloring),
+//! which also parallelizes the work within a single large island.
+//!
+//! Static and kinematic bodies are not part of islands, so they don't connect islands to each other.
+//!
+//! See [`IslandPlugin`].
+
+use crate::{dynamics::solver::ContactConstraints, prelude::*};
+use bevy::{ecs::entity::EntityHashMap, prelude::*, utils::HashSet};
+
+/// A plugin for building [simulation islands](self) from contacts and [joints](dynamics::solver::joints).
+///
+/// The islands are stored in the [`PhysicsIslands`] resource. They are updated every step
+/// after the contact constraints have been generated and before the [solver](SolverPlugin) runs.
+///
+/// Islands are persistent and updated incrementally. Islands are merged when a new contact or joint
+/// connects them, and an island is only rebuilt when a contact or joint between its bodies is removed,
+/// which can split it into several islands. Islands of sleeping bodies are kept until one of their bodies
+/// is woken up, touched by an awake body, or despawned, which wakes up all of the bodies in the island.
+pub struct IslandPlugin;
+
+impl Plugin for IslandPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PhysicsIslands>();
+
+        let physics_schedule = app
+            .get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first");
+
+        physics_schedule.add_systems(
+            (
+                |mut islands: ResMut<PhysicsIslands>| islands.joint_pairs.clear(),
+                collect_joint_pairs::<FixedJoint>,
+                collect_joint_pairs::<GenericJoint>,
+                collect_joint_pairs::<RevoluteJoint>,
+                #[cfg(feature = "3d")]
+                collect_joint_pairs::<SphericalJoint>,
+                collect_joint_pairs::<PrismaticJoint>,
+                collect_joint_pairs::<DistanceJoint>,
+                update_islands,
+            )
+                .chain()
+                .in_set(PhysicsStepSet::Solver)
+                .before(SolverSet::PreSubstep),
+        );
+    }
+}
+
+/// A simulation island, a group of dynamic bodies connected by contacts and joints.
+///
+/// See the [module-level documentation](self) for more information.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PhysicsIsland {
+    /// The dynamic bodies in the island.
+    pub bodies: Vec<Entity>,
+    /// Whether the bodies in the island are [sleeping](Sleeping).
+    pub is_sleeping: bool,
+}
+
+/// A resource that stores the [simulation islands](self) of the world.
+///
+/// ## Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn print_islands(islands: Res<PhysicsIslands>) {
+///     for island in islands.iter() {
+///         println!(
+///             "island with {} bodies, sleeping: {}",
+///             island.bodies.len(),
+///             island.is_sleeping
+///         );
+///     }
+/// }
+/// ```
+#[derive(Resource, Clone, Debug, Default)]
+pub struct PhysicsIslands {
+    /// The islands, indexed by their island index. Removed islands are left empty,
+    /// and their indices are reused for new islands.
+    islands: Vec<PhysicsIsland>,
+    free_islands: Vec<usize>,
+    body_islands: EntityHashMap<usize>,
+    joint_pairs: Vec<(Entity, Entity)>,
+    /// The contact and joint pairs that connected bodies in the previous step.
+    pairs: HashSet<(Entity, Entity)>,
+}
+
+impl PhysicsIslands {
+    /// Returns the island that the given body belongs to, if any.
+    ///
+    /// Only dynamic bodies are part of islands.
+    pub fn get(&self, body: Entity) -> Option<&PhysicsIsland> {
+        self.body_islands
+            .get(&body)
+            .map(|&index| &self.islands[index])
+    }
+
+    /// Returns `true` if the given bodies belong to the same island.
+    pub fn are_connected(&self, body1: Entity, body2: Entity) -> bool {
+        match (self.body_islands.get(&body1), self.body_islands.get(&body2)) {
+            (Some(island1), Some(island2)) => island1 == island2,
+            _ => false,
+        }
+    }
+
+    /// Returns an iterator over the islands.
+    pub fn iter(&self) -> impl Iterator<Item = &PhysicsIsland> {
+        self.islands
+            .iter()
+            .filter(|island| !island.bodies.is_empty())
+    }
+
+    /// Returns a mutable iterator over the islands.
+    pub(crate) fn iter_mut(&mut self) -> impl Iterator<Item = &mut PhysicsIsland> {
+        self.islands
+            .iter_mut()
+            .filter(|island| !island.bodies.is_empty())
+    }
+
+    /// Returns the number of islands.
+    pub fn len(&self) -> usize {
+        self.islands.len() - self.free_islands.len()
+    }
+
+    /// Returns `true` if there are no islands.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Adds a new island with the given bodies, and returns its index.
+    fn add_island(&mut self, island: PhysicsIsland) -> usize {
+        let index = match self.free_islands.pop() {
+            Some(index) => {
+                self.islands[index] = island;
+                index
+            }
+            None => {
+                self.islands.push(island);
+                self.islands.len() - 1
+            }
+        };
+        for &entity in self.islands[index].bodies.iter() {
+            self.body_islands.insert(entity, index);
+        }
+        index
+    }
+
+    /// Removes the island with the given index, and returns it.
+    ///
+    /// The bodies of the island must be reassigned to other islands by the caller.
+    fn remove_island(&mut self, index: usize) -> PhysicsIsland {
+        self.free_islands.push(index);
+        std::mem::take(&mut self.islands[index])
+    }
+
+    /// Removes the given body from its island, and returns the index of the island.
+    fn remove_body(&mut self, entity: Entity) -> Option<usize> {
+        let index = self.body_islands.remove(&entity)?;
+        let bodies = &mut self.islands[index].bodies;
+        bodies.retain(|&body| body != entity);
+        if bodies.is_empty() {
+            self.free_islands.push(index);
+        }
+        Some(index)
+    }
+
+    /// Merges the island with the index `from` into the island with the index `into`.
+    fn merge_islands(&mut self, into: usize, from: usize) {
+        let island = self.remove_island(from);
+        for &entity in island.bodies.iter() {
+            self.body_islands.insert(entity, into);
+        }
+        self.islands[into].bodies.extend(island.bodies);
+    }
+}
+
+/// Adds the bodies connected by enabled [joints](dynamics::solver::joints) to [`PhysicsIslands`].
+fn collect_joint_pairs<T: Joint>(
+    joints: Query<&T, Without<JointDisabled>>,
+    mut islands: ResMut<PhysicsIslands>,
+) {
+    for joint in &joints {
+        let [entity1, entity2] = joint.entities();
+        islands.joint_pairs.push((entity1, entity2));
+    }
+}
+
+/// Incrementally updates the islands from changes to the bodies, [`ContactConstraints`] and joints.
+///
+/// Sleeping islands that have been disturbed are woken up, islands connected by new contacts
+/// or joints are merged, and islands that lost a contact or joint between their bodies are split.
+#[allow(clippy::type_complexity)]
+fn update_islands(
+    mut commands: Commands,
+    mut islands: ResMut<PhysicsIslands>,
+    constraints: Res<ContactConstraints>,
+    mut bodies: Query<(&RigidBody, Has<Sleeping>, &mut TimeSleeping)>,
+    changed_bodies: Query<(Entity, &RigidBody, Has<Sleeping>), Changed<RigidBody>>,
+    added_sleeping: Query<Entity, Added<Sleeping>>,
+    mut removed_bodies: RemovedComponents<RigidBody>,
+    mut removed_sleeping: RemovedComponents<Sleeping>,
+) {
+    let islands = &mut *islands;
+    let mut islands_to_wake = HashSet::<usize>::default();
+    let mut islands_to_split = HashSet::<usize>::default();
+
+    // Remove despawned bodies and bodies that are no longer dynamic from their islands.
+    // Removing a body from a sleeping island wakes it up.
+    let removed = removed_bodies.read().chain(
+        changed_bodies
+            .iter()
+            .filter(|(_, rb, _)| !rb.is_dynamic())
+            .map(|(entity, ..)| entity),
+    );
+    for entity in removed.collect::<Vec<_>>() {
+        if let Some(index) = islands.remove_body(entity) {
+            islands_to_split.insert(index);
+            if islands.islands[index].is_sleeping {
+                islands_to_wake.insert(index);
+            }
+        }
+    }
+
+    // New dynamic bodies get their own island.
+    for (entity, rb, is_sleeping) in &changed_bodies {
+        if rb.is_dynamic() && !islands.body_islands.contains_key(&entity) {
+            islands.add_island(PhysicsIsland {
+                bodies: vec![entity],
+                is_sleeping,
+            });
+        }
+    }
+
+    // Bodies that were put to sleep manually while their island is awake are moved to their own island.
+    for entity in &added_sleeping {
+        let Some(&index) = islands.body_islands.get(&entity) else {
+            continue;
+        };
+        if islands.islands[index].is_sleeping {
+            continue;
+        }
+        islands.remove_body(entity);
+        islands_to_split.insert(index);
+        islands.add_island(PhysicsIsland {
+            bodies: vec![entity],
+            is_sleeping: true,
+        });
+    }
+
+    // A sleeping island is woken up if any of its bodies is woken up.
+    for entity in removed_sleeping.read() {
+        if let Some(&index) = islands.body_islands.get(&entity) {
+            if islands.islands[index].is_sleeping {
+                islands_to_wake.insert(index);
+            }
+        }
+    }
+
+    // Contact constraints are only generated if at least one of the bodies is awake,
+    // so any constraint touching a sleeping island wakes it up.
+    let mut pairs = HashSet::<(Entity, Entity)>::default();
+    for constraint in constraints.iter() {
+        let (entity1, entity2) = (constraint.entity1, constraint.entity2);
+        for entity in [entity1, entity2] {
+            if let Some(&index) = islands.body_islands.get(&entity) {
+                if islands.islands[index].is_sleeping {
+                    islands_to_wake.insert(index);
+                }
+            }
+        }
+        if islands.body_islands.contains_key(&entity1)
+            && islands.body_islands.contains_key(&entity2)
+        {
+            pairs.insert((entity1.min(entity2), entity1.max(entity2)));
+        }
+    }
+
+    // Joints wake up sleeping islands when they are attached to an awake body.
+    for &(entity1, entity2) in islands.joint_pairs.iter() {
+        for (entity, other) in [(entity1, entity2), (entity2, entity1)] {
+            let Some(&index) = islands.body_islands.get(&entity) else {
+                continue;
+            };
+            let other_is_awake = bodies
+                .get(other)
+                .is_ok_and(|(rb, is_sleeping, _)| !rb.is_static() && !is_sleeping);
+            if islands.islands[index].is_sleeping && other_is_awake {
+                islands_to_wake.insert(index);
+            }
+        }
+        if islands.body_islands.contains_key(&entity1)
+            && islands.body_islands.contains_key(&entity2)
+        {
+            pairs.insert((entity1.min(entity2), entity1.max(entity2)));
+        }
+    }
+
+    for &index in islands_to_wake.iter() {
+        let island = &mut islands.islands[index];
+        island.is_sleeping = false;
+        for &entity in island.bodies.iter() {
+            if let Ok((_, is_sleeping, mut time_sleeping)) = bodies.get_mut(entity) {
+                time_sleeping.0 = 0.0;
+                if is_sleeping {
+                    commands.entity(entity).remove::<Sleeping>();
+                }
+            }
+        }
+    }
+
+    // Merge islands connected by contacts and joints, moving the bodies of the smaller island.
+    for &(entity1, entity2) in pairs.iter() {
+        let index1 = islands.body_islands[&entity1];
+        let index2 = islands.body_islands[&entity2];
+        if index1 == index2 {
+            continue;
+        }
+        let (into, from) =
+            if islands.islands[index1].bodies.len() >= islands.islands[index2].bodies.len() {
+                (index1, index2)
+            } else {
+                (index2, index1)
+            };
+        islands.merge_islands(into, from);
+        if islands_to_split.remove(&from) {
+            islands_to_split.insert(into);
+        }
+    }
+
+    // Islands that lost a contact or joint between their bodies might have to be split.
+    // The pairs of sleeping islands don't have contact constraints, so they are kept as is.
+    let previous_pairs = std::mem::take(&mut islands.pairs);
+    for (entity1, entity2) in previous_pairs {
+        if pairs.contains(&(entity1, entity2)) {
+            continue;
+        }
+        let (Some(&index1), Some(&index2)) = (
+            islands.body_islands.get(&entity1),
+            islands.body_islands.get(&entity2),
+        ) else {
+            continue;
+        };
+        if index1 == index2 && islands.islands[index1].is_sleeping {
+            pairs.insert((entity1, entity2));
+        } else if index1 == index2 {
+            islands_to_split.insert(index1);
+        }
+    }
+
+    for index in islands_to_split {
+        let island = &islands.islands[index];
+        if island.is_sleeping || island.bodies.len() < 2 {
+            continue;
+        }
+        split_island(islands, index, &pairs);
+    }
+
+    islands.pairs = pairs;
+}
+
+/// Rebuilds the island with the given index from the given contact and joint pairs,
+/// splitting it into several islands if its bodies are no longer connected.
+fn split_island(islands: &mut PhysicsIslands, index: usize, pairs: &HashSet<(Entity, Entity)>) {
+    let island = islands.remove_island(index);
+    let nodes = island
+        .bodies
+        .iter()
+        .enumerate()
+        .map(|(node, &entity)| (entity, node))
+        .collect::<EntityHashMap<usize>>();
+
+    // Connect the bodies using a disjoint-set forest.
+    let mut parents = (0..island.bodies.len()).collect::<Vec<_>>();
+    for (entity1, entity2) in pairs.iter() {
+        if let (Some(&node1), Some(&node2)) = (nodes.get(entity1), nodes.get(entity2)) {
+            let root1 = find_root(&mut parents, node1);
+            let root2 = find_root(&mut parents, node2);
+            if root1 != root2 {
+                parents[root1.max(root2)] = root1.min(root2);
+            }
+        }
+    }
+
+    // Create an island for each set of connected bodies.
+    let mut root_bodies = vec![Vec::new(); island.bodies.len()];
+    for (node, &entity) in island.bodies.iter().enumerate() {
+        let root = find_root(&mut parents, node);
+        root_bodies[root].push(entity);
+    }
+    for bodies in root_bodies.into_iter().filter(|bodies| !bodies.is_empty()) {
+        islands.add_island(PhysicsIsland {
+            bodies,
+            is_sleeping: false,
+        });
+    }
+}
+
+/// Finds the root of the set containing `node`, compressing the path along the way.
+fn find_root(parents: &mut [usize], mut node: usize) -> usize {
+    while parents[node] != node {
+        parents[node] = parents[parents[node]];
+        node = parents[node];
+    }
+    node
+}
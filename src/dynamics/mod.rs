@@ -26,7 +26,13 @@
 //! | [`IntegratorPlugin`] | Handles motion caused by velocity, and applies external forces and gravity.                                                           |
 //! | [`SolverPlugin`]     | Solves constraints (contacts and joints).                                                                                             |
 //! | [`CcdPlugin`]        | Performs sweep-based [Continuous Collision Detection](dynamics::ccd) for bodies with the [`SweptCcd`] component to prevent tunneling. |
+//! | [`IslandPlugin`]     | Groups bodies into [simulation islands](islands) that sleep together and are solved in parallel.                                      |
 //! | [`SleepingPlugin`]   | Manages sleeping and waking for bodies, automatically deactivating them to save computational resources.                              |
+//! | [`RagdollPlugin`]    | Constructs [ragdolls](ragdoll) from bone hierarchies and blends between their animated and simulated poses.                           |
+//! | [`CharacterControllerPlugin`] | Moves [kinematic character controllers](character_controller), sliding along obstacles and detecting the ground.             |
+//! | [`BuoyancyPlugin`]   | Applies buoyancy and drag to bodies submerged in [fluid volumes](buoyancy).                                                           |
+//! | [`VehiclePlugin`]    | Simulates [vehicles](vehicle) with raycast wheels, applying suspension, drive and friction forces to the chassis.                     |
+//! | [`AerodynamicsPlugin`] | Applies [aerodynamic](aerodynamics) drag and lift to bodies with the [`Aerodynamics`] component.                                    |
 //!
 //! ## Accuracy
 //!
@@ -61,20 +67,57 @@
 //! [Gauss-Seidel]: https://en.wikipedia.org/wiki/Gauss%E2%80%93Seidel_method
 //! [Semi-implicit Euler]: https://en.wikipedia.org/wiki/Semi-implicit_Euler_method
 
+pub mod aerodynamics;
+#[cfg(feature = "default-collider")]
+pub mod buoyancy;
 pub mod ccd;
+#[cfg(feature = "default-collider")]
+pub mod character_controller;
 pub mod integrator;
+pub mod islands;
+#[cfg(feature = "default-collider")]
+pub mod ragdoll;
 pub mod rigid_body;
 pub mod sleeping;
 pub mod solver;
+#[cfg(feature = "default-collider")]
+pub mod vehicle;
 
 /// Re-exports common types related to the rigid body dynamics functionality.
 pub mod prelude {
+    #[cfg(feature = "default-collider")]
+    pub use super::buoyancy::{BuoyancyPlugin, FluidVolume};
+    #[cfg(feature = "default-collider")]
+    pub use super::character_controller::{
+        CharacterCollision, CharacterController, CharacterControllerPlugin, CharacterControllerSet,
+        CharacterControllerState, CharacterVelocity,
+    };
+    #[cfg(feature = "default-collider")]
+    pub use super::ragdoll::{
+        Ragdoll, RagdollBone, RagdollBoneConfig, RagdollConstructor, RagdollJointType,
+        RagdollPlugin,
+    };
+    #[cfg(feature = "default-collider")]
+    pub use super::vehicle::{SlipCurve, VehiclePlugin, Wheel, WheelContact, WheelState};
     pub use super::{
+        aerodynamics::{
+            AerodynamicForces, Aerodynamics, AerodynamicsPlugin, Atmosphere, LiftCurve, LiftSurface,
+        },
         ccd::{CcdPlugin, SpeculativeMargin, SweepMode, SweptCcd},
-        integrator::{Gravity, IntegratorPlugin},
+        integrator::{
+            gravity_fields::{
+                GravityFieldPlugin, GravityFieldSet, GravityOverride, GravityZone, LocalGravity,
+                RadialGravity,
+            },
+            Gravity, IntegratorPlugin,
+        },
+        islands::{IslandPlugin, PhysicsIsland, PhysicsIslands},
         rigid_body::*,
         sleeping::{DeactivationTime, SleepingPlugin, SleepingThreshold},
-        solver::{joints::*, PhysicsLengthUnit, SolverPlugin, SolverSet},
+        solver::{
+            joints::*, softness_parameters::SoftnessParameters, PhysicsLengthUnit, SolverPlugin,
+            SolverSet,
+        },
     };
 }
 
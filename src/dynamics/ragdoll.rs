@@ -0,0 +1,666 @@
+//! Ragdolls constructed from a bone hierarchy.
+//!
+//! A [`RagdollConstructor`] walks the descendants of its entity, like a skeleton spawned from a glTF scene,
+//! and creates a rigid body with a capsule collider for each configured bone. The bodies are connected
+//! to the bodies of their parent bones with [joints](crate::dynamics::solver::joints):
+//! [`SphericalJoint`]s by default in 3D, and [`RevoluteJoint`]s in 2D.
+//!
+//! Once the ragdoll has been constructed, the [`Ragdoll`] component is inserted on the entity.
+//! Its [`blend`](Ragdoll::blend) determines how the bones are posed:
+//!
+//! - `0.0`: The bones are fully animated. The bodies are [kinematic](RigidBody::Kinematic)
+//!   and follow the animated pose of the bones.
+//! - `1.0`: The bones are fully simulated. The bodies are [dynamic](RigidBody::Dynamic),
+//!   and the bones follow the bodies.
+//! - Values in between interpolate between the animated and simulated poses of the bones.
+//!
+//! The blended pose is written to the `Transform` of the bones after the physics step, and the animated pose
+//! is restored at the start of the next frame. This way, animation systems and other gameplay logic
+//! always see and modify the animated pose.
+//!
+//! See [`RagdollConstructor`] for an example.
+
+use crate::{prelude::*, prepare::PrepareSet, sync::SyncSet};
+#[cfg(feature = "bevy_scene")]
+use bevy::scene::SceneInstance;
+use bevy::{
+    ecs::{intern::Interned, schedule::ScheduleLabel},
+    prelude::*,
+    utils::HashMap,
+};
+
+/// A plugin for constructing [ragdolls](self) and blending between their animated and simulated poses.
+pub struct RagdollPlugin {
+    schedule: Interned<dyn ScheduleLabel>,
+}
+
+impl RagdollPlugin {
+    /// Creates a [`RagdollPlugin`] with the schedule that is used for running the [`PhysicsSchedule`].
+    ///
+    /// The default schedule is `PostUpdate`.
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+        }
+    }
+}
+
+impl Default for RagdollPlugin {
+    fn default() -> Self {
+        Self::new(PostUpdate)
+    }
+}
+
+impl Plugin for RagdollPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<RagdollConstructor>()
+            .register_type::<RagdollBoneConfig>()
+            .register_type::<RagdollJointType>()
+            .register_type::<Ragdoll>()
+            .register_type::<RagdollBone>();
+
+        app.add_systems(First, restore_animated_poses);
+        app.add_systems(Update, init_ragdoll_constructors);
+
+        app.add_systems(
+            self.schedule,
+            update_ragdoll_body_types.in_set(PrepareSet::PreInit),
+        );
+        app.add_systems(self.schedule, write_blended_poses.in_set(SyncSet::Last));
+
+        // Get the `PhysicsSchedule`, and panic if it doesn't exist.
+        let physics = app
+            .get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first");
+
+        // Kinematic ragdoll bodies can act as moving platforms for characters, so their velocities are updated first.
+        physics.add_systems(
+            follow_animated_poses
+                .in_set(PhysicsStepSet::First)
+                .before(CharacterControllerSet::Move),
+        );
+    }
+}
+
+/// A component that constructs a [ragdoll](self) for the descendants of its entity,
+/// such as the bones of a skeleton spawned from a glTF scene.
+///
+/// Bones are identified by their `Name`. For each bone, a [dynamic](RigidBody::Dynamic) rigid body
+/// is spawned at the position of the bone, with a capsule collider extending towards the average
+/// position of its child bones. Bones without child bones get a ball collider.
+/// The body of each bone is connected to the body of its closest ancestor bone with a joint
+/// configured by [`RagdollBoneConfig`].
+///
+/// The bodies are spawned as separate entities with the [`RagdollBone`] component,
+/// and all of them share the initial rotation of the entity with the constructor.
+/// Joint axes and limits are therefore relative to the initial pose of the ragdoll.
+///
+/// If this component is used on a scene, such as one spawned by a [`SceneBundle`], it will
+/// wait until the scene is loaded before constructing the ragdoll. Afterwards, the constructor
+/// is replaced with the [`Ragdoll`] component.
+///
+/// ## Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn setup(mut commands: Commands, assets: Res<AssetServer>) {
+///     let scene = assets.load("character.gltf#Scene0");
+///
+///     // Construct a ragdoll for the given bones, starting out fully animated.
+///     commands.spawn((
+///         SceneBundle { scene, ..default() },
+///         RagdollConstructor::new(None)
+///             .with_bone("Hips", RagdollBoneConfig::new(0.15))
+///             .with_bone("Spine", RagdollBoneConfig::new(0.12).with_twist_limits(-0.3, 0.3))
+///             .with_bone("Head", RagdollBoneConfig::new(0.1))
+///             .with_blend(0.0),
+///     ));
+/// }
+///
+/// /// Switches to the simulated pose when a character dies.
+/// fn go_limp(mut ragdolls: Query<&mut Ragdoll, Added<Dead>>) {
+///     for mut ragdoll in &mut ragdolls {
+///         ragdoll.blend = 1.0;
+///     }
+/// }
+///
+/// #[derive(Component)]
+/// struct Dead;
+/// ```
+#[derive(Component, Clone, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Component, Debug, PartialEq, Default)]
+pub struct RagdollConstructor {
+    /// The configuration used for each named descendant that isn't included in [`bones`](Self::bones).
+    /// If `None`, all entities except the ones in [`bones`](Self::bones) will be skipped.
+    pub default_bone: Option<RagdollBoneConfig>,
+    /// Specifies the [`RagdollBoneConfig`] for bones by `Name`. Entries with a `None` value will be skipped.
+    pub bones: HashMap<String, Option<RagdollBoneConfig>>,
+    /// The initial [`Ragdoll::blend`] of the constructed ragdoll.
+    ///
+    /// `1.0` (fully simulated) by default.
+    pub blend: Scalar,
+}
+
+impl Default for RagdollConstructor {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl RagdollConstructor {
+    /// Creates a new [`RagdollConstructor`] with the configuration used for bones
+    /// not included in [`bones`](Self::bones) set to the given `default_bone`.
+    ///
+    /// If the given configuration is `None`, only the bones added with
+    /// [`with_bone`](Self::with_bone) are part of the ragdoll.
+    pub fn new(default_bone: impl Into<Option<RagdollBoneConfig>>) -> Self {
+        Self {
+            default_bone: default_bone.into(),
+            bones: default(),
+            blend: 1.0,
+        }
+    }
+
+    /// Specifies the [`RagdollBoneConfig`] used for a bone with the given `name`.
+    pub fn with_bone(mut self, name: &str, config: RagdollBoneConfig) -> Self {
+        self.bones.insert(name.to_string(), Some(config));
+        self
+    }
+
+    /// Excludes the bone with the given `name` from the ragdoll.
+    pub fn without_bone(mut self, name: &str) -> Self {
+        self.bones.insert(name.to_string(), None);
+        self
+    }
+
+    /// Sets the initial [`Ragdoll::blend`] of the constructed ragdoll.
+    pub fn with_blend(mut self, blend: Scalar) -> Self {
+        self.blend = blend;
+        self
+    }
+}
+
+/// The type of [joint](crate::dynamics::solver::joints) connecting the body of a bone
+/// to the body of its parent bone in a [ragdoll](self).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq, Default)]
+pub enum RagdollJointType {
+    /// A [`SphericalJoint`] that allows rotation around all axes, like a shoulder or a hip.
+    #[cfg(feature = "3d")]
+    #[default]
+    Spherical,
+    /// A [`RevoluteJoint`] that allows rotation around a single axis, like an elbow or a knee.
+    #[cfg_attr(feature = "2d", default)]
+    Revolute,
+}
+
+/// Configuration for a bone of a [ragdoll](self) constructed using [`RagdollConstructor`].
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct RagdollBoneConfig {
+    /// The radius of the capsule collider of the bone.
+    pub radius: Scalar,
+    /// The [`ColliderDensity`] of the collider of the bone.
+    ///
+    /// `1.0` by default.
+    pub density: ColliderDensity,
+    /// The type of joint connecting the bone to its parent bone.
+    pub joint_type: RagdollJointType,
+    /// The axis of rotation for [revolute](RagdollJointType::Revolute) joints
+    /// in the local space of the ragdoll.
+    ///
+    /// The x-axis by default.
+    #[cfg(feature = "3d")]
+    pub hinge_axis: Vector,
+    /// The extents of the allowed rotation of the bone away from its initial direction
+    /// for [spherical](RagdollJointType::Spherical) joints.
+    #[cfg(feature = "3d")]
+    pub swing_limit: Option<AngleLimit>,
+    /// The extents of the allowed rotation of the bone around its own direction
+    /// for [spherical](RagdollJointType::Spherical) joints,
+    /// or around the hinge axis for [revolute](RagdollJointType::Revolute) joints.
+    pub twist_limit: Option<AngleLimit>,
+}
+
+impl RagdollBoneConfig {
+    /// Creates a new [`RagdollBoneConfig`] with the given collider `radius`
+    /// and a default joint without limits.
+    pub fn new(radius: Scalar) -> Self {
+        Self {
+            radius,
+            density: ColliderDensity(1.0),
+            joint_type: RagdollJointType::default(),
+            #[cfg(feature = "3d")]
+            hinge_axis: Vector::X,
+            #[cfg(feature = "3d")]
+            swing_limit: None,
+            twist_limit: None,
+        }
+    }
+
+    /// Sets the [`ColliderDensity`] of the collider of the bone.
+    pub fn with_density(mut self, density: impl Into<ColliderDensity>) -> Self {
+        self.density = density.into();
+        self
+    }
+
+    /// Connects the bone to its parent bone with a [`RevoluteJoint`] rotating around
+    /// the given `axis` in the local space of the ragdoll.
+    #[cfg(feature = "3d")]
+    pub fn with_hinge_axis(mut self, axis: Vector) -> Self {
+        self.joint_type = RagdollJointType::Revolute;
+        self.hinge_axis = axis;
+        self
+    }
+
+    /// Sets the limits of the allowed rotation of the bone away from its initial direction.
+    #[cfg(feature = "3d")]
+    pub fn with_swing_limits(mut self, min: Scalar, max: Scalar) -> Self {
+        self.swing_limit = Some(AngleLimit::new(min, max));
+        self
+    }
+
+    /// Sets the limits of the allowed rotation of the bone around its own direction,
+    /// or around the hinge axis for [revolute](RagdollJointType::Revolute) joints.
+    pub fn with_twist_limits(mut self, min: Scalar, max: Scalar) -> Self {
+        self.twist_limit = Some(AngleLimit::new(min, max));
+        self
+    }
+}
+
+/// A [ragdoll](self) constructed using [`RagdollConstructor`].
+#[derive(Component, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Component, Debug, PartialEq)]
+pub struct Ragdoll {
+    /// The interpolation factor between the animated and simulated poses of the bones,
+    /// from `0.0` (fully animated) to `1.0` (fully simulated).
+    ///
+    /// When the blend is `0.0`, the bodies of the ragdoll are kinematic and follow the animated pose.
+    /// Otherwise, the bodies are dynamic.
+    pub blend: Scalar,
+    /// The bodies of the ragdoll, ordered so that parent bones come before their children.
+    pub bodies: Vec<Entity>,
+}
+
+impl Ragdoll {
+    /// Returns `true` if the bodies of the ragdoll are simulated.
+    pub fn is_simulated(&self) -> bool {
+        self.blend > 0.0
+    }
+}
+
+/// A component for the rigid body of a bone in a [ragdoll](self).
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Component, Debug, PartialEq)]
+pub struct RagdollBone {
+    /// The entity with the [`Ragdoll`] component.
+    pub ragdoll: Entity,
+    /// The bone entity that the body belongs to.
+    pub bone: Entity,
+    /// The rotation of the bone relative to the body.
+    pub rotation_offset: Quat,
+    /// The local animated pose of the bone, if it has been overwritten by the blended pose.
+    animated_pose: Option<Transform>,
+}
+
+/// Computes the global transform of `entity` by walking up its ancestors,
+/// stopping at the first ancestor whose global transform is `known`.
+fn compute_global_transform(
+    entity: Entity,
+    transforms: &Query<(&Transform, Option<&Parent>)>,
+    known: &HashMap<Entity, GlobalTransform>,
+) -> GlobalTransform {
+    let mut local = Transform::IDENTITY;
+    let mut current = entity;
+
+    loop {
+        if let Some(global) = known.get(&current) {
+            return global.mul_transform(local);
+        }
+        let Ok((transform, parent)) = transforms.get(current) else {
+            return GlobalTransform::from(local);
+        };
+        local = transform.mul_transform(local);
+        let Some(parent) = parent else {
+            return GlobalTransform::from(local);
+        };
+        current = parent.get();
+    }
+}
+
+/// Returns the world-space position of a global transform.
+fn global_position(transform: &GlobalTransform) -> Vector {
+    #[cfg(feature = "2d")]
+    {
+        transform.translation().truncate().adjust_precision()
+    }
+    #[cfg(feature = "3d")]
+    {
+        transform.translation().adjust_precision()
+    }
+}
+
+/// Constructs ragdolls for entities with the [`RagdollConstructor`] component.
+///
+/// If an entity has a `SceneInstance`, its ragdoll is only constructed once the scene is ready.
+#[allow(clippy::type_complexity)]
+fn init_ragdoll_constructors(
+    mut commands: Commands,
+    #[cfg(feature = "bevy_scene")] scene_spawner: Res<SceneSpawner>,
+    #[cfg(feature = "bevy_scene")] scenes: Query<&Handle<Scene>>,
+    #[cfg(feature = "bevy_scene")] scene_instances: Query<&SceneInstance>,
+    constructors: Query<(Entity, &RagdollConstructor)>,
+    children: Query<&Children>,
+    names: Query<&Name>,
+    transforms: Query<(&Transform, Option<&Parent>)>,
+) {
+    for (ragdoll_entity, constructor) in &constructors {
+        #[cfg(feature = "bevy_scene")]
+        {
+            if scenes.contains(ragdoll_entity) {
+                if let Ok(scene_instance) = scene_instances.get(ragdoll_entity) {
+                    if !scene_spawner.instance_is_ready(**scene_instance) {
+                        // Wait for the scene to be ready
+                        continue;
+                    }
+                } else {
+                    // SceneInstance is added in the SpawnScene schedule, so it might not be available yet
+                    continue;
+                }
+            }
+        }
+
+        let mut globals = HashMap::default();
+        let ragdoll_global = compute_global_transform(ragdoll_entity, &transforms, &globals);
+        globals.insert(ragdoll_entity, ragdoll_global);
+
+        // All bodies share the rotation of the ragdoll, so that the joints are at rest in the initial pose.
+        let body_rotation = ragdoll_global.to_scale_rotation_translation().1;
+        let rotation = Rotation::from(body_rotation.adjust_precision());
+
+        // Collect the bones, parents before children.
+        let mut bones: Vec<(Entity, RagdollBoneConfig, Option<Entity>)> = vec![];
+        for entity in children.iter_descendants(ragdoll_entity) {
+            let Ok(name) = names.get(entity) else {
+                continue;
+            };
+            let Some(config) = constructor
+                .bones
+                .get(name.as_str())
+                .copied()
+                .unwrap_or(constructor.default_bone)
+            else {
+                continue;
+            };
+
+            // Find the closest ancestor that is a bone.
+            let mut parent_bone = None;
+            let mut current = entity;
+            while let Ok((_, Some(parent))) = transforms.get(current) {
+                current = parent.get();
+                if current == ragdoll_entity {
+                    break;
+                }
+                if bones.iter().any(|(bone, ..)| *bone == current) {
+                    parent_bone = Some(current);
+                    break;
+                }
+            }
+
+            globals.insert(
+                entity,
+                compute_global_transform(entity, &transforms, &globals),
+            );
+            bones.push((entity, config, parent_bone));
+        }
+
+        let mut bodies = HashMap::<Entity, Entity>::default();
+        let mut ragdoll_bodies = Vec::with_capacity(bones.len());
+
+        for (bone, config, _) in bones.iter() {
+            let global = globals[bone];
+            let position = global_position(&global);
+
+            // Extend the capsule towards the average position of the child bones.
+            let child_positions: Vec<Vector> = bones
+                .iter()
+                .filter(|(_, _, parent)| *parent == Some(*bone))
+                .map(|(child, ..)| global_position(&globals[child]))
+                .collect();
+            let collider = if child_positions.is_empty() {
+                #[cfg(feature = "2d")]
+                {
+                    Collider::circle(config.radius)
+                }
+                #[cfg(feature = "3d")]
+                {
+                    Collider::sphere(config.radius)
+                }
+            } else {
+                let end = child_positions.iter().sum::<Vector>() / child_positions.len() as Scalar;
+                Collider::capsule_endpoints(
+                    config.radius,
+                    Vector::ZERO,
+                    rotation.inverse() * (end - position),
+                )
+            };
+
+            let body = commands
+                .spawn((
+                    if constructor.blend > 0.0 {
+                        RigidBody::Dynamic
+                    } else {
+                        RigidBody::Kinematic
+                    },
+                    collider,
+                    config.density,
+                    Position(position),
+                    rotation,
+                    TransformBundle::from_transform(
+                        Transform::from_translation(global.translation())
+                            .with_rotation(body_rotation),
+                    ),
+                    RagdollBone {
+                        ragdoll: ragdoll_entity,
+                        bone: *bone,
+                        rotation_offset: body_rotation.inverse()
+                            * global.to_scale_rotation_translation().1,
+                        animated_pose: None,
+                    },
+                ))
+                .id();
+
+            bodies.insert(*bone, body);
+            ragdoll_bodies.push(body);
+        }
+
+        for (bone, config, parent_bone) in bones.iter() {
+            let Some(parent_bone) = parent_bone else {
+                continue;
+            };
+            let (body1, body2) = (bodies[parent_bone], bodies[bone]);
+            let anchor = rotation.inverse()
+                * (global_position(&globals[bone]) - global_position(&globals[parent_bone]));
+
+            match config.joint_type {
+                #[cfg(feature = "3d")]
+                RagdollJointType::Spherical => {
+                    // Limit the swing and twist relative to the direction of the bone.
+                    let direction = anchor.try_normalize().unwrap_or(Vector::Y);
+                    let mut joint = SphericalJoint::new(body1, body2).with_local_anchor_1(anchor);
+                    joint.swing_axis = direction;
+                    joint.twist_axis = direction.any_orthonormal_vector();
+                    joint.swing_limit = config.swing_limit;
+                    joint.twist_limit = config.twist_limit;
+                    commands.spawn(joint);
+                }
+                RagdollJointType::Revolute => {
+                    let mut joint = RevoluteJoint::new(body1, body2).with_local_anchor_1(anchor);
+                    #[cfg(feature = "3d")]
+                    {
+                        joint = joint.with_aligned_axis(config.hinge_axis);
+                    }
+                    joint.angle_limit = config.twist_limit;
+                    commands.spawn(joint);
+                }
+            }
+        }
+
+        commands
+            .entity(ragdoll_entity)
+            .remove::<RagdollConstructor>()
+            .insert(Ragdoll {
+                blend: constructor.blend,
+                bodies: ragdoll_bodies,
+            });
+    }
+}
+
+/// Makes the bodies of [ragdolls](self) kinematic when they are fully animated, and dynamic otherwise.
+fn update_ragdoll_body_types(
+    ragdolls: Query<&Ragdoll, Changed<Ragdoll>>,
+    mut bodies: Query<&mut RigidBody, With<RagdollBone>>,
+) {
+    for ragdoll in &ragdolls {
+        let rb = if ragdoll.is_simulated() {
+            RigidBody::Dynamic
+        } else {
+            RigidBody::Kinematic
+        };
+        let mut iter = bodies.iter_many_mut(&ragdoll.bodies);
+        while let Some(mut body) = iter.fetch_next() {
+            if *body != rb {
+                *body = rb;
+            }
+        }
+    }
+}
+
+/// Moves the kinematic bodies of fully animated [ragdolls](self) towards the animated pose of their bones.
+///
+/// The bodies are moved using velocities, so that they keep their momentum when the ragdoll starts being simulated.
+fn follow_animated_poses(
+    ragdolls: Query<&Ragdoll>,
+    mut bodies: Query<(
+        &RagdollBone,
+        &Position,
+        &Rotation,
+        &mut LinearVelocity,
+        &mut AngularVelocity,
+    )>,
+    transforms: Query<(&Transform, Option<&Parent>)>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+    if delta_secs == 0.0 {
+        return;
+    }
+
+    let mut globals = HashMap::default();
+
+    for ragdoll in ragdolls.iter().filter(|ragdoll| !ragdoll.is_simulated()) {
+        let mut iter = bodies.iter_many_mut(&ragdoll.bodies);
+        while let Some((bone, position, rotation, mut lin_vel, mut ang_vel)) = iter.fetch_next() {
+            let global = compute_global_transform(bone.bone, &transforms, &globals);
+            globals.insert(bone.bone, global);
+
+            let target_position = global_position(&global);
+            let target_rotation = Rotation::from(
+                (global.to_scale_rotation_translation().1 * bone.rotation_offset.inverse())
+                    .adjust_precision(),
+            );
+
+            lin_vel.0 = (target_position - position.0) / delta_secs;
+
+            #[cfg(feature = "2d")]
+            {
+                ang_vel.0 = rotation.angle_between(target_rotation) / delta_secs;
+            }
+            #[cfg(feature = "3d")]
+            {
+                let (axis, mut angle) = (target_rotation.0 * rotation.0.inverse()).to_axis_angle();
+                if angle > PI {
+                    angle -= 2.0 * PI;
+                }
+                ang_vel.0 = axis * angle / delta_secs;
+            }
+        }
+    }
+}
+
+/// Writes the pose of the bones of simulated [ragdolls](self), interpolated between
+/// the animated and simulated poses, to their `Transform`.
+fn write_blended_poses(
+    ragdolls: Query<&Ragdoll>,
+    mut bodies: Query<(&mut RagdollBone, &Position, &Rotation)>,
+    mut transforms: Query<(&mut Transform, Option<&Parent>)>,
+) {
+    let mut animated_globals = HashMap::default();
+    let mut blended_globals = HashMap::default();
+
+    for ragdoll in ragdolls.iter().filter(|ragdoll| ragdoll.is_simulated()) {
+        #[allow(clippy::unnecessary_cast)]
+        let blend = ragdoll.blend.min(1.0) as f32;
+
+        let mut iter = bodies.iter_many_mut(&ragdoll.bodies);
+        while let Some((mut bone, position, rotation)) = iter.fetch_next() {
+            let Ok((_, parent)) = transforms.get(bone.bone) else {
+                continue;
+            };
+            let parent_global = parent.map_or(GlobalTransform::IDENTITY, |parent| {
+                compute_global_transform(parent.get(), &transforms.to_readonly(), &blended_globals)
+            });
+
+            let animated =
+                compute_global_transform(bone.bone, &transforms.to_readonly(), &animated_globals)
+                    .compute_transform();
+            animated_globals.insert(bone.bone, GlobalTransform::from(animated));
+
+            #[cfg(feature = "2d")]
+            let simulated_translation = position.f32().extend(animated.translation.z);
+            #[cfg(feature = "3d")]
+            let simulated_translation = position.f32();
+            let simulated_rotation = Quaternion::from(*rotation).f32() * bone.rotation_offset;
+
+            let blended = GlobalTransform::from(
+                Transform::from_translation(
+                    animated.translation.lerp(simulated_translation, blend),
+                )
+                .with_rotation(animated.rotation.slerp(simulated_rotation, blend))
+                .with_scale(animated.scale),
+            );
+            blended_globals.insert(bone.bone, blended);
+
+            let Ok((mut transform, _)) = transforms.get_mut(bone.bone) else {
+                continue;
+            };
+            bone.animated_pose = Some(*transform);
+            *transform = blended.reparented_to(&parent_global);
+        }
+    }
+}
+
+/// Restores the animated pose of the bones of [ragdolls](self) overwritten by [`write_blended_poses`],
+/// so that animation systems and gameplay logic see and modify the animated pose.
+fn restore_animated_poses(
+    mut bodies: Query<&mut RagdollBone>,
+    mut transforms: Query<&mut Transform, Without<RagdollBone>>,
+) {
+    for mut bone in &mut bodies {
+        let Some(animated_pose) = bone.animated_pose.take() else {
+            continue;
+        };
+        if let Ok(mut transform) = transforms.get_mut(bone.bone) {
+            *transform = animated_pose;
+        }
+    }
+}
@@ -473,6 +473,9 @@ pub enum CoefficientCombine {
 ///
 /// When two bodies collide, their restitution coefficients are combined using the specified [`CoefficientCombine`] rule.
 ///
+/// [`Restitution`] can also be added to child colliders of a rigid body. It then overrides the restitution
+/// of the body for contacts involving that collider, so that a single body can have surfaces with different properties.
+///
 /// ## Example
 ///
 /// Create a new [`Restitution`] component with a restitution coefficient of 0.4:
@@ -506,6 +509,7 @@ pub enum CoefficientCombine {
 #[doc(alias = "Elasticity")]
 #[derive(Reflect, Clone, Copy, Component, Debug, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
 #[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
 #[reflect(Debug, Component, PartialEq)]
 pub struct Restitution {
@@ -602,6 +606,9 @@ impl From<Scalar> for Restitution {
 /// 0.0: No friction at all, the body slides indefinitely\
 /// 1.0: High friction\
 ///
+/// [`Friction`] can also be added to child colliders of a rigid body. It then overrides the friction
+/// of the body for contacts involving that collider, for example to give a vehicle rubber wheels and a steel chassis.
+///
 /// ## Example
 ///
 /// Create a new [`Friction`] component with dynamic and static friction coefficients of 0.4:
@@ -619,6 +626,14 @@ impl From<Scalar> for Restitution {
 /// Friction::new(0.4).with_static_coefficient(0.6)
 /// ```
 ///
+/// Resist rolling and spinning motion, for example to stop balls from rolling forever:
+///
+/// ```ignore
+/// Friction::new(0.4).with_rolling_coefficient(0.02)
+/// // Spinning friction is only available in 3D
+/// Friction::new(0.4).with_spinning_coefficient(0.02)
+/// ```
+///
 /// Configure how the friction coefficients of two [`Friction`] components are combined with [`CoefficientCombine`]:
 ///
 /// ```ignore
@@ -640,8 +655,27 @@ impl From<Scalar> for Restitution {
 ///     Friction::new(0.4).with_combine_rule(CoefficientCombine::Multiply)
 /// );
 /// ```
+///
+/// ## Migration
+///
+/// The [`rolling_coefficient`](Self::rolling_coefficient) and `spinning_coefficient` fields have been added,
+/// so struct expressions that list every field no longer compile. Use the constructors and builder methods
+/// like [`Friction::new`] and [`with_rolling_coefficient`](Self::with_rolling_coefficient) instead,
+/// or fill in the remaining fields with `..Friction::ZERO` or `..default()`:
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+///
+/// const ICE: Friction = Friction {
+///     dynamic_coefficient: 0.05,
+///     static_coefficient: 0.1,
+///     ..Friction::ZERO
+/// };
+/// ```
 #[derive(Reflect, Clone, Copy, Component, Debug, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
 #[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
 #[reflect(Debug, Component, PartialEq)]
 pub struct Friction {
@@ -649,15 +683,31 @@ pub struct Friction {
     pub dynamic_coefficient: Scalar,
     /// Coefficient of static friction.
     pub static_coefficient: Scalar,
+    /// Coefficient of rolling friction.
+    ///
+    /// Rolling friction resists relative rotation of the bodies around the axes perpendicular
+    /// to the contact normal. The resisting torque is limited by the coefficient multiplied
+    /// by the normal force, so the coefficient is a distance, typically a small fraction
+    /// of the radius of the rolling body.
+    pub rolling_coefficient: Scalar,
+    /// Coefficient of spinning friction, also known as torsional friction.
+    ///
+    /// Spinning friction resists relative rotation of the bodies around the contact normal.
+    /// Like [rolling friction](Self::rolling_coefficient), the coefficient is a distance.
+    #[cfg(feature = "3d")]
+    pub spinning_coefficient: Scalar,
     /// The coefficient combine rule used when two bodies collide.
     pub combine_rule: CoefficientCombine,
 }
 
 impl Friction {
-    /// Zero dynamic and static friction and [`CoefficientCombine::Average`].
+    /// Zero friction coefficients and [`CoefficientCombine::Average`].
     pub const ZERO: Self = Self {
         dynamic_coefficient: 0.0,
         static_coefficient: 0.0,
+        rolling_coefficient: 0.0,
+        #[cfg(feature = "3d")]
+        spinning_coefficient: 0.0,
         combine_rule: CoefficientCombine::Average,
     };
 
@@ -694,26 +744,40 @@ impl Friction {
         }
     }
 
+    /// Sets the coefficient of rolling friction.
+    pub fn with_rolling_coefficient(&self, coefficient: Scalar) -> Self {
+        Self {
+            rolling_coefficient: coefficient,
+            ..*self
+        }
+    }
+
+    /// Sets the coefficient of spinning friction.
+    #[cfg(feature = "3d")]
+    pub fn with_spinning_coefficient(&self, coefficient: Scalar) -> Self {
+        Self {
+            spinning_coefficient: coefficient,
+            ..*self
+        }
+    }
+
     /// Combines the properties of two `Friction` components.
     pub fn combine(&self, other: Self) -> Self {
         // Choose rule with higher priority
         let rule = self.combine_rule.max(other.combine_rule);
-        let (dynamic1, dynamic2) = (self.dynamic_coefficient, other.dynamic_coefficient);
-        let (static1, static2) = (self.static_coefficient, other.static_coefficient);
+        let combine = |a: Scalar, b: Scalar| match rule {
+            CoefficientCombine::Average => (a + b) * 0.5,
+            CoefficientCombine::Min => a.min(b),
+            CoefficientCombine::Multiply => a * b,
+            CoefficientCombine::Max => a.max(b),
+        };
 
         Self {
-            dynamic_coefficient: match rule {
-                CoefficientCombine::Average => (dynamic1 + dynamic2) * 0.5,
-                CoefficientCombine::Min => dynamic1.min(dynamic2),
-                CoefficientCombine::Multiply => dynamic1 * dynamic2,
-                CoefficientCombine::Max => dynamic1.max(dynamic2),
-            },
-            static_coefficient: match rule {
-                CoefficientCombine::Average => (static1 + static2) * 0.5,
-                CoefficientCombine::Min => static1.min(static2),
-                CoefficientCombine::Multiply => static1 * static2,
-                CoefficientCombine::Max => static1.max(static2),
-            },
+            dynamic_coefficient: combine(self.dynamic_coefficient, other.dynamic_coefficient),
+            static_coefficient: combine(self.static_coefficient, other.static_coefficient),
+            rolling_coefficient: combine(self.rolling_coefficient, other.rolling_coefficient),
+            #[cfg(feature = "3d")]
+            spinning_coefficient: combine(self.spinning_coefficient, other.spinning_coefficient),
             combine_rule: rule,
         }
     }
@@ -724,6 +788,9 @@ impl Default for Friction {
         Self {
             dynamic_coefficient: 0.3,
             static_coefficient: 0.3,
+            rolling_coefficient: 0.0,
+            #[cfg(feature = "3d")]
+            spinning_coefficient: 0.0,
             combine_rule: CoefficientCombine::default(),
         }
     }
@@ -739,6 +806,84 @@ impl From<Scalar> for Friction {
     }
 }
 
+/// Scales the [`Friction`] of a collider differently along different directions of its surface.
+///
+/// The primary scale is applied to friction along the given local `direction`, and the secondary scale
+/// is applied to friction perpendicular to it. This can be used for surfaces like ice tracks or skis
+/// that slide easily in one direction but grip in others.
+///
+/// If both colliders in a contact have anisotropic friction, the smaller scale along each direction is used,
+/// like with [`CoefficientCombine::Min`].
+///
+/// ## Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::{math::Vector, prelude::*};")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::{math::Vector, prelude::*};")]
+/// use bevy::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     // A ski that slides easily forwards and backwards, but not sideways.
+///     commands.spawn((
+///         RigidBody::Dynamic,
+#[cfg_attr(feature = "2d", doc = "        Collider::rectangle(2.0, 0.1),")]
+#[cfg_attr(feature = "3d", doc = "        Collider::cuboid(0.2, 0.1, 2.0),")]
+#[cfg_attr(
+    feature = "2d",
+    doc = "        AnisotropicFriction::new(Vector::X, 0.05, 1.0),"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "        AnisotropicFriction::new(Vector::Z, 0.05, 1.0),"
+)]
+///     ));
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, PartialEq)]
+pub struct AnisotropicFriction {
+    /// The unit direction along which the primary scale is applied.
+    ///
+    /// This is in the local space of the collider, except for the values stored in [`ContactManifold`]
+    /// and [`ContactConstraint`](dynamics::solver::contact::ContactConstraint), which are in world space.
+    pub direction: Vector,
+    /// The scale applied to the friction coefficients along the `direction`.
+    pub primary_scale: Scalar,
+    /// The scale applied to the friction coefficients perpendicular to the `direction`.
+    pub secondary_scale: Scalar,
+}
+
+impl AnisotropicFriction {
+    /// Creates a new [`AnisotropicFriction`] component with the given local direction
+    /// and friction scales along and perpendicular to it.
+    pub fn new(direction: Vector, primary_scale: Scalar, secondary_scale: Scalar) -> Self {
+        Self {
+            direction: direction.normalize_or_zero(),
+            primary_scale,
+            secondary_scale,
+        }
+    }
+
+    /// Returns the friction scale along the given unit `tangent` direction.
+    ///
+    /// The scale is interpolated elliptically between the primary and secondary scales
+    /// based on the alignment of the tangent with the `direction`.
+    pub fn scale_along(&self, tangent: Vector) -> Scalar {
+        let alignment_squared = tangent.dot(self.direction).powi(2).min(1.0);
+        (alignment_squared * self.primary_scale.powi(2)
+            + (1.0 - alignment_squared) * self.secondary_scale.powi(2))
+        .sqrt()
+    }
+}
+
+impl Default for AnisotropicFriction {
+    fn default() -> Self {
+        Self::new(Vector::X, 1.0, 1.0)
+    }
+}
+
 /// Automatically slows down a dynamic [rigid body](RigidBody), decreasing its
 /// [linear velocity](LinearVelocity) each frame. This can be used to simulate air resistance.
 ///
@@ -1,6 +1,13 @@
 #![allow(missing_docs)]
 
 use crate::{prelude::*, utils::get_pos_translation};
+#[cfg(feature = "physics-material")]
+use bevy::asset::Handle;
+#[cfg(all(feature = "parallel", not(feature = "enhanced-determinism")))]
+use bevy::{
+    ecs::component::Tick,
+    prelude::{Component, Mut},
+};
 use bevy::{
     ecs::query::QueryData,
     prelude::{Entity, Has, Ref},
@@ -28,6 +35,8 @@ pub struct RigidBodyQuery {
     pub center_of_mass: &'static mut CenterOfMass,
     pub friction: &'static Friction,
     pub restitution: &'static Restitution,
+    #[cfg(feature = "physics-material")]
+    pub material: Option<&'static Handle<PhysicsMaterial>>,
     pub locked_axes: Option<&'static LockedAxes>,
     pub dominance: Option<&'static Dominance>,
     pub time_sleeping: &'static mut TimeSleeping,
@@ -35,6 +44,142 @@ pub struct RigidBodyQuery {
     pub is_sensor: Has<Sensor>,
 }
 
+/// A component copied from a [`RigidBodyQuery`] along with its change ticks.
+#[cfg(all(feature = "parallel", not(feature = "enhanced-determinism")))]
+struct CopiedComponent<T> {
+    value: T,
+    added: Tick,
+    changed: Tick,
+}
+
+#[cfg(all(feature = "parallel", not(feature = "enhanced-determinism")))]
+impl<T: Component + Copy> CopiedComponent<T> {
+    fn new(value: &T) -> Self {
+        Self {
+            value: *value,
+            added: Tick::new(0),
+            changed: Tick::new(0),
+        }
+    }
+
+    fn as_ref(&self) -> Ref<'_, T> {
+        Ref::new(
+            &self.value,
+            &self.added,
+            &self.changed,
+            Tick::new(0),
+            Tick::new(0),
+        )
+    }
+
+    fn as_mut(&mut self) -> Mut<'_, T> {
+        Mut::new(
+            &mut self.value,
+            &mut self.added,
+            &mut self.changed,
+            Tick::new(0),
+            Tick::new(0),
+        )
+    }
+}
+
+/// A local copy of a rigid body that can be accessed like a [`RigidBodyQueryItem`]
+/// without mutable access to the body itself.
+///
+/// Static and kinematic bodies can be [shared](crate::dynamics::solver::graph_coloring) by contact constraints
+/// that are solved in parallel. The solver only modifies dynamic bodies, so these constraints are solved
+/// against a copy of the shared body, and the copy is discarded afterwards.
+#[cfg(all(feature = "parallel", not(feature = "enhanced-determinism")))]
+pub(crate) struct RigidBodyCopy<'w> {
+    entity: Entity,
+    rb: CopiedComponent<RigidBody>,
+    position: CopiedComponent<Position>,
+    rotation: CopiedComponent<Rotation>,
+    previous_rotation: CopiedComponent<PreviousRotation>,
+    accumulated_translation: CopiedComponent<AccumulatedTranslation>,
+    linear_velocity: CopiedComponent<LinearVelocity>,
+    pre_solve_linear_velocity: CopiedComponent<PreSolveLinearVelocity>,
+    angular_velocity: CopiedComponent<AngularVelocity>,
+    pre_solve_angular_velocity: CopiedComponent<PreSolveAngularVelocity>,
+    mass: CopiedComponent<Mass>,
+    inverse_mass: CopiedComponent<InverseMass>,
+    inertia: CopiedComponent<Inertia>,
+    inverse_inertia: CopiedComponent<InverseInertia>,
+    center_of_mass: CopiedComponent<CenterOfMass>,
+    friction: &'w Friction,
+    restitution: &'w Restitution,
+    #[cfg(feature = "physics-material")]
+    material: Option<&'w Handle<PhysicsMaterial>>,
+    locked_axes: Option<&'w LockedAxes>,
+    dominance: Option<&'w Dominance>,
+    time_sleeping: CopiedComponent<TimeSleeping>,
+    is_sleeping: bool,
+    is_sensor: bool,
+}
+
+#[cfg(all(feature = "parallel", not(feature = "enhanced-determinism")))]
+impl<'w> RigidBodyCopy<'w> {
+    /// Copies the given rigid body.
+    pub(crate) fn new(body: RigidBodyQueryReadOnlyItem<'w>) -> Self {
+        Self {
+            entity: body.entity,
+            rb: CopiedComponent::new(&*body.rb),
+            position: CopiedComponent::new(body.position),
+            rotation: CopiedComponent::new(body.rotation),
+            previous_rotation: CopiedComponent::new(body.previous_rotation),
+            accumulated_translation: CopiedComponent::new(body.accumulated_translation),
+            linear_velocity: CopiedComponent::new(body.linear_velocity),
+            pre_solve_linear_velocity: CopiedComponent::new(body.pre_solve_linear_velocity),
+            angular_velocity: CopiedComponent::new(body.angular_velocity),
+            pre_solve_angular_velocity: CopiedComponent::new(body.pre_solve_angular_velocity),
+            mass: CopiedComponent::new(body.mass),
+            inverse_mass: CopiedComponent::new(body.inverse_mass),
+            inertia: CopiedComponent::new(body.inertia),
+            inverse_inertia: CopiedComponent::new(body.inverse_inertia),
+            center_of_mass: CopiedComponent::new(body.center_of_mass),
+            friction: body.friction,
+            restitution: body.restitution,
+            #[cfg(feature = "physics-material")]
+            material: body.material,
+            locked_axes: body.locked_axes,
+            dominance: body.dominance,
+            time_sleeping: CopiedComponent::new(body.time_sleeping),
+            is_sleeping: body.is_sleeping,
+            is_sensor: body.is_sensor,
+        }
+    }
+
+    /// Returns a query item that reads from and writes to the copy.
+    pub(crate) fn as_item(&mut self) -> RigidBodyQueryItem<'_> {
+        RigidBodyQueryItem {
+            entity: self.entity,
+            rb: self.rb.as_ref(),
+            position: self.position.as_mut(),
+            rotation: self.rotation.as_mut(),
+            previous_rotation: self.previous_rotation.as_mut(),
+            accumulated_translation: self.accumulated_translation.as_mut(),
+            linear_velocity: self.linear_velocity.as_mut(),
+            pre_solve_linear_velocity: self.pre_solve_linear_velocity.as_mut(),
+            angular_velocity: self.angular_velocity.as_mut(),
+            pre_solve_angular_velocity: self.pre_solve_angular_velocity.as_mut(),
+            mass: self.mass.as_mut(),
+            inverse_mass: self.inverse_mass.as_mut(),
+            inertia: self.inertia.as_mut(),
+            inverse_inertia: self.inverse_inertia.as_mut(),
+            center_of_mass: self.center_of_mass.as_mut(),
+            friction: self.friction,
+            restitution: self.restitution,
+            #[cfg(feature = "physics-material")]
+            material: self.material,
+            locked_axes: self.locked_axes,
+            dominance: self.dominance,
+            time_sleeping: self.time_sleeping.as_mut(),
+            is_sleeping: self.is_sleeping,
+            is_sensor: self.is_sensor,
+        }
+    }
+}
+
 impl<'w> RigidBodyQueryItem<'w> {
     /// Computes the velocity at the given `point` relative to the center of the body.
     pub fn velocity_at_point(&self, point: Vector) -> Vector {
@@ -16,6 +16,10 @@ use bevy::{
 /// Bodies are woken up when an active body or constraint interacts with them, or when gravity changes,
 /// or when the body's position, rotation, velocity, or external forces are changed.
 ///
+/// Bodies connected by contacts and joints form [simulation islands](dynamics::islands) that sleep and wake
+/// as a whole. The islands are managed by the [`IslandPlugin`]. Without it, bodies in contact with
+/// other dynamic bodies never fall asleep.
+///
 /// This plugin does *not* handle constraints waking up bodies. That is done by the [solver](dynamics::solver).
 ///
 /// The sleeping systems run in [`PhysicsStepSet::Sleeping`].
@@ -25,6 +29,7 @@ impl Plugin for SleepingPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<SleepingThreshold>()
             .init_resource::<DeactivationTime>()
+            .init_resource::<PhysicsIslands>()
             .init_resource::<LastPhysicsTick>();
 
         let physics_schedule = app
@@ -107,6 +112,9 @@ impl Default for DeactivationTime {
 
 /// Adds the [`Sleeping`] component to bodies whose linear and anigular velocities have been
 /// under the [`SleepingThreshold`] for a duration indicated by [`DeactivationTime`].
+///
+/// Bodies in [simulation islands](dynamics::islands) only fall asleep together with the rest of their island,
+/// once all of the bodies in the island have been at rest for long enough.
 #[allow(clippy::type_complexity)]
 pub fn mark_sleeping_bodies(
     mut commands: Commands,
@@ -122,6 +130,7 @@ pub fn mark_sleeping_bodies(
         (Without<Sleeping>, Without<SleepingDisabled>),
     >,
     rb_query: Query<&RigidBody>,
+    mut islands: ResMut<PhysicsIslands>,
     deactivation_time: Res<DeactivationTime>,
     sleep_threshold: Res<SleepingThreshold>,
     length_unit: Res<PhysicsLengthUnit>,
@@ -132,16 +141,8 @@ pub fn mark_sleeping_bodies(
 
     for (entity, rb, mut lin_vel, mut ang_vel, mut time_sleeping, colliding_entities) in &mut query
     {
-        // Only dynamic bodies can sleep, and only if they are not
-        // in contact with other dynamic bodies.
-        //
-        // Contacts with other types of bodies will be allowed once
-        // sleeping/waking is implemented with simulation islands.
-        if !rb.is_dynamic()
-            || rb_query
-                .iter_many(colliding_entities.iter())
-                .any(|rb| rb.is_dynamic())
-        {
+        // Only dynamic bodies can sleep.
+        if !rb.is_dynamic() {
             continue;
         }
 
@@ -165,6 +166,21 @@ pub fn mark_sleeping_bodies(
             time_sleeping.0 = 0.0;
         }
 
+        // Bodies in islands are handled below.
+        if islands.get(entity).is_some() {
+            continue;
+        }
+
+        // Bodies that are not in an island, for example because they were just spawned
+        // or the `IslandPlugin` is disabled, can only sleep if they are not in contact
+        // with other dynamic bodies.
+        if rb_query
+            .iter_many(colliding_entities.iter())
+            .any(|rb| rb.is_dynamic())
+        {
+            continue;
+        }
+
         // If the body has been still for long enough, set it to sleep and reset velocities.
         if time_sleeping.0 > deactivation_time.0 {
             commands.entity(entity).try_insert(Sleeping);
@@ -172,6 +188,30 @@ pub fn mark_sleeping_bodies(
             *ang_vel = AngularVelocity::ZERO;
         }
     }
+
+    // Put islands to sleep once all of their bodies have been still for long enough.
+    // Bodies with `SleepingDisabled` keep their whole island awake.
+    for island in islands.iter_mut().filter(|island| !island.is_sleeping) {
+        let can_sleep = island.bodies.iter().all(|&entity| {
+            query
+                .get(entity)
+                .is_ok_and(|(.., time_sleeping, _)| time_sleeping.0 > deactivation_time.0)
+        });
+
+        if !can_sleep {
+            continue;
+        }
+
+        for &entity in island.bodies.iter() {
+            if let Ok((_, _, mut lin_vel, mut ang_vel, ..)) = query.get_mut(entity) {
+                commands.entity(entity).try_insert(Sleeping);
+                *lin_vel = LinearVelocity::ZERO;
+                *ang_vel = AngularVelocity::ZERO;
+            }
+        }
+
+        island.is_sleeping = true;
+    }
 }
 
 /// A [`Tick`] corresponding to the end of the previous run of the [`PhysicsSchedule`].
@@ -216,6 +256,7 @@ pub(crate) fn wake_on_changed(
                 Changed<ExternalImpulse>,
                 Changed<ExternalAngularImpulse>,
                 Changed<GravityScale>,
+                Changed<GravityOverride>,
             )>,
         >,
     )>,
@@ -259,16 +300,24 @@ fn wake_all_sleeping_bodies(
 }
 
 /// Wakes up bodies when they stop colliding.
+///
+/// The rest of the [island](dynamics::islands) of a woken body is woken up by the [`IslandPlugin`].
 #[allow(clippy::type_complexity)]
 fn wake_on_collision_ended(
     mut commands: Commands,
     moved_bodies: Query<Ref<Position>, (Changed<Position>, Without<Sleeping>)>,
     colliders: Query<(&ColliderParent, Ref<ColliderTransform>)>,
     collisions: Res<Collisions>,
-    mut sleeping: Query<(Entity, &mut TimeSleeping)>,
+    mut sleeping: Query<(Entity, &mut TimeSleeping, Has<Sleeping>)>,
 ) {
-    // Wake up bodies when a body they're colliding with moves.
-    for (entity, mut time_sleeping) in &mut sleeping {
+    // Wake up sleeping bodies when a body they're colliding with moves.
+    for (entity, mut time_sleeping, is_sleeping) in &mut sleeping {
+        // Awake bodies in contact with moving bodies must still be able to
+        // accumulate sleeping time, so that resting stacks can fall asleep.
+        if !is_sleeping {
+            continue;
+        }
+
         // Here we could use CollidingEntities, but it'd be empty if the ContactReportingPlugin was disabled.
         let mut colliding_entities = collisions.collisions_with_entity(entity).map(|c| {
             if entity == c.entity1 {
@@ -293,11 +342,11 @@ fn wake_on_collision_ended(
         if contacts.during_current_frame || !contacts.during_previous_frame {
             continue;
         }
-        if let Ok((_, mut time_sleeping)) = sleeping.get_mut(contacts.entity1) {
+        if let Ok((_, mut time_sleeping, _)) = sleeping.get_mut(contacts.entity1) {
             commands.entity(contacts.entity1).remove::<Sleeping>();
             time_sleeping.0 = 0.0;
         }
-        if let Ok((_, mut time_sleeping)) = sleeping.get_mut(contacts.entity2) {
+        if let Ok((_, mut time_sleeping, _)) = sleeping.get_mut(contacts.entity2) {
             commands.entity(contacts.entity2).remove::<Sleeping>();
             time_sleeping.0 = 0.0;
         }
@@ -1,9 +1,15 @@
 //! Constraints and other types used for solving contacts.
 
 mod normal_part;
+mod rolling_part;
+#[cfg(feature = "3d")]
+mod spinning_part;
 mod tangent_part;
 
 pub use normal_part::ContactNormalPart;
+pub use rolling_part::ContactRollingPart;
+#[cfg(feature = "3d")]
+pub use spinning_part::ContactSpinningPart;
 pub use tangent_part::ContactTangentPart;
 
 use crate::{dynamics::solver::softness_parameters::SoftnessCoefficients, prelude::*};
@@ -71,10 +77,32 @@ pub struct ContactConstraint {
     pub friction: Friction,
     /// The combined [`Restitution`] of the bodies.
     pub restitution: Restitution,
+    /// The [`AnisotropicFriction`] of the first collider, with the direction in world space.
+    ///
+    /// See [`ContactManifold::anisotropic_friction1`].
+    pub anisotropic_friction1: Option<AnisotropicFriction>,
+    /// The [`AnisotropicFriction`] of the second collider, with the direction in world space.
+    ///
+    /// See [`ContactManifold::anisotropic_friction2`].
+    pub anisotropic_friction2: Option<AnisotropicFriction>,
+    /// The target relative velocity of the second body with respect to the first body
+    /// along the contact surface, in world space.
+    ///
+    /// See [`ContactManifold::tangent_velocity`].
+    pub tangent_velocity: Vector,
     /// The world-space contact normal shared by all points in the contact manifold.
     pub normal: Vector,
     /// The contact points in the manifold. Each point shares the same `normal`.
     pub points: Vec<ContactConstraintPoint>,
+    /// The rolling friction part of the contact constraint, shared by all points.
+    ///
+    /// `None` if the coefficient of rolling friction is zero.
+    pub rolling_part: Option<ContactRollingPart>,
+    /// The spinning friction part of the contact constraint, shared by all points.
+    ///
+    /// `None` if the coefficient of spinning friction is zero.
+    #[cfg(feature = "3d")]
+    pub spinning_part: Option<ContactSpinningPart>,
     /// The index of the [`ContactManifold`] in the [`Contacts`] stored for the two bodies.
     pub manifold_index: usize,
 }
@@ -93,8 +121,6 @@ impl ContactConstraint {
         collider_transform2: Option<ColliderTransform>,
         collision_margin: impl Into<CollisionMargin>,
         speculative_margin: impl Into<SpeculativeMargin>,
-        friction: Friction,
-        restitution: Restitution,
         softness: SoftnessCoefficients,
         warm_start: bool,
         delta_secs: Scalar,
@@ -122,10 +148,16 @@ impl ContactConstraint {
             entity2: body2.entity,
             collider_entity1,
             collider_entity2,
-            friction,
-            restitution,
+            friction: manifold.friction,
+            restitution: manifold.restitution,
+            anisotropic_friction1: manifold.anisotropic_friction1,
+            anisotropic_friction2: manifold.anisotropic_friction2,
+            tangent_velocity: manifold.tangent_velocity,
             normal,
             points: Vec::with_capacity(manifold.contacts.len()),
+            rolling_part: None,
+            #[cfg(feature = "3d")]
+            spinning_part: None,
             manifold_index: manifold_id,
         };
 
@@ -180,7 +212,7 @@ impl ContactConstraint {
                     softness,
                 ),
                 // There should only be a friction part if the coefficient of friction is non-negative.
-                tangent_part: (friction.dynamic_coefficient > 0.0).then_some(
+                tangent_part: (constraint.friction.dynamic_coefficient > 0.0).then_some(
                     ContactTangentPart::generate(
                         inverse_mass_sum,
                         i1,
@@ -203,6 +235,18 @@ impl ContactConstraint {
             constraint.points.push(point);
         }
 
+        // Rolling and spinning friction only affect angular velocity,
+        // so they are solved once for the whole manifold instead of per point.
+        if !constraint.points.is_empty() {
+            constraint.rolling_part = (constraint.friction.rolling_coefficient > 0.0)
+                .then(|| ContactRollingPart::generate(i1, i2));
+            #[cfg(feature = "3d")]
+            {
+                constraint.spinning_part = (constraint.friction.spinning_coefficient > 0.0)
+                    .then(|| ContactSpinningPart::generate(i1, i2, normal));
+            }
+        }
+
         constraint
     }
 
@@ -248,6 +292,24 @@ impl ContactConstraint {
                 body2.angular_velocity.0 += inv_inertia2 * cross(r2, p);
             }
         }
+
+        let rolling_impulse = self
+            .rolling_part
+            .as_ref()
+            .map_or(default(), |part| part.impulse);
+        #[cfg(feature = "2d")]
+        let angular_impulse = warm_start_coefficient * rolling_impulse;
+        #[cfg(feature = "3d")]
+        let angular_impulse = warm_start_coefficient
+            * (rolling_impulse
+                + self.spinning_part.as_ref().map_or(0.0, |part| part.impulse) * normal);
+
+        if body1.rb.is_dynamic() && body1.dominance() <= body2.dominance() {
+            body1.angular_velocity.0 -= inv_inertia1 * angular_impulse;
+        }
+        if body2.rb.is_dynamic() && body2.dominance() <= body1.dominance() {
+            body2.angular_velocity.0 += inv_inertia2 * angular_impulse;
+        }
     }
 
     /// Solves the [`ContactConstraint`], applying an impulse to the given bodies.
@@ -315,6 +377,7 @@ impl ContactConstraint {
         let friction = self.friction;
         let tangent_directions =
             self.tangent_directions(body1.linear_velocity.0, body2.linear_velocity.0);
+        let friction_scales = self.friction_scales(tangent_directions);
 
         // Friction
         for point in self.points.iter_mut() {
@@ -326,14 +389,16 @@ impl ContactConstraint {
             let r1 = point.anchor1;
             let r2 = point.anchor2;
 
-            // Relative velocity at contact point
-            let relative_velocity = body2.velocity_at_point(r2) - body1.velocity_at_point(r1);
+            // Relative velocity at contact point, offset by the target tangent velocity
+            let relative_velocity =
+                body2.velocity_at_point(r2) - body1.velocity_at_point(r1) - self.tangent_velocity;
 
             // Compute the incremental impulse. The clamping and impulse accumulation is handled by the method.
-            let impulse = friction_part.solve_impulse(
+            let impulse = friction_part.solve_impulse_anisotropic(
                 tangent_directions,
                 relative_velocity,
                 friction,
+                friction_scales,
                 point.normal_part.impulse,
             );
 
@@ -347,6 +412,56 @@ impl ContactConstraint {
                 body2.angular_velocity.0 += inv_inertia2 * cross(r2, impulse);
             }
         }
+
+        // The rolling and spinning friction impulses are limited by the total normal impulse of the manifold.
+        let total_normal_impulse: Scalar = self
+            .points
+            .iter()
+            .map(|point| point.normal_part.impulse)
+            .sum();
+
+        // Rolling friction
+        if let Some(ref mut rolling_part) = self.rolling_part {
+            let relative_angular_velocity = body2.angular_velocity.0 - body1.angular_velocity.0;
+
+            // Compute the incremental angular impulse. The clamping and impulse accumulation is handled by the method.
+            let impulse = rolling_part.solve_impulse(
+                self.normal,
+                relative_angular_velocity,
+                friction.rolling_coefficient,
+                total_normal_impulse,
+            );
+
+            // Apply the angular impulse.
+            if body1.rb.is_dynamic() && body1.dominance() <= body2.dominance() {
+                body1.angular_velocity.0 -= inv_inertia1 * impulse;
+            }
+            if body2.rb.is_dynamic() && body2.dominance() <= body1.dominance() {
+                body2.angular_velocity.0 += inv_inertia2 * impulse;
+            }
+        }
+
+        // Spinning friction
+        #[cfg(feature = "3d")]
+        if let Some(ref mut spinning_part) = self.spinning_part {
+            let relative_angular_velocity = body2.angular_velocity.0 - body1.angular_velocity.0;
+
+            // Compute the incremental angular impulse. The clamping and impulse accumulation is handled by the method.
+            let impulse = spinning_part.solve_impulse(
+                self.normal,
+                relative_angular_velocity,
+                friction.spinning_coefficient,
+                total_normal_impulse,
+            );
+
+            // Apply the angular impulse.
+            if body1.rb.is_dynamic() && body1.dominance() <= body2.dominance() {
+                body1.angular_velocity.0 -= inv_inertia1 * impulse;
+            }
+            if body2.rb.is_dynamic() && body2.dominance() <= body1.dominance() {
+                body2.angular_velocity.0 += inv_inertia2 * impulse;
+            }
+        }
     }
 
     /// Applies [restitution](`Restitution`) for the given bodies if the relative speed
@@ -402,6 +517,10 @@ impl ContactConstraint {
     }
 
     /// Computes `DIM - 1` tangent directions.
+    ///
+    /// In 3D, the first tangent is aligned with the direction of the [`AnisotropicFriction`]
+    /// of the first collider, or the second collider if only it has anisotropic friction.
+    /// Otherwise, it is aligned with the relative tangential velocity of the bodies.
     #[allow(unused_variables)]
     pub fn tangent_directions(&self, velocity1: Vector, velocity2: Vector) -> [Vector; DIM - 1] {
         #[cfg(feature = "2d")]
@@ -411,17 +530,41 @@ impl ContactConstraint {
         #[cfg(feature = "3d")]
         {
             let force_direction = -self.normal;
-            let relative_velocity = velocity1 - velocity2;
-            let tangent_velocity =
-                relative_velocity + force_direction * force_direction.dot(relative_velocity);
 
-            let tangent = tangent_velocity
-                .try_normalize()
-                .unwrap_or(force_direction.any_orthonormal_vector());
+            let anisotropic_friction = self.anisotropic_friction1.or(self.anisotropic_friction2);
+            let anisotropic_tangent = anisotropic_friction.and_then(|friction| {
+                let direction = friction.direction;
+                (direction - force_direction * force_direction.dot(direction)).try_normalize()
+            });
+
+            let tangent = anisotropic_tangent.unwrap_or_else(|| {
+                let relative_velocity = velocity1 - velocity2;
+                let tangent_velocity =
+                    relative_velocity - force_direction * force_direction.dot(relative_velocity);
+
+                tangent_velocity
+                    .try_normalize()
+                    .unwrap_or(force_direction.any_orthonormal_vector())
+            });
             let bitangent = force_direction.cross(tangent);
             [tangent, bitangent]
         }
     }
+
+    /// Computes the scales of the friction coefficient along the given tangent directions
+    /// based on the [`AnisotropicFriction`] of the colliders.
+    ///
+    /// If both colliders have anisotropic friction, the smaller scale along each direction is used.
+    /// Without anisotropic friction, all of the scales are `1.0`.
+    pub fn friction_scales(&self, tangent_directions: [Vector; DIM - 1]) -> [Scalar; DIM - 1] {
+        tangent_directions.map(|tangent| {
+            [self.anisotropic_friction1, self.anisotropic_friction2]
+                .into_iter()
+                .flatten()
+                .map(|friction| friction.scale_along(tangent))
+                .fold(1.0, Scalar::min)
+        })
+    }
 }
 
 impl MapEntities for ContactConstraint {
@@ -430,3 +573,37 @@ impl MapEntities for ContactConstraint {
         self.entity2 = entity_mapper.map_entity(self.entity2);
     }
 }
+
+#[cfg(all(test, feature = "3d"))]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn tangent_directions_are_orthogonal_to_normal() {
+        let constraint = ContactConstraint {
+            entity1: Entity::PLACEHOLDER,
+            entity2: Entity::PLACEHOLDER,
+            collider_entity1: Entity::PLACEHOLDER,
+            collider_entity2: Entity::PLACEHOLDER,
+            friction: Friction::default(),
+            restitution: Restitution::default(),
+            anisotropic_friction1: None,
+            anisotropic_friction2: None,
+            tangent_velocity: Vector::ZERO,
+            normal: Vector::Y,
+            points: vec![],
+            rolling_part: None,
+            spinning_part: None,
+            manifold_index: 0,
+        };
+
+        // The bodies are sliding past each other while also approaching each other along the normal.
+        let [tangent, bitangent] =
+            constraint.tangent_directions(Vector::new(1.0, -2.0, 0.0), Vector::ZERO);
+
+        assert_relative_eq!(tangent.dot(Vector::Y), 0.0);
+        assert_relative_eq!(bitangent.dot(Vector::Y), 0.0);
+        assert_relative_eq!(tangent, Vector::X);
+    }
+}
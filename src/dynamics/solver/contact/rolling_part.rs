@@ -0,0 +1,110 @@
+use crate::prelude::*;
+use bevy::reflect::Reflect;
+
+/// The accumulated angular impulse of a [`ContactRollingPart`]. A scalar in 2D, and a vector in 3D.
+#[cfg(feature = "2d")]
+pub type RollingImpulse = Scalar;
+/// The accumulated angular impulse of a [`ContactRollingPart`]. A scalar in 2D, and a vector in 3D.
+#[cfg(feature = "3d")]
+pub type RollingImpulse = Vector;
+
+#[cfg(feature = "2d")]
+type AngularValue = Scalar;
+#[cfg(feature = "3d")]
+type AngularValue = Vector;
+
+/// The rolling friction part of a [`ContactConstraint`](super::ContactConstraint).
+///
+/// Rolling friction resists the relative angular velocity of the bodies around the axes
+/// perpendicular to the contact normal, stopping bodies like balls and wheels from rolling forever.
+/// It is solved once for the whole contact manifold, because it only affects angular velocity.
+#[derive(Clone, Debug, Default, PartialEq, Reflect)]
+pub struct ContactRollingPart {
+    /// The accumulated angular impulse caused by rolling friction.
+    pub impulse: RollingImpulse,
+
+    /// The angular inertial properties of the bodies, or in other words,
+    /// the angular mass "seen" by the constraint.
+    #[cfg(feature = "2d")]
+    pub effective_mass: Scalar,
+    /// The angular inertial properties of the bodies, or in other words,
+    /// the angular mass "seen" by the constraint.
+    #[cfg(feature = "3d")]
+    pub effective_mass: Matrix3,
+}
+
+impl ContactRollingPart {
+    /// Generates a new [`ContactRollingPart`].
+    pub fn generate(
+        inverse_inertia1: impl Into<InverseInertia>,
+        inverse_inertia2: impl Into<InverseInertia>,
+    ) -> Self {
+        let i1 = inverse_inertia1.into().0;
+        let i2 = inverse_inertia2.into().0;
+
+        // The constraint only involves the angular velocities of the bodies,
+        // so the Jacobian is [0, -I, 0, I], and the effective inverse mass is the sum
+        // of the inverse inertia tensors.
+        #[cfg(feature = "2d")]
+        let effective_mass = (i1 + i2).recip_or_zero();
+        #[cfg(feature = "3d")]
+        let effective_mass = {
+            let k = i1 + i2;
+            if k.determinant().abs() > Scalar::EPSILON {
+                k.inverse()
+            } else {
+                Matrix3::ZERO
+            }
+        };
+
+        Self {
+            impulse: RollingImpulse::default(),
+            effective_mass,
+        }
+    }
+
+    /// Solves the rolling friction constraint, updating the total impulse in `self` and returning
+    /// the incremental angular impulse to apply to each body.
+    ///
+    /// The magnitude of the total impulse is limited by the rolling friction coefficient
+    /// multiplied by the total normal impulse of the contact manifold.
+    #[allow(unused_variables)]
+    pub fn solve_impulse(
+        &mut self,
+        normal: Vector,
+        relative_angular_velocity: AngularValue,
+        coefficient: Scalar,
+        total_normal_impulse: Scalar,
+    ) -> RollingImpulse {
+        let impulse_limit = coefficient * total_normal_impulse;
+
+        #[cfg(feature = "2d")]
+        {
+            let impulse = -self.effective_mass * relative_angular_velocity;
+            let new_impulse = (self.impulse + impulse).clamp(-impulse_limit, impulse_limit);
+            let impulse = new_impulse - self.impulse;
+            self.impulse = new_impulse;
+            impulse
+        }
+
+        #[cfg(feature = "3d")]
+        {
+            // Only resist rotation around the axes perpendicular to the normal.
+            // Rotation around the normal is handled by spinning friction.
+            let rolling_velocity =
+                relative_angular_velocity - normal * normal.dot(relative_angular_velocity);
+            let impulse = -(self.effective_mass * rolling_velocity);
+            let impulse = impulse - normal * normal.dot(impulse);
+
+            let new_impulse = (self.impulse + impulse).clamp_length_max(impulse_limit);
+            let impulse = new_impulse - self.impulse;
+
+            if !impulse.is_finite() {
+                return Vector::ZERO;
+            }
+
+            self.impulse = new_impulse;
+            impulse
+        }
+    }
+}
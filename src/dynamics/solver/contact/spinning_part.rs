@@ -0,0 +1,62 @@
+use crate::prelude::*;
+use bevy::reflect::Reflect;
+
+/// The spinning friction part of a [`ContactConstraint`](super::ContactConstraint).
+///
+/// Spinning friction, also known as torsional friction, resists the relative angular velocity
+/// of the bodies around the contact normal, stopping bodies like spinning tops and coins from spinning forever.
+/// It is solved once for the whole contact manifold, because it only affects angular velocity.
+#[derive(Clone, Debug, Default, PartialEq, Reflect)]
+pub struct ContactSpinningPart {
+    /// The accumulated angular impulse magnitude around the contact normal caused by spinning friction.
+    pub impulse: Scalar,
+
+    /// The angular inertial properties of the bodies projected onto the contact normal,
+    /// or in other words, the angular mass "seen" by the constraint around the normal.
+    pub effective_mass: Scalar,
+}
+
+impl ContactSpinningPart {
+    /// Generates a new [`ContactSpinningPart`].
+    pub fn generate(
+        inverse_inertia1: impl Into<InverseInertia>,
+        inverse_inertia2: impl Into<InverseInertia>,
+        normal: Vector,
+    ) -> Self {
+        let i1 = inverse_inertia1.into().0;
+        let i2 = inverse_inertia2.into().0;
+
+        // The Jacobian is [0, -normal, 0, normal], so the effective inverse mass
+        // is the sum of the inverse inertia tensors projected onto the normal.
+        let k = normal.dot(i1 * normal) + normal.dot(i2 * normal);
+
+        Self {
+            impulse: 0.0,
+            effective_mass: k.recip_or_zero(),
+        }
+    }
+
+    /// Solves the spinning friction constraint, updating the total impulse in `self` and returning
+    /// the incremental angular impulse to apply to each body.
+    ///
+    /// The magnitude of the total impulse is limited by the spinning friction coefficient
+    /// multiplied by the total normal impulse of the contact manifold.
+    pub fn solve_impulse(
+        &mut self,
+        normal: Vector,
+        relative_angular_velocity: Vector,
+        coefficient: Scalar,
+        total_normal_impulse: Scalar,
+    ) -> Vector {
+        let impulse_limit = coefficient * total_normal_impulse;
+
+        let spin_speed = normal.dot(relative_angular_velocity);
+        let impulse = -self.effective_mass * spin_speed;
+
+        let new_impulse = (self.impulse + impulse).clamp(-impulse_limit, impulse_limit);
+        let impulse = new_impulse - self.impulse;
+        self.impulse = new_impulse;
+
+        impulse * normal
+    }
+}
@@ -151,6 +151,27 @@ impl ContactTangentPart {
         relative_velocity: Vector,
         friction: Friction,
         normal_impulse: Scalar,
+    ) -> Vector {
+        self.solve_impulse_anisotropic(
+            tangent_directions,
+            relative_velocity,
+            friction,
+            [1.0; DIM - 1],
+            normal_impulse,
+        )
+    }
+
+    /// Solves the friction constraint like [`solve_impulse`](Self::solve_impulse),
+    /// but with the friction coefficient scaled by the given `friction_scales` along each tangent direction.
+    ///
+    /// This is used for [`AnisotropicFriction`].
+    pub fn solve_impulse_anisotropic(
+        &mut self,
+        tangent_directions: [Vector; DIM - 1],
+        relative_velocity: Vector,
+        friction: Friction,
+        friction_scales: [Scalar; DIM - 1],
+        normal_impulse: Scalar,
     ) -> Vector {
         // Compute the maximum bound for the friction impulse.
         //
@@ -187,6 +208,7 @@ impl ContactTangentPart {
             let mut impulse = self.effective_mass * (-tangent_speed);
 
             // Clamp the accumulated impulse.
+            let impulse_limit = impulse_limit * friction_scales[0];
             let new_impulse = (self.impulse + impulse).clamp(-impulse_limit, impulse_limit);
             impulse = new_impulse - self.impulse;
             self.impulse = new_impulse;
@@ -218,8 +240,16 @@ impl ContactTangentPart {
             // Compute the incremental tangent impoulse.
             let delta_impulse = effective_mass * Vector2::new(tangent_speed1, tangent_speed2);
 
-            // Clamp the accumulated impulse.
-            let new_impulse = (self.impulse - delta_impulse).clamp_length_max(impulse_limit);
+            // Clamp the accumulated impulse. With anisotropic friction, the friction cone
+            // is elliptical, so the impulse is scaled back onto the ellipse instead of a circle.
+            let limits = impulse_limit * Vector2::from(friction_scales);
+            let new_impulse = self.impulse - delta_impulse;
+            let ratio = (new_impulse / limits.max(Vector2::splat(Scalar::EPSILON))).length();
+            let new_impulse = if ratio > 1.0 {
+                new_impulse / ratio
+            } else {
+                new_impulse
+            };
             let impulse = new_impulse - self.impulse;
 
             if !impulse.is_finite() {
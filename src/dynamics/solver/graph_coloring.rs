@@ -0,0 +1,174 @@
+//! Graph coloring for solving [contact constraints](super::ContactConstraints) in parallel.
+//!
+//! The solver iterates over constraints and applies impulses to the bodies they affect,
+//! so two constraints that share a body can't be solved at the same time. To find constraints
+//! that *can* be solved at the same time, the constraints are treated as the edges of a graph
+//! whose nodes are bodies, and each constraint is assigned a *color* such that no two constraints
+//! of the same color share a body.
+//!
+//! The colors are then solved one after another, but the constraints within a color
+//! are independent of each other and can be solved in parallel. Because of this, the result
+//! doesn't depend on the number of threads or on how the work is scheduled.
+//!
+//! Static and kinematic bodies are not affected by the constraints, so they are ignored by the coloring
+//! and can be shared by any number of constraints of the same color. This way, bodies resting on the same
+//! ground can still be solved in parallel. The solver only reads these *shared* bodies.
+//!
+//! The number of colors is limited by [`MAX_COLORS`]. Constraints that can't be assigned a color,
+//! for example because a body is touching more bodies than there are colors, end up in an *overflow*
+//! that is solved serially after the colors.
+//!
+//! The coloring is done by the [`SolverPlugin`](super::SolverPlugin) when the `parallel` feature is enabled.
+//! It is disabled by the `enhanced-determinism` feature, because reordering the constraints
+//! changes the result compared to the serial solver, which would break cross-platform determinism
+//! between builds with and without the `parallel` feature.
+
+use bevy::{ecs::entity::EntityHashSet, prelude::*};
+#[cfg(all(feature = "parallel", not(feature = "enhanced-determinism")))]
+use {
+    super::{contact::ContactConstraint, ContactConstraints},
+    crate::prelude::RigidBody,
+};
+
+/// The maximum number of colors used for [graph coloring](self).
+///
+/// Constraints that can't be assigned one of the colors are solved serially.
+pub const MAX_COLORS: usize = 24;
+
+/// A resource that stores how the [`ContactConstraints`](super::ContactConstraints) are split into [colors](self).
+///
+/// The constraints are sorted by color, so that the constraints of each color are stored
+/// contiguously in the order of the colors, followed by the overflow constraints.
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+pub struct ContactConstraintColors {
+    /// The number of constraints in each color.
+    color_counts: Vec<usize>,
+    /// The bodies of each colored constraint, used to detect if the constraints
+    /// have been modified after they were colored.
+    body_pairs: Vec<[Entity; 2]>,
+    /// The static and kinematic bodies that can be shared by constraints of the same color.
+    shared_bodies: EntityHashSet,
+}
+
+impl ContactConstraintColors {
+    /// Returns the number of constraints in each color.
+    pub fn color_counts(&self) -> &[usize] {
+        &self.color_counts
+    }
+
+    /// Returns the total number of constraints that were assigned a color.
+    pub fn colored_count(&self) -> usize {
+        self.body_pairs.len()
+    }
+
+    /// Returns `true` if the given body is static or kinematic, and can be shared by constraints of the same color.
+    ///
+    /// Shared bodies are only read by the solver when solving the colors.
+    pub fn is_shared(&self, body: Entity) -> bool {
+        self.shared_bodies.contains(&body)
+    }
+
+    /// Clears the colors. All constraints will be solved serially.
+    pub fn clear(&mut self) {
+        self.color_counts.clear();
+        self.body_pairs.clear();
+        self.shared_bodies.clear();
+    }
+
+    /// Splits the given constraints into the slices of each color and the remaining overflow constraints.
+    ///
+    /// Returns `None` if the constraints don't match the coloring, for example because they were
+    /// modified after the coloring was computed.
+    #[cfg(all(feature = "parallel", not(feature = "enhanced-determinism")))]
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn split<'a>(
+        &self,
+        constraints: &'a mut [ContactConstraint],
+    ) -> Option<(
+        Vec<&'a mut [ContactConstraint]>,
+        &'a mut [ContactConstraint],
+    )> {
+        if self.body_pairs.len() > constraints.len()
+            || self
+                .body_pairs
+                .iter()
+                .zip(constraints.iter())
+                .any(|(pair, constraint)| *pair != [constraint.entity1, constraint.entity2])
+        {
+            return None;
+        }
+
+        let mut colors = Vec::with_capacity(self.color_counts.len());
+        let mut rest = constraints;
+        for &count in self.color_counts.iter() {
+            let (color, remaining) = rest.split_at_mut(count);
+            colors.push(color);
+            rest = remaining;
+        }
+
+        Some((colors, rest))
+    }
+}
+
+/// Assigns a [color](self) to each contact constraint, and sorts the [`ContactConstraints`] by color.
+///
+/// Colors are assigned greedily: each constraint gets the first color that doesn't
+/// contain either of its dynamic bodies yet. Static and kinematic bodies are not modified by the solver,
+/// so they are recorded as [shared](ContactConstraintColors::is_shared) instead of being added to the colors.
+#[cfg(all(feature = "parallel", not(feature = "enhanced-determinism")))]
+pub(crate) fn color_contact_constraints(
+    mut constraints: ResMut<ContactConstraints>,
+    mut colors: ResMut<ContactConstraintColors>,
+    bodies: Query<&RigidBody>,
+) {
+    colors.clear();
+
+    // Bodies that are missing from the query are treated like dynamic bodies.
+    let is_shared = |entity: Entity| bodies.get(entity).is_ok_and(|rb| !rb.is_dynamic());
+
+    let mut color_bodies: Vec<EntityHashSet> = vec![EntityHashSet::default(); MAX_COLORS];
+    let mut buckets: Vec<Vec<ContactConstraint>> = (0..=MAX_COLORS).map(|_| Vec::new()).collect();
+
+    for constraint in constraints.drain(..) {
+        let (entity1, entity2) = (constraint.entity1, constraint.entity2);
+
+        let (shared1, shared2) = (is_shared(entity1), is_shared(entity2));
+
+        let color = if entity1 == entity2 {
+            None
+        } else {
+            color_bodies.iter().position(|bodies| {
+                (shared1 || !bodies.contains(&entity1)) && (shared2 || !bodies.contains(&entity2))
+            })
+        };
+
+        if let Some(color) = color {
+            for (entity, shared) in [(entity1, shared1), (entity2, shared2)] {
+                if shared {
+                    colors.shared_bodies.insert(entity);
+                } else {
+                    color_bodies[color].insert(entity);
+                }
+            }
+            buckets[color].push(constraint);
+        } else {
+            buckets[MAX_COLORS].push(constraint);
+        }
+    }
+
+    let overflow = buckets.pop().unwrap_or_default();
+
+    // Only keep the colors that are in use. The colors are filled in order,
+    // so all colors after the first empty one are empty too.
+    for bucket in buckets.into_iter().take_while(|bucket| !bucket.is_empty()) {
+        colors.color_counts.push(bucket.len());
+        colors.body_pairs.extend(
+            bucket
+                .iter()
+                .map(|constraint| [constraint.entity1, constraint.entity2]),
+        );
+        constraints.extend(bucket);
+    }
+
+    constraints.extend(overflow);
+}
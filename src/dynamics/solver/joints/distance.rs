@@ -1,6 +1,9 @@
 //! [`DistanceJoint`] component.
 
-use crate::{dynamics::solver::xpbd::*, prelude::*};
+use crate::{
+    dynamics::solver::{joints::impulse::*, softness_parameters::SoftnessCoefficients, xpbd::*},
+    prelude::*,
+};
 use bevy::{
     ecs::{
         entity::{EntityMapper, MapEntities},
@@ -29,6 +32,8 @@ pub struct DistanceJoint {
     pub rest_length: Scalar,
     /// The extents of the allowed relative translation between the attached bodies.
     pub length_limits: Option<DistanceLimit>,
+    /// If `true`, the bodies connected by the joint don't collide with each other. `true` by default.
+    pub disable_collision: bool,
     /// Linear damping applied by the joint.
     pub damping_linear: Scalar,
     /// Angular damping applied by the joint.
@@ -37,8 +42,15 @@ pub struct DistanceJoint {
     pub lagrange: Scalar,
     /// The joint's compliance, the inverse of stiffness, has the unit meters / Newton.
     pub compliance: Scalar,
+    /// The frequency and damping ratio based softness of the joint.
+    /// If set, it takes priority over the `compliance`.
+    ///
+    /// The distance joint only has a linear part, so the angular softness has no effect.
+    pub softness: JointSoftness,
     /// The force exerted by the joint.
     pub force: Vector,
+    /// The accumulated impulse along the joint axis used by the [impulse-based joint solver](impulse).
+    pub impulse: Scalar,
 }
 
 impl XpbdConstraint<2> for DistanceJoint {
@@ -55,6 +67,62 @@ impl XpbdConstraint<2> for DistanceJoint {
     }
 }
 
+impl ImpulseJoint for DistanceJoint {
+    fn warm_start(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        coefficient: Scalar,
+    ) {
+        let anchors = JointAnchors::new(body1, body2, self.local_anchor1, self.local_anchor2);
+        let axis = anchors.separation.normalize_or_zero();
+        apply_linear_impulse(
+            body1,
+            body2,
+            coefficient * self.impulse * axis,
+            anchors.r1,
+            anchors.r2,
+        );
+    }
+
+    fn solve_impulses(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        softness: SoftnessCoefficients,
+        delta_secs: Scalar,
+        use_bias: bool,
+    ) {
+        let anchors = JointAnchors::new(body1, body2, self.local_anchor1, self.local_anchor2);
+        let length = anchors.separation.length();
+
+        // The direction is undefined when the attachment points coincide.
+        if length <= Scalar::EPSILON {
+            return;
+        }
+
+        let limits = self
+            .length_limits
+            .unwrap_or(DistanceLimit::new(self.rest_length, self.rest_length));
+
+        solve_linear_constraint(
+            body1,
+            body2,
+            anchors.separation / length,
+            anchors.r1,
+            anchors.r2,
+            length,
+            limits,
+            &mut self.impulse,
+            ConstraintSoftness::new(self.softness.linear, self.compliance, softness, delta_secs),
+            delta_secs,
+            use_bias,
+        );
+
+        self.force = impulse_to_force(self.impulse * anchors.separation / length, delta_secs);
+    }
+}
+
 impl Joint for DistanceJoint {
     fn new(entity1: Entity, entity2: Entity) -> Self {
         Self {
@@ -64,11 +132,14 @@ impl Joint for DistanceJoint {
             local_anchor2: Vector::ZERO,
             rest_length: 0.0,
             length_limits: None,
+            disable_collision: true,
             damping_linear: 0.0,
             damping_angular: 0.0,
             lagrange: 0.0,
             compliance: 0.0,
+            softness: JointSoftness::RIGID,
             force: Vector::ZERO,
+            impulse: 0.0,
         }
     }
 
@@ -76,6 +147,10 @@ impl Joint for DistanceJoint {
         Self { compliance, ..self }
     }
 
+    fn with_softness(self, softness: JointSoftness) -> Self {
+        Self { softness, ..self }
+    }
+
     fn with_local_anchor_1(self, anchor: Vector) -> Self {
         Self {
             local_anchor1: anchor,
@@ -104,6 +179,17 @@ impl Joint for DistanceJoint {
         }
     }
 
+    fn with_collision_disabled(self, disabled: bool) -> Self {
+        Self {
+            disable_collision: disabled,
+            ..self
+        }
+    }
+
+    fn softness(&self) -> JointSoftness {
+        self.softness
+    }
+
     fn local_anchor_1(&self) -> Vector {
         self.local_anchor1
     }
@@ -119,6 +205,14 @@ impl Joint for DistanceJoint {
     fn damping_angular(&self) -> Scalar {
         self.damping_angular
     }
+
+    fn collision_disabled(&self) -> bool {
+        self.disable_collision
+    }
+
+    fn force(&self) -> Vector {
+        self.force
+    }
 }
 
 impl DistanceJoint {
@@ -154,8 +248,20 @@ impl DistanceJoint {
         let w = [w1, w2];
 
         // Compute Lagrange multiplier update, essentially the signed magnitude of the correction
-        let delta_lagrange =
-            self.compute_lagrange_update(self.lagrange, distance, &w, self.compliance, dt);
+        let delta_lagrange = if let Some(softness) = self.softness.linear {
+            let distance_dot =
+                dir.dot(body1.velocity_at_point(world_r1) - body2.velocity_at_point(world_r2));
+            self.compute_soft_lagrange_update(
+                self.lagrange,
+                distance,
+                distance_dot,
+                &w,
+                softness,
+                dt,
+            )
+        } else {
+            self.compute_lagrange_update(self.lagrange, distance, &w, self.compliance, dt)
+        };
         self.lagrange += delta_lagrange;
 
         // Apply positional correction (method from PositionConstraint)
@@ -1,6 +1,9 @@
 //! [`FixedJoint`] component.
 
-use crate::{dynamics::solver::xpbd::*, prelude::*};
+use crate::{
+    dynamics::solver::{joints::impulse::*, softness_parameters::SoftnessCoefficients, xpbd::*},
+    prelude::*,
+};
 use bevy::{
     ecs::{
         entity::{EntityMapper, MapEntities},
@@ -26,6 +29,8 @@ pub struct FixedJoint {
     pub local_anchor1: Vector,
     /// Attachment point on the second body.
     pub local_anchor2: Vector,
+    /// If `true`, the bodies connected by the joint don't collide with each other. `true` by default.
+    pub disable_collision: bool,
     /// Linear damping applied by the joint.
     pub damping_linear: Scalar,
     /// Angular damping applied by the joint.
@@ -36,10 +41,17 @@ pub struct FixedJoint {
     pub align_lagrange: Scalar,
     /// The joint's compliance, the inverse of stiffness, has the unit meters / Newton.
     pub compliance: Scalar,
+    /// The frequency and damping ratio based softness of the joint's linear and angular parts.
+    /// If set, it takes priority over the `compliance`.
+    pub softness: JointSoftness,
     /// The force exerted by the joint.
     pub force: Vector,
     /// The torque exerted by the joint when aligning the bodies.
     pub align_torque: Torque,
+    /// The accumulated linear impulse used by the [impulse-based joint solver](impulse).
+    pub linear_impulse: Vector,
+    /// The accumulated angular impulse used by the [impulse-based joint solver](impulse).
+    pub angular_impulse: AngularImpulse,
 }
 
 impl XpbdConstraint<2> for FixedJoint {
@@ -59,25 +71,93 @@ impl XpbdConstraint<2> for FixedJoint {
         // Align orientation
         let difference = self.get_rotation_difference(&body1.rotation, &body2.rotation);
         let mut lagrange = self.align_lagrange;
-        self.align_torque =
-            self.align_orientation(body1, body2, difference, &mut lagrange, compliance, dt);
+        self.align_torque = self.align_orientation_with_softness(
+            body1,
+            body2,
+            difference,
+            &mut lagrange,
+            compliance,
+            self.softness.angular,
+            dt,
+        );
         self.align_lagrange = lagrange;
 
         // Align position of local attachment points
         let mut lagrange = self.position_lagrange;
-        self.force = self.align_position(
+        self.force = self.align_position_with_softness(
             body1,
             body2,
             self.local_anchor1,
             self.local_anchor2,
             &mut lagrange,
             compliance,
+            self.softness.linear,
             dt,
         );
         self.position_lagrange = lagrange;
     }
 }
 
+impl ImpulseJoint for FixedJoint {
+    fn warm_start(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        coefficient: Scalar,
+    ) {
+        let anchors = JointAnchors::new(body1, body2, self.local_anchor1, self.local_anchor2);
+        apply_angular_impulse(body1, body2, coefficient * self.angular_impulse);
+        apply_linear_impulse(
+            body1,
+            body2,
+            coefficient * self.linear_impulse,
+            anchors.r1,
+            anchors.r2,
+        );
+    }
+
+    fn solve_impulses(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        softness: SoftnessCoefficients,
+        delta_secs: Scalar,
+        use_bias: bool,
+    ) {
+        let linear_softness =
+            ConstraintSoftness::new(self.softness.linear, self.compliance, softness, delta_secs);
+        let angular_softness =
+            ConstraintSoftness::new(self.softness.angular, self.compliance, softness, delta_secs);
+
+        // Align orientation
+        let error = rotation_error(&body1.rotation, &body2.rotation);
+        solve_angular_lock(
+            body1,
+            body2,
+            error,
+            &mut self.angular_impulse,
+            angular_softness,
+            delta_secs,
+            use_bias,
+        );
+
+        // Align position of local attachment points
+        let anchors = JointAnchors::new(body1, body2, self.local_anchor1, self.local_anchor2);
+        solve_point_constraint(
+            body1,
+            body2,
+            &anchors,
+            &mut self.linear_impulse,
+            linear_softness,
+            delta_secs,
+            use_bias,
+        );
+
+        self.force = impulse_to_force(self.linear_impulse, delta_secs);
+        self.align_torque = self.angular_impulse / delta_secs;
+    }
+}
+
 impl Joint for FixedJoint {
     fn new(entity1: Entity, entity2: Entity) -> Self {
         Self {
@@ -85,16 +165,23 @@ impl Joint for FixedJoint {
             entity2,
             local_anchor1: Vector::ZERO,
             local_anchor2: Vector::ZERO,
+            disable_collision: true,
             damping_linear: 1.0,
             damping_angular: 1.0,
             position_lagrange: 0.0,
             align_lagrange: 0.0,
             compliance: 0.0,
+            softness: JointSoftness::RIGID,
             force: Vector::ZERO,
             #[cfg(feature = "2d")]
             align_torque: 0.0,
             #[cfg(feature = "3d")]
             align_torque: Vector::ZERO,
+            linear_impulse: Vector::ZERO,
+            #[cfg(feature = "2d")]
+            angular_impulse: 0.0,
+            #[cfg(feature = "3d")]
+            angular_impulse: Vector::ZERO,
         }
     }
 
@@ -102,6 +189,10 @@ impl Joint for FixedJoint {
         Self { compliance, ..self }
     }
 
+    fn with_softness(self, softness: JointSoftness) -> Self {
+        Self { softness, ..self }
+    }
+
     fn with_local_anchor_1(self, anchor: Vector) -> Self {
         Self {
             local_anchor1: anchor,
@@ -130,6 +221,17 @@ impl Joint for FixedJoint {
         }
     }
 
+    fn with_collision_disabled(self, disabled: bool) -> Self {
+        Self {
+            disable_collision: disabled,
+            ..self
+        }
+    }
+
+    fn softness(&self) -> JointSoftness {
+        self.softness
+    }
+
     fn local_anchor_1(&self) -> Vector {
         self.local_anchor1
     }
@@ -145,6 +247,18 @@ impl Joint for FixedJoint {
     fn damping_angular(&self) -> Scalar {
         self.damping_angular
     }
+
+    fn collision_disabled(&self) -> bool {
+        self.disable_collision
+    }
+
+    fn force(&self) -> Vector {
+        self.force
+    }
+
+    fn torque(&self) -> Torque {
+        self.align_torque
+    }
 }
 
 impl FixedJoint {
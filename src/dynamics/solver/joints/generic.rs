@@ -0,0 +1,661 @@
+//! [`GenericJoint`] component.
+
+use crate::{
+    dynamics::solver::{joints::impulse::*, softness_parameters::SoftnessCoefficients, xpbd::*},
+    prelude::*,
+};
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+};
+
+/// The number of rotational degrees of freedom.
+#[cfg(feature = "2d")]
+const ANGULAR_DIM: usize = 1;
+/// The number of rotational degrees of freedom.
+#[cfg(feature = "3d")]
+const ANGULAR_DIM: usize = 3;
+
+/// An axis of a [`GenericJoint`] in the local space of the first body.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq, Hash)]
+pub enum JointAxis {
+    /// The local X axis.
+    X,
+    /// The local Y axis.
+    Y,
+    /// The local Z axis.
+    #[cfg(feature = "3d")]
+    Z,
+}
+
+impl JointAxis {
+    /// Returns the index of the axis.
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// The motion allowed along or around an axis of a [`GenericJoint`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Default, PartialEq)]
+pub enum AxisMotion {
+    /// No relative motion is allowed.
+    #[default]
+    Locked,
+    /// Relative motion is allowed freely.
+    Free,
+    /// Relative motion is allowed between `min` and `max`.
+    Limited {
+        /// The minimum distance or angle.
+        min: Scalar,
+        /// The maximum distance or angle.
+        max: Scalar,
+    },
+}
+
+impl AxisMotion {
+    /// Returns the [`DistanceLimit`] corresponding to the motion along a linear axis,
+    /// or `None` if the axis is free.
+    pub fn distance_limit(self) -> Option<DistanceLimit> {
+        match self {
+            Self::Locked => Some(DistanceLimit::ZERO),
+            Self::Free => None,
+            Self::Limited { min, max } => Some(DistanceLimit::new(min, max)),
+        }
+    }
+
+    /// Returns the [`AngleLimit`] corresponding to the motion around an angular axis,
+    /// or `None` if the axis is free.
+    pub fn angle_limit(self) -> Option<AngleLimit> {
+        match self {
+            Self::Locked => Some(AngleLimit::ZERO),
+            Self::Free => None,
+            Self::Limited { min, max } => Some(AngleLimit::new(min, max)),
+        }
+    }
+}
+
+/// A generic joint where the relative motion of the attached bodies along and around each axis
+/// can be configured independently.
+///
+/// Each linear and angular axis can be [locked](AxisMotion::Locked), [free](AxisMotion::Free),
+/// or [limited](AxisMotion::Limited), and can optionally be driven by a [`JointMotor`].
+/// Position motors can also be used as springs that pull the bodies towards a target position.
+///
+/// The axes are the coordinate axes in the local space of the first body.
+/// By default, all axes are locked, so the joint behaves like a [`FixedJoint`].
+///
+/// Generic joints can be useful for things like ragdolls, vehicle suspensions and mechanical rigs
+/// that don't fit any of the more specialized joints.
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     let chassis = commands.spawn(RigidBody::Dynamic).id();
+///     let wheel = commands.spawn(RigidBody::Dynamic).id();
+///
+///     // A suspension that allows limited movement along the Y axis,
+///     // with a spring that pulls the wheel back to its rest position.
+///     commands.spawn(
+///         GenericJoint::new(chassis, wheel)
+///             .with_linear_motion(JointAxis::Y, AxisMotion::Limited { min: -0.5, max: 0.5 })
+///             .with_linear_motor(JointAxis::Y, JointMotor::position(0.0, 500.0, 50.0)),
+///     );
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, MapEntities, PartialEq)]
+pub struct GenericJoint {
+    /// First entity constrained by the joint.
+    pub entity1: Entity,
+    /// Second entity constrained by the joint.
+    pub entity2: Entity,
+    /// Attachment point on the first body.
+    pub local_anchor1: Vector,
+    /// Attachment point on the second body.
+    pub local_anchor2: Vector,
+    /// The motion allowed along each linear axis, indexed by [`JointAxis`].
+    pub linear_motion: [AxisMotion; DIM],
+    /// The motion allowed around each angular axis.
+    ///
+    /// In 2D, there is only one angular axis. In 3D, the axes are indexed by [`JointAxis`].
+    pub angular_motion: [AxisMotion; ANGULAR_DIM],
+    /// The motors driving the relative translation along each linear axis, indexed by [`JointAxis`].
+    pub linear_motors: [Option<JointMotor>; DIM],
+    /// The motors driving the relative rotation around each angular axis.
+    ///
+    /// In 2D, there is only one angular axis. In 3D, the axes are indexed by [`JointAxis`].
+    pub angular_motors: [Option<JointMotor>; ANGULAR_DIM],
+    /// If `true`, the bodies connected by the joint don't collide with each other. `true` by default.
+    pub disable_collision: bool,
+    /// Linear damping applied by the joint.
+    pub damping_linear: Scalar,
+    /// Angular damping applied by the joint.
+    pub damping_angular: Scalar,
+    /// Lagrange multiplier for the positional correction.
+    pub position_lagrange: Scalar,
+    /// Lagrange multipliers for the angular corrections around each angular axis.
+    pub angular_lagrange: [Scalar; ANGULAR_DIM],
+    /// The joint's compliance, the inverse of stiffness, has the unit meters / Newton.
+    pub compliance: Scalar,
+    /// The frequency and damping ratio based softness of the joint's linear and angular parts.
+    /// If set, it takes priority over the `compliance`.
+    pub softness: JointSoftness,
+    /// The force exerted by the joint.
+    pub force: Vector,
+    /// The torque exerted by the joint.
+    pub torque: Torque,
+    /// The accumulated impulses along each linear axis used by the [impulse-based joint solver](impulse).
+    pub linear_impulses: [Scalar; DIM],
+    /// The accumulated impulses around each angular axis used by the [impulse-based joint solver](impulse).
+    pub angular_impulses: [Scalar; ANGULAR_DIM],
+}
+
+impl XpbdConstraint<2> for GenericJoint {
+    fn entities(&self) -> [Entity; 2] {
+        [self.entity1, self.entity2]
+    }
+
+    fn clear_lagrange_multipliers(&mut self) {
+        self.position_lagrange = 0.0;
+        self.angular_lagrange = [0.0; ANGULAR_DIM];
+    }
+
+    fn solve(&mut self, bodies: [&mut RigidBodyQueryItem; 2], dt: Scalar) {
+        let [body1, body2] = bodies;
+
+        // Constrain the relative rotation of the bodies around each angular axis
+        self.torque = self.constrain_rotations(body1, body2, dt);
+
+        // Constrain the relative translation of the bodies along each linear axis
+        self.force = self.constrain_positions(body1, body2, dt);
+    }
+}
+
+impl ImpulseJoint for GenericJoint {
+    fn warm_start(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        coefficient: Scalar,
+    ) {
+        let anchors = JointAnchors::new(body1, body2, self.local_anchor1, self.local_anchor2);
+
+        let mut linear_impulse = Vector::ZERO;
+        for (i, impulse) in self.linear_impulses.iter().enumerate() {
+            linear_impulse += *impulse * (*body1.rotation * Vector::AXES[i]);
+        }
+
+        #[cfg(feature = "2d")]
+        let angular_impulse = self.angular_impulses[0];
+        #[cfg(feature = "3d")]
+        let angular_impulse = self
+            .angular_impulses
+            .iter()
+            .enumerate()
+            .fold(Vector::ZERO, |acc, (i, impulse)| {
+                acc + *impulse * (*body1.rotation * Vector::AXES[i])
+            });
+
+        apply_angular_impulse(body1, body2, coefficient * angular_impulse);
+        apply_linear_impulse(
+            body1,
+            body2,
+            coefficient * linear_impulse,
+            anchors.r1 + anchors.separation,
+            anchors.r2,
+        );
+    }
+
+    fn solve_impulses(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        softness: SoftnessCoefficients,
+        delta_secs: Scalar,
+        use_bias: bool,
+    ) {
+        let linear_softness =
+            ConstraintSoftness::new(self.softness.linear, self.compliance, softness, delta_secs);
+        let angular_softness =
+            ConstraintSoftness::new(self.softness.angular, self.compliance, softness, delta_secs);
+
+        // Constrain the relative rotation of the bodies around each angular axis
+        #[cfg(feature = "2d")]
+        {
+            if let Some(limit) = self.angular_motion[0].angle_limit() {
+                let angle = body1.rotation.angle_between(*body2.rotation);
+                solve_angular_constraint(
+                    body1,
+                    body2,
+                    angle,
+                    limit,
+                    &mut self.angular_impulses[0],
+                    angular_softness,
+                    delta_secs,
+                    use_bias,
+                );
+            } else {
+                self.angular_impulses[0] = 0.0;
+            }
+            self.torque = self.angular_impulses[0] / delta_secs;
+        }
+        #[cfg(feature = "3d")]
+        {
+            self.torque = Vector::ZERO;
+            for i in 0..ANGULAR_DIM {
+                let Some(limit) = self.angular_motion[i].angle_limit() else {
+                    self.angular_impulses[i] = 0.0;
+                    continue;
+                };
+
+                // The angle around the axis `n` is measured between the perpendicular axis `n1`
+                // on the first body and the projection `n2` of the same axis on the second body.
+                let n = *body1.rotation * Vector::AXES[i];
+                let n1 = *body1.rotation * Vector::AXES[(i + 1) % 3];
+                let n2 = *body2.rotation * Vector::AXES[(i + 1) % 3];
+                let Some(n2) = (n2 - n.dot(n2) * n).try_normalize() else {
+                    continue;
+                };
+
+                let angle = n1.cross(n2).dot(n).atan2(n1.dot(n2));
+
+                solve_angular_constraint(
+                    body1,
+                    body2,
+                    n,
+                    angle,
+                    limit,
+                    &mut self.angular_impulses[i],
+                    angular_softness,
+                    delta_secs,
+                    use_bias,
+                );
+                self.torque += self.angular_impulses[i] * n / delta_secs;
+            }
+        }
+
+        // Constrain the relative translation of the bodies along each linear axis
+        let anchors = JointAnchors::new(body1, body2, self.local_anchor1, self.local_anchor2);
+
+        // The linear constraints are applied at the attachment point of the second body.
+        let r1 = anchors.r1 + anchors.separation;
+        let r2 = anchors.r2;
+
+        self.force = Vector::ZERO;
+        for i in 0..DIM {
+            let Some(limit) = self.linear_motion[i].distance_limit() else {
+                self.linear_impulses[i] = 0.0;
+                continue;
+            };
+
+            let axis = *body1.rotation * Vector::AXES[i];
+            solve_linear_constraint(
+                body1,
+                body2,
+                axis,
+                r1,
+                r2,
+                anchors.separation.dot(axis),
+                limit,
+                &mut self.linear_impulses[i],
+                linear_softness,
+                delta_secs,
+                use_bias,
+            );
+            self.force += impulse_to_force(self.linear_impulses[i] * axis, delta_secs);
+        }
+    }
+}
+
+impl Joint for GenericJoint {
+    fn new(entity1: Entity, entity2: Entity) -> Self {
+        Self {
+            entity1,
+            entity2,
+            local_anchor1: Vector::ZERO,
+            local_anchor2: Vector::ZERO,
+            linear_motion: [AxisMotion::Locked; DIM],
+            angular_motion: [AxisMotion::Locked; ANGULAR_DIM],
+            linear_motors: [None; DIM],
+            angular_motors: [None; ANGULAR_DIM],
+            disable_collision: true,
+            damping_linear: 1.0,
+            damping_angular: 1.0,
+            position_lagrange: 0.0,
+            angular_lagrange: [0.0; ANGULAR_DIM],
+            compliance: 0.0,
+            softness: JointSoftness::RIGID,
+            force: Vector::ZERO,
+            torque: Torque::ZERO,
+            linear_impulses: [0.0; DIM],
+            angular_impulses: [0.0; ANGULAR_DIM],
+        }
+    }
+
+    fn with_compliance(self, compliance: Scalar) -> Self {
+        Self { compliance, ..self }
+    }
+
+    fn with_softness(self, softness: JointSoftness) -> Self {
+        Self { softness, ..self }
+    }
+
+    fn with_local_anchor_1(self, anchor: Vector) -> Self {
+        Self {
+            local_anchor1: anchor,
+            ..self
+        }
+    }
+
+    fn with_local_anchor_2(self, anchor: Vector) -> Self {
+        Self {
+            local_anchor2: anchor,
+            ..self
+        }
+    }
+
+    fn with_linear_velocity_damping(self, damping: Scalar) -> Self {
+        Self {
+            damping_linear: damping,
+            ..self
+        }
+    }
+
+    fn with_angular_velocity_damping(self, damping: Scalar) -> Self {
+        Self {
+            damping_angular: damping,
+            ..self
+        }
+    }
+
+    fn with_collision_disabled(self, disabled: bool) -> Self {
+        Self {
+            disable_collision: disabled,
+            ..self
+        }
+    }
+
+    fn softness(&self) -> JointSoftness {
+        self.softness
+    }
+
+    fn local_anchor_1(&self) -> Vector {
+        self.local_anchor1
+    }
+
+    fn local_anchor_2(&self) -> Vector {
+        self.local_anchor2
+    }
+
+    fn damping_linear(&self) -> Scalar {
+        self.damping_linear
+    }
+
+    fn damping_angular(&self) -> Scalar {
+        self.damping_angular
+    }
+
+    fn collision_disabled(&self) -> bool {
+        self.disable_collision
+    }
+
+    fn force(&self) -> Vector {
+        self.force
+    }
+
+    fn torque(&self) -> Torque {
+        self.torque
+    }
+
+    fn apply_motor(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        dt: Scalar,
+    ) {
+        let world_r1 = *body1.rotation * self.local_anchor1;
+        let world_r2 = *body2.rotation * self.local_anchor2;
+
+        for (i, motor) in self.linear_motors.iter().enumerate() {
+            let Some(motor) = motor else {
+                continue;
+            };
+
+            let axis = *body1.rotation * Vector::AXES[i];
+            let position =
+                (body2.current_position() + world_r2 - body1.current_position() - world_r1)
+                    .dot(axis);
+
+            motor.apply_linear(body1, body2, axis, position, dt);
+        }
+
+        #[cfg(feature = "2d")]
+        if let Some(motor) = self.angular_motors[0] {
+            let angle = body1.rotation.angle_between(*body2.rotation);
+            motor.apply_angular(body1, body2, angle, dt);
+        }
+        #[cfg(feature = "3d")]
+        for (i, motor) in self.angular_motors.iter().enumerate() {
+            let Some(motor) = motor else {
+                continue;
+            };
+
+            // [a1, b1, b2], where [a, b] are perpendicular unit axes on the bodies.
+            let a1 = *body1.rotation * Vector::AXES[i];
+            let b1 = *body1.rotation * Vector::AXES[(i + 1) % 3];
+            let b2 = *body2.rotation * Vector::AXES[(i + 1) % 3];
+
+            // Relative angle of the bodies around the axis in the [-pi, pi] range.
+            let angle = b1.cross(b2).dot(a1).atan2(b1.dot(b2));
+
+            motor.apply_angular(body1, body2, a1, angle, dt);
+        }
+    }
+}
+
+impl GenericJoint {
+    /// Sets the motion allowed along the given linear axis.
+    pub fn with_linear_motion(mut self, axis: JointAxis, motion: AxisMotion) -> Self {
+        self.linear_motion[axis.index()] = motion;
+        self
+    }
+
+    /// Sets the motion allowed around the angular axis.
+    #[cfg(feature = "2d")]
+    pub fn with_angular_motion(mut self, motion: AxisMotion) -> Self {
+        self.angular_motion[0] = motion;
+        self
+    }
+
+    /// Sets the motion allowed around the given angular axis.
+    #[cfg(feature = "3d")]
+    pub fn with_angular_motion(mut self, axis: JointAxis, motion: AxisMotion) -> Self {
+        self.angular_motion[axis.index()] = motion;
+        self
+    }
+
+    /// Sets the motor that drives the relative translation along the given linear axis.
+    pub fn with_linear_motor(mut self, axis: JointAxis, motor: JointMotor) -> Self {
+        self.linear_motors[axis.index()] = Some(motor);
+        self
+    }
+
+    /// Sets the motor that drives the relative rotation around the angular axis.
+    #[cfg(feature = "2d")]
+    pub fn with_angular_motor(mut self, motor: JointMotor) -> Self {
+        self.angular_motors[0] = Some(motor);
+        self
+    }
+
+    /// Sets the motor that drives the relative rotation around the given angular axis.
+    #[cfg(feature = "3d")]
+    pub fn with_angular_motor(mut self, axis: JointAxis, motor: JointMotor) -> Self {
+        self.angular_motors[axis.index()] = Some(motor);
+        self
+    }
+
+    /// Constrains the relative positions of the bodies along each linear axis.
+    ///
+    /// Returns the force exerted by this constraint.
+    fn constrain_positions(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        dt: Scalar,
+    ) -> Vector {
+        let world_r1 = *body1.rotation * self.local_anchor1;
+        let world_r2 = *body2.rotation * self.local_anchor2;
+
+        let mut delta_x = Vector::ZERO;
+
+        for (i, motion) in self.linear_motion.iter().enumerate() {
+            if let Some(limit) = motion.distance_limit() {
+                delta_x += limit.compute_correction_along_axis(
+                    body1.current_position() + world_r1,
+                    body2.current_position() + world_r2,
+                    *body1.rotation * Vector::AXES[i],
+                );
+            }
+        }
+
+        let magnitude = delta_x.length();
+
+        if magnitude <= Scalar::EPSILON {
+            return Vector::ZERO;
+        }
+
+        let dir = delta_x / magnitude;
+
+        // Compute generalized inverse masses
+        let w1 = PositionConstraint::compute_generalized_inverse_mass(self, body1, world_r1, dir);
+        let w2 = PositionConstraint::compute_generalized_inverse_mass(self, body2, world_r2, dir);
+
+        // Compute Lagrange multiplier update
+        let delta_lagrange = if let Some(softness) = self.softness.linear {
+            let magnitude_dot =
+                dir.dot(body1.velocity_at_point(world_r1) - body2.velocity_at_point(world_r2));
+            self.compute_soft_lagrange_update(
+                self.position_lagrange,
+                magnitude,
+                magnitude_dot,
+                &[w1, w2],
+                softness,
+                dt,
+            )
+        } else {
+            self.compute_lagrange_update(
+                self.position_lagrange,
+                magnitude,
+                &[w1, w2],
+                self.compliance,
+                dt,
+            )
+        };
+        self.position_lagrange += delta_lagrange;
+
+        // Apply positional correction to align the positions of the bodies
+        self.apply_positional_lagrange_update(
+            body1,
+            body2,
+            delta_lagrange,
+            dir,
+            world_r1,
+            world_r2,
+        );
+
+        // Return constraint force
+        self.compute_force(self.position_lagrange, dir, dt)
+    }
+
+    /// Constrains the relative rotation of the bodies around each angular axis.
+    ///
+    /// Returns the torque exerted by this constraint.
+    fn constrain_rotations(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        dt: Scalar,
+    ) -> Torque {
+        let mut torque = Torque::ZERO;
+
+        #[cfg(feature = "2d")]
+        if let Some(limit) = self.angular_motion[0].angle_limit() {
+            if let Some(correction) = limit.compute_correction(*body1.rotation, *body2.rotation, PI)
+            {
+                let mut lagrange = self.angular_lagrange[0];
+                torque += self.align_orientation_with_softness(
+                    body1,
+                    body2,
+                    correction,
+                    &mut lagrange,
+                    self.compliance,
+                    self.softness.angular,
+                    dt,
+                );
+                self.angular_lagrange[0] = lagrange;
+            }
+        }
+
+        #[cfg(feature = "3d")]
+        for i in 0..ANGULAR_DIM {
+            let Some(limit) = self.angular_motion[i].angle_limit() else {
+                continue;
+            };
+
+            // The angle around the axis `n` is measured between the perpendicular axis `n1`
+            // on the first body and the projection `n2` of the same axis on the second body.
+            let n = *body1.rotation * Vector::AXES[i];
+            let n1 = *body1.rotation * Vector::AXES[(i + 1) % 3];
+            let n2 = *body2.rotation * Vector::AXES[(i + 1) % 3];
+            let n2 = n2 - n.dot(n2) * n;
+            let n2_magnitude = n2.length();
+
+            if n2_magnitude <= Scalar::EPSILON {
+                continue;
+            }
+
+            let n2 = n2 / n2_magnitude;
+
+            if let Some(correction) = limit.compute_correction(n, n1, n2, PI) {
+                let mut lagrange = self.angular_lagrange[i];
+                torque += self.align_orientation_with_softness(
+                    body1,
+                    body2,
+                    correction,
+                    &mut lagrange,
+                    self.compliance,
+                    self.softness.angular,
+                    dt,
+                );
+                self.angular_lagrange[i] = lagrange;
+            }
+        }
+
+        torque
+    }
+}
+
+impl PositionConstraint for GenericJoint {}
+
+impl AngularConstraint for GenericJoint {}
+
+impl MapEntities for GenericJoint {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.entity1 = entity_mapper.map_entity(self.entity1);
+        self.entity2 = entity_mapper.map_entity(self.entity2);
+    }
+}
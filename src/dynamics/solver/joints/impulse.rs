@@ -0,0 +1,669 @@
+//! Impulse-based solving for [joints](super).
+//!
+//! When [`SolverConfig::joint_solver`] is set to [`JointSolverMethod::Impulse`], joints are solved
+//! by the same substepped soft constraint solver as contacts instead of using [XPBD](crate::dynamics::solver::xpbd).
+//! Each joint is [warm started](SubstepSolverSet::WarmStart) with the impulses it accumulated during
+//! the previous substep, [solved with a position bias](SubstepSolverSet::SolveConstraints), and
+//! [relaxed](SubstepSolverSet::Relax) together with contacts. Because joints and contacts are solved
+//! in the same iterations, this converges much better for things like chains holding heavy objects.
+//!
+//! Rigid joints are stabilized using the [`JointSoftnessCoefficients`], while joints with a non-zero
+//! compliance act like springs with the corresponding stiffness.
+//!
+//! [Custom joints](super#custom-joints) can support the impulse-based solver by implementing [`ImpulseJoint`].
+//! The functions in this module can be used for solving the most common constraint parts.
+
+use crate::{
+    dynamics::solver::softness_parameters::{SoftnessCoefficients, SoftnessParameters},
+    prelude::*,
+};
+
+// For intra-doc links
+#[allow(unused_imports)]
+use crate::dynamics::solver::{
+    JointSoftnessCoefficients, JointSolverMethod, SolverConfig, SubstepSolverSet,
+};
+
+/// The angular impulse applied by a joint. A scalar in 2D and a vector in 3D.
+#[cfg(feature = "2d")]
+pub type AngularImpulse = Scalar;
+/// The angular impulse applied by a joint. A scalar in 2D and a vector in 3D.
+#[cfg(feature = "3d")]
+pub type AngularImpulse = Vector;
+
+/// A trait for [joints](super) that can be solved by the [impulse-based joint solver](self).
+pub trait ImpulseJoint: Joint {
+    /// Applies the impulses accumulated by the joint, scaled by the given `coefficient`.
+    ///
+    /// See [`SubstepSolverSet::WarmStart`] for more information.
+    fn warm_start(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        coefficient: Scalar,
+    );
+
+    /// Solves the joint's velocity constraints, applying impulses to the given bodies.
+    ///
+    /// The rigid parts of the joint are stabilized using the given `softness`. If `use_bias` is `false`,
+    /// they are solved without a position bias to [relax](SubstepSolverSet::Relax) the velocities.
+    fn solve_impulses(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        softness: SoftnessCoefficients,
+        delta_secs: Scalar,
+        use_bias: bool,
+    );
+}
+
+/// Determines how a part of a joint responds to constraint error in the [impulse-based joint solver](self).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConstraintSoftness {
+    /// The constraint is rigid, and error is resolved using the given [`SoftnessCoefficients`].
+    ///
+    /// The softness is only used when solving with a position bias.
+    Rigid(SoftnessCoefficients),
+    /// The constraint acts like a spring with the given compliance, the inverse of stiffness.
+    ///
+    /// Unlike rigid constraints, springs are also applied when solving without a position bias.
+    Compliant(Scalar),
+    /// The constraint acts like a spring with the given [`SoftnessCoefficients`],
+    /// computed from a frequency and damping ratio using [`SoftnessParameters`].
+    ///
+    /// Unlike rigid constraints, springs are also applied when solving without a position bias.
+    Soft(SoftnessCoefficients),
+}
+
+impl ConstraintSoftness {
+    /// Returns the softness of a constraint with the given [`SoftnessParameters`] and `compliance`.
+    ///
+    /// The softness parameters take priority over the compliance if they are set.
+    /// If neither is set, the constraint is rigid and uses the given `rigid_softness`.
+    pub fn new(
+        softness: Option<SoftnessParameters>,
+        compliance: Scalar,
+        rigid_softness: SoftnessCoefficients,
+        delta_secs: Scalar,
+    ) -> Self {
+        match softness {
+            Some(softness) => Self::Soft(softness.compute_coefficients(delta_secs)),
+            None => Self::from_compliance(compliance, rigid_softness),
+        }
+    }
+
+    /// Returns the softness of a constraint with the given `compliance`.
+    ///
+    /// If the compliance is zero, the constraint is rigid and uses the given `rigid_softness`.
+    pub fn from_compliance(compliance: Scalar, rigid_softness: SoftnessCoefficients) -> Self {
+        if compliance > 0.0 {
+            Self::Compliant(compliance)
+        } else {
+            Self::Rigid(rigid_softness)
+        }
+    }
+
+    /// Computes the incremental impulse for a one-dimensional constraint.
+    ///
+    /// - `inverse_mass` is the generalized inverse mass seen by the constraint.
+    /// - `velocity` is the velocity along the constraint axis.
+    /// - `error` is the current value of the constraint function.
+    /// - `impulse` is the accumulated impulse of the constraint.
+    pub fn compute_impulse(
+        self,
+        inverse_mass: Scalar,
+        velocity: Scalar,
+        error: Scalar,
+        impulse: Scalar,
+        delta_secs: Scalar,
+        use_bias: bool,
+    ) -> Scalar {
+        // Springs are also applied when relaxing.
+        let use_bias = use_bias || matches!(self, Self::Soft(_));
+
+        match self {
+            Self::Rigid(softness) | Self::Soft(softness) if use_bias => {
+                -softness.mass_scale
+                    * inverse_mass.recip_or_zero()
+                    * (velocity + softness.bias * error)
+                    - softness.impulse_scale * impulse
+            }
+            Self::Rigid(_) | Self::Soft(_) => -inverse_mass.recip_or_zero() * velocity,
+            Self::Compliant(compliance) => {
+                // This matches the XPBD compliance formulation:
+                // γ = α / h^2 acts as softness, and the error is corrected fully during one substep.
+                let gamma = compliance / (delta_secs * delta_secs);
+                -(velocity + error / delta_secs + gamma * impulse) / (inverse_mass + gamma)
+            }
+        }
+    }
+
+    /// Computes the incremental impulse for a block of constraints solved simultaneously.
+    ///
+    /// This is the same as [`ConstraintSoftness::compute_impulse`], but `inverse_mass` is a matrix
+    /// that couples the constraints, and the other values are vectors with one value per constraint.
+    pub fn compute_block_impulse(
+        self,
+        inverse_mass: Matrix,
+        velocity: Vector,
+        error: Vector,
+        impulse: Vector,
+        delta_secs: Scalar,
+        use_bias: bool,
+    ) -> Vector {
+        // Springs are also applied when relaxing.
+        let use_bias = use_bias || matches!(self, Self::Soft(_));
+
+        match self {
+            Self::Rigid(softness) | Self::Soft(softness) if use_bias => {
+                -softness.mass_scale * solve_block(inverse_mass, velocity + softness.bias * error)
+                    - softness.impulse_scale * impulse
+            }
+            Self::Rigid(_) | Self::Soft(_) => -solve_block(inverse_mass, velocity),
+            Self::Compliant(compliance) => {
+                let gamma = compliance / (delta_secs * delta_secs);
+                -solve_block(
+                    inverse_mass + Matrix::from_diagonal(Vector::splat(gamma)),
+                    velocity + error / delta_secs + gamma * impulse,
+                )
+            }
+        }
+    }
+}
+
+/// Solves `inverse_mass * x = rhs` for `x`. Returns zero if the matrix is singular.
+fn solve_block(inverse_mass: Matrix, rhs: Vector) -> Vector {
+    if inverse_mass.determinant() == 0.0 {
+        return Vector::ZERO;
+    }
+    inverse_mass.inverse() * rhs
+}
+
+/// The world-space attachment points of a joint.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct JointAnchors {
+    /// The attachment point on the first body relative to its center of mass.
+    pub r1: Vector,
+    /// The attachment point on the second body relative to its center of mass.
+    pub r2: Vector,
+    /// The offset from the attachment point on the first body
+    /// to the attachment point on the second body.
+    pub separation: Vector,
+}
+
+impl JointAnchors {
+    /// Computes the world-space attachment points for the given local anchors
+    /// using the current positions and rotations of the bodies.
+    pub fn new(
+        body1: &RigidBodyQueryItem,
+        body2: &RigidBodyQueryItem,
+        local_anchor1: Vector,
+        local_anchor2: Vector,
+    ) -> Self {
+        let r1 = *body1.rotation * (local_anchor1 - body1.center_of_mass.0);
+        let r2 = *body2.rotation * (local_anchor2 - body2.center_of_mass.0);
+        let p1 = body1.current_position() + *body1.rotation * local_anchor1;
+        let p2 = body2.current_position() + *body2.rotation * local_anchor2;
+
+        Self {
+            r1,
+            r2,
+            separation: p2 - p1,
+        }
+    }
+}
+
+/// Applies a linear `impulse` at the points `r1` and `r2` relative to the centers of mass of the bodies.
+///
+/// The impulse is applied to the second body, and the opposite impulse to the first body.
+pub fn apply_linear_impulse(
+    body1: &mut RigidBodyQueryItem,
+    body2: &mut RigidBodyQueryItem,
+    impulse: Vector,
+    r1: Vector,
+    r2: Vector,
+) {
+    if is_affected(body1, body2) {
+        let inv_mass1 = body1.effective_inv_mass();
+        let inv_inertia1 = body1.effective_world_inv_inertia();
+        body1.linear_velocity.0 -= impulse * inv_mass1;
+        body1.angular_velocity.0 -= inv_inertia1 * cross(r1, impulse);
+    }
+    if is_affected(body2, body1) {
+        let inv_mass2 = body2.effective_inv_mass();
+        let inv_inertia2 = body2.effective_world_inv_inertia();
+        body2.linear_velocity.0 += impulse * inv_mass2;
+        body2.angular_velocity.0 += inv_inertia2 * cross(r2, impulse);
+    }
+}
+
+/// Applies an angular `impulse` to the bodies.
+///
+/// The impulse is applied to the second body, and the opposite impulse to the first body.
+pub fn apply_angular_impulse(
+    body1: &mut RigidBodyQueryItem,
+    body2: &mut RigidBodyQueryItem,
+    impulse: AngularImpulse,
+) {
+    if is_affected(body1, body2) {
+        let inv_inertia1 = body1.effective_world_inv_inertia();
+        body1.angular_velocity.0 -= inv_inertia1 * impulse;
+    }
+    if is_affected(body2, body1) {
+        let inv_inertia2 = body2.effective_world_inv_inertia();
+        body2.angular_velocity.0 += inv_inertia2 * impulse;
+    }
+}
+
+/// Converts a linear `impulse` applied to the second body over the time step `delta_secs`
+/// into the force reported by [`Joint::force`].
+///
+/// Like in the XPBD solver, the reported force is the force applied to the first body.
+pub fn impulse_to_force(impulse: Vector, delta_secs: Scalar) -> Vector {
+    -impulse / delta_secs
+}
+
+/// Returns `true` if the `body` is affected by impulses from a constraint with the `other` body.
+fn is_affected(body: &RigidBodyQueryItem, other: &RigidBodyQueryItem) -> bool {
+    body.rb.is_dynamic() && body.dominance() <= other.dominance()
+}
+
+/// Computes the generalized inverse mass of a constraint along the linear `axis`
+/// applied at the points `r1` and `r2` relative to the centers of mass of the bodies.
+fn linear_inverse_mass(
+    body1: &RigidBodyQueryItem,
+    body2: &RigidBodyQueryItem,
+    axis: Vector,
+    r1: Vector,
+    r2: Vector,
+) -> Scalar {
+    let mut inverse_mass = 0.0;
+
+    if is_affected(body1, body2) {
+        inverse_mass += axis.dot(body1.effective_inv_mass() * axis)
+            + angular_inverse_mass(body1.effective_world_inv_inertia(), cross(r1, axis));
+    }
+    if is_affected(body2, body1) {
+        inverse_mass += axis.dot(body2.effective_inv_mass() * axis)
+            + angular_inverse_mass(body2.effective_world_inv_inertia(), cross(r2, axis));
+    }
+
+    inverse_mass
+}
+
+#[cfg(feature = "2d")]
+fn angular_inverse_mass(inverse_inertia: Scalar, axis: Scalar) -> Scalar {
+    axis * inverse_inertia * axis
+}
+
+#[cfg(feature = "3d")]
+fn angular_inverse_mass(inverse_inertia: Matrix3, axis: Vector) -> Scalar {
+    axis.dot(inverse_inertia * axis)
+}
+
+/// Returns the sum of the world-space inverse inertias of the bodies that are affected by the constraint.
+#[cfg(feature = "2d")]
+fn inverse_inertia_sum(body1: &RigidBodyQueryItem, body2: &RigidBodyQueryItem) -> Scalar {
+    let mut inverse_inertia = 0.0;
+    if is_affected(body1, body2) {
+        inverse_inertia += body1.effective_world_inv_inertia();
+    }
+    if is_affected(body2, body1) {
+        inverse_inertia += body2.effective_world_inv_inertia();
+    }
+    inverse_inertia
+}
+
+/// Returns the sum of the world-space inverse inertias of the bodies that are affected by the constraint.
+#[cfg(feature = "3d")]
+fn inverse_inertia_sum(body1: &RigidBodyQueryItem, body2: &RigidBodyQueryItem) -> Matrix3 {
+    let mut inverse_inertia = Matrix3::ZERO;
+    if is_affected(body1, body2) {
+        inverse_inertia += body1.effective_world_inv_inertia();
+    }
+    if is_affected(body2, body1) {
+        inverse_inertia += body2.effective_world_inv_inertia();
+    }
+    inverse_inertia
+}
+
+/// Computes the incremental impulse for a constraint that keeps `position` within the given limits,
+/// and updates the accumulated `impulse`.
+///
+/// If `min` and `max` are equal, the constraint is bilateral. Otherwise, only the limit closest to
+/// the current position is active, and the accumulated impulse is clamped to only push the position
+/// back within the limits. Limits that aren't violated yet are treated like speculative contacts,
+/// only preventing the velocity from crossing the limit during the substep.
+#[allow(clippy::too_many_arguments)]
+fn compute_limit_impulse(
+    inverse_mass: Scalar,
+    position: Scalar,
+    velocity: Scalar,
+    min: Scalar,
+    max: Scalar,
+    impulse: &mut Scalar,
+    softness: ConstraintSoftness,
+    delta_secs: Scalar,
+    use_bias: bool,
+) -> Scalar {
+    if inverse_mass <= Scalar::EPSILON {
+        return 0.0;
+    }
+
+    if min == max {
+        let delta = softness.compute_impulse(
+            inverse_mass,
+            velocity,
+            position - min,
+            *impulse,
+            delta_secs,
+            use_bias,
+        );
+        *impulse += delta;
+        return delta;
+    }
+
+    let is_lower = position - min <= max - position;
+    let error = if is_lower {
+        position - min
+    } else {
+        position - max
+    };
+
+    let delta = if (is_lower && error > 0.0) || (!is_lower && error < 0.0) {
+        // The limit isn't violated: Only push back the part of the velocity that would cross it.
+        -(velocity + error / delta_secs) / inverse_mass
+    } else {
+        softness.compute_impulse(
+            inverse_mass,
+            velocity,
+            error,
+            *impulse,
+            delta_secs,
+            use_bias,
+        )
+    };
+
+    let new_impulse = if is_lower {
+        (*impulse + delta).max(0.0)
+    } else {
+        (*impulse + delta).min(0.0)
+    };
+    let delta = new_impulse - *impulse;
+    *impulse = new_impulse;
+    delta
+}
+
+/// Solves a constraint that keeps the attachment points of the bodies at the same position.
+///
+/// The accumulated `impulse` is updated, and the incremental impulse is applied to the bodies.
+pub fn solve_point_constraint(
+    body1: &mut RigidBodyQueryItem,
+    body2: &mut RigidBodyQueryItem,
+    anchors: &JointAnchors,
+    impulse: &mut Vector,
+    softness: ConstraintSoftness,
+    delta_secs: Scalar,
+    use_bias: bool,
+) {
+    let JointAnchors { r1, r2, separation } = *anchors;
+
+    let velocity = body2.velocity_at_point(r2) - body1.velocity_at_point(r1);
+
+    // The effective inverse mass matrix of the constraint:
+    //
+    // K = (1/m1 + 1/m2) * I - skew(r1) * I1^-1 * skew(r1) - skew(r2) * I2^-1 * skew(r2)
+    let mut inverse_mass = Matrix::ZERO;
+    if is_affected(body1, body2) {
+        inverse_mass += point_inverse_mass(body1, r1);
+    }
+    if is_affected(body2, body1) {
+        inverse_mass += point_inverse_mass(body2, r2);
+    }
+
+    let delta = softness.compute_block_impulse(
+        inverse_mass,
+        velocity,
+        separation,
+        *impulse,
+        delta_secs,
+        use_bias,
+    );
+    *impulse += delta;
+
+    apply_linear_impulse(body1, body2, delta, r1, r2);
+}
+
+/// Computes the contribution of a body to the inverse mass matrix of a point constraint
+/// applied at the point `r` relative to the center of mass of the body.
+fn point_inverse_mass(body: &RigidBodyQueryItem, r: Vector) -> Matrix {
+    let inverse_mass = Matrix::from_diagonal(body.effective_inv_mass());
+
+    #[cfg(feature = "2d")]
+    {
+        let r_perp = r.perp();
+        inverse_mass
+            + body.effective_world_inv_inertia()
+                * Matrix::from_cols(r_perp * r_perp.x, r_perp * r_perp.y)
+    }
+    #[cfg(feature = "3d")]
+    {
+        let skew = Matrix3::from_cols(
+            Vector::new(0.0, r.z, -r.y),
+            Vector::new(-r.z, 0.0, r.x),
+            Vector::new(r.y, -r.x, 0.0),
+        );
+        inverse_mass - skew * body.effective_world_inv_inertia() * skew
+    }
+}
+
+/// Solves a constraint that keeps the relative `position` of the bodies along the linear `axis` within `limit`.
+///
+/// The constraint is applied at the points `r1` and `r2` relative to the centers of mass of the bodies.
+/// The accumulated `impulse` is updated, and the incremental impulse is applied to the bodies.
+#[allow(clippy::too_many_arguments)]
+pub fn solve_linear_constraint(
+    body1: &mut RigidBodyQueryItem,
+    body2: &mut RigidBodyQueryItem,
+    axis: Vector,
+    r1: Vector,
+    r2: Vector,
+    position: Scalar,
+    limit: DistanceLimit,
+    impulse: &mut Scalar,
+    softness: ConstraintSoftness,
+    delta_secs: Scalar,
+    use_bias: bool,
+) {
+    let velocity = (body2.velocity_at_point(r2) - body1.velocity_at_point(r1)).dot(axis);
+    let inverse_mass = linear_inverse_mass(body1, body2, axis, r1, r2);
+
+    let delta = compute_limit_impulse(
+        inverse_mass,
+        position,
+        velocity,
+        limit.min,
+        limit.max,
+        impulse,
+        softness,
+        delta_secs,
+        use_bias,
+    );
+
+    apply_linear_impulse(body1, body2, delta * axis, r1, r2);
+}
+
+/// Solves a constraint that keeps the relative `angle` of the bodies within `limit`.
+///
+/// The accumulated `impulse` is updated, and the incremental impulse is applied to the bodies.
+#[cfg(feature = "2d")]
+#[allow(clippy::too_many_arguments)]
+pub fn solve_angular_constraint(
+    body1: &mut RigidBodyQueryItem,
+    body2: &mut RigidBodyQueryItem,
+    angle: Scalar,
+    limit: AngleLimit,
+    impulse: &mut Scalar,
+    softness: ConstraintSoftness,
+    delta_secs: Scalar,
+    use_bias: bool,
+) {
+    let velocity = body2.angular_velocity.0 - body1.angular_velocity.0;
+    let inverse_mass = inverse_inertia_sum(body1, body2);
+
+    let delta = compute_limit_impulse(
+        inverse_mass,
+        angle,
+        velocity,
+        limit.min,
+        limit.max,
+        impulse,
+        softness,
+        delta_secs,
+        use_bias,
+    );
+
+    apply_angular_impulse(body1, body2, delta);
+}
+
+/// Solves a constraint that keeps the relative `angle` of the bodies around the world-space `axis` within `limit`.
+///
+/// The accumulated `impulse` is updated, and the incremental impulse is applied to the bodies.
+#[cfg(feature = "3d")]
+#[allow(clippy::too_many_arguments)]
+pub fn solve_angular_constraint(
+    body1: &mut RigidBodyQueryItem,
+    body2: &mut RigidBodyQueryItem,
+    axis: Vector,
+    angle: Scalar,
+    limit: AngleLimit,
+    impulse: &mut Scalar,
+    softness: ConstraintSoftness,
+    delta_secs: Scalar,
+    use_bias: bool,
+) {
+    let velocity = (body2.angular_velocity.0 - body1.angular_velocity.0).dot(axis);
+    let inverse_mass = angular_inverse_mass(inverse_inertia_sum(body1, body2), axis);
+
+    let delta = compute_limit_impulse(
+        inverse_mass,
+        angle,
+        velocity,
+        limit.min,
+        limit.max,
+        impulse,
+        softness,
+        delta_secs,
+        use_bias,
+    );
+
+    apply_angular_impulse(body1, body2, delta * axis);
+}
+
+/// Solves a constraint that keeps the world-space axes `axis1` and `axis2` of the bodies aligned,
+/// allowing rotation only around the shared axis.
+///
+/// The accumulated `impulse` is updated, and the incremental impulse is applied to the bodies.
+#[cfg(feature = "3d")]
+#[allow(clippy::too_many_arguments)]
+pub fn solve_axis_alignment(
+    body1: &mut RigidBodyQueryItem,
+    body2: &mut RigidBodyQueryItem,
+    axis1: Vector,
+    axis2: Vector,
+    impulse: &mut Vector,
+    softness: ConstraintSoftness,
+    delta_secs: Scalar,
+    use_bias: bool,
+) {
+    let inverse_inertia = inverse_inertia_sum(body1, body2);
+    let error = axis1.cross(axis2);
+
+    // Remove the part of the accumulated impulse that is along the free axis
+    // after the bodies have rotated.
+    *impulse = impulse.reject_from_normalized(axis1);
+
+    let b = axis1.any_orthonormal_vector();
+    let c = axis1.cross(b);
+
+    for direction in [b, c] {
+        let inverse_mass = angular_inverse_mass(inverse_inertia, direction);
+        if inverse_mass <= Scalar::EPSILON {
+            continue;
+        }
+
+        let velocity = (body2.angular_velocity.0 - body1.angular_velocity.0).dot(direction);
+        let delta = softness.compute_impulse(
+            inverse_mass,
+            velocity,
+            error.dot(direction),
+            impulse.dot(direction),
+            delta_secs,
+            use_bias,
+        );
+        *impulse += delta * direction;
+
+        apply_angular_impulse(body1, body2, delta * direction);
+    }
+}
+
+/// Solves a constraint that keeps the bodies at the same orientation.
+///
+/// `error` is the rotation from the first body to the second body: an angle in 2D, and a scaled axis in 3D.
+/// The accumulated `impulse` is updated, and the incremental impulse is applied to the bodies.
+pub fn solve_angular_lock(
+    body1: &mut RigidBodyQueryItem,
+    body2: &mut RigidBodyQueryItem,
+    error: AngularImpulse,
+    impulse: &mut AngularImpulse,
+    softness: ConstraintSoftness,
+    delta_secs: Scalar,
+    use_bias: bool,
+) {
+    let velocity = body2.angular_velocity.0 - body1.angular_velocity.0;
+    let inverse_mass = inverse_inertia_sum(body1, body2);
+
+    #[cfg(feature = "2d")]
+    let delta = if inverse_mass <= Scalar::EPSILON {
+        0.0
+    } else {
+        softness.compute_impulse(
+            inverse_mass,
+            velocity,
+            error,
+            *impulse,
+            delta_secs,
+            use_bias,
+        )
+    };
+    #[cfg(feature = "3d")]
+    let delta = softness.compute_block_impulse(
+        inverse_mass,
+        velocity,
+        error,
+        *impulse,
+        delta_secs,
+        use_bias,
+    );
+
+    *impulse += delta;
+
+    apply_angular_impulse(body1, body2, delta);
+}
+
+/// Computes the rotation from `rotation1` to `rotation2`: an angle in 2D, and a scaled axis in 3D.
+///
+/// This can be used as the `error` for [`solve_angular_lock`].
+pub fn rotation_error(rotation1: &Rotation, rotation2: &Rotation) -> AngularImpulse {
+    #[cfg(feature = "2d")]
+    {
+        rotation1.angle_between(*rotation2)
+    }
+    #[cfg(feature = "3d")]
+    {
+        let difference = rotation2.0 * rotation1.inverse().0;
+        // Use the shortest rotation.
+        let sign = if difference.w < 0.0 { -1.0 } else { 1.0 };
+        2.0 * sign * difference.xyz()
+    }
+}
@@ -22,6 +22,7 @@
     feature = "3d",
     doc = "| [`SphericalJoint`] | 1 Rotation                | 3 Rotations                 |"
 )]
+//! | [`GenericJoint`]   | Configurable              | Configurable                |
 //!
 //! ## Using joints
 //!
@@ -49,6 +50,18 @@
 //! *Compliance* refers to the inverse of stiffness, so using a compliance of 0 corresponds to
 //! infinite stiffness.
 //!
+//! Compliance depends on the masses of the connected bodies and the time step, which can make it hard to tune.
+//! Instead, joints can be made springy with a frequency and damping ratio using [`SoftnessParameters`]
+//! and the `with_linear_softness` and `with_angular_softness` methods, or `with_softness` to set both
+//! with a [`JointSoftness`]. This is useful for things like springy suspensions and bendy ropes.
+//!
+//! ### Solver
+//!
+//! By default, joints are solved using [Extended Position-Based Dynamics (XPBD)](dynamics::solver::xpbd).
+//! Setting [`SolverConfig::joint_solver`] to [`JointSolverMethod::Impulse`] instead solves the built-in joints
+//! with the [impulse-based joint solver](impulse), together with contacts. This converges better
+//! for things like chains and ragdolls with large mass ratios.
+//!
 //! ### Attachment positions
 //!
 //! By default, joints are connected to the centers of entities, but attachment positions can be used to change this.
@@ -62,6 +75,37 @@
 //! `with_angular_velocity_damping` methods. Increasing the damping values will cause the velocities
 //! of the connected entities to decrease faster.
 //!
+//! ### Motors
+//!
+//! [`RevoluteJoint`] and [`PrismaticJoint`] can be driven by a [`JointMotor`] using the `with_motor` method.
+//! [`GenericJoint`] supports a motor for each of its axes.
+//! Motors can either drive the bodies at a target velocity, or towards a target position like a servo.
+//!
+//! ### Disabling joints
+//!
+//! Joints can be temporarily disabled by adding the [`JointDisabled`] marker component to the joint entity.
+//! Removing the component enables the joint again, keeping its anchors, limits and other configuration.
+//!
+//! ### Collisions between connected bodies
+//!
+//! By default, the bodies connected by a joint don't collide with each other, so that overlapping colliders
+//! in chains and ragdolls don't fight the joint. Collisions can be enabled again using
+//! `with_collision_disabled(false)`.
+//!
+//! The pairs are collected into the [`JointCollisionFilter`] every physics step, so removing or
+//! [disabling](JointDisabled) the joint restores collisions between the bodies.
+//!
+//! ### Breaking joints
+//!
+//! Joints can be made breakable by adding the [`BreakableJoint`] component to the joint entity.
+//! When the force or torque exerted by the joint exceeds the configured threshold, the joint is broken,
+//! and a [`JointBroken`] event is sent.
+//!
+//! ### Joint forces
+//!
+//! The force and torque exerted by a joint can be read by adding the [`JointForces`] component
+//! to the joint entity. It is updated after each physics step.
+//!
 //! ### Other configuration
 //!
 //! Different joints may have different configuration options. Many joints allow you to change the axis of allowed
@@ -77,6 +121,13 @@
 //! except you should also implement the [`Joint`] trait's methods. The trait has some useful helper methods
 //! like `align_position` and `align_orientation` to reduce some common boilerplate.
 //!
+//! To support the [impulse-based joint solver](impulse), custom joints should also implement [`ImpulseJoint`],
+//! and the `warm_start_joints`, `solve_joints` and `relax_joints` systems should be added for the joint
+//! in the corresponding [`SubstepSolverSet`]s.
+//!
+//! Collisions between the bodies connected by a custom joint are only disabled if the
+//! `update_joint_collision_filter` system is added for the joint before [`PhysicsStepSet::BroadPhase`].
+//!
 //! Many joints also have joint limits. You can use [`DistanceLimit`] and [`AngleLimit`] to help store these limits
 //! and to compute the current distance from the specified limits.
 //!
@@ -85,6 +136,8 @@
 
 mod distance;
 mod fixed;
+mod generic;
+pub mod impulse;
 mod prismatic;
 mod revolute;
 #[cfg(feature = "3d")]
@@ -92,13 +145,15 @@ mod spherical;
 
 pub use distance::*;
 pub use fixed::*;
+pub use generic::*;
+pub use impulse::{AngularImpulse, ImpulseJoint};
 pub use prismatic::*;
 pub use revolute::*;
 #[cfg(feature = "3d")]
 pub use spherical::*;
 
 use crate::{dynamics::solver::xpbd::*, prelude::*};
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::HashSet};
 
 /// A trait for [joints](self).
 pub trait Joint: Component + PositionConstraint + AngularConstraint {
@@ -108,6 +163,36 @@ pub trait Joint: Component + PositionConstraint + AngularConstraint {
     /// Sets the joint's compliance (inverse of stiffness, meters / Newton).
     fn with_compliance(self, compliance: Scalar) -> Self;
 
+    /// Sets the frequency and damping ratio based [softness](JointSoftness) of the joint.
+    ///
+    /// If set, the softness takes priority over the joint's compliance.
+    /// Joints that don't support softness are returned unchanged.
+    #[allow(unused_variables)]
+    fn with_softness(self, softness: JointSoftness) -> Self
+    where
+        Self: Sized,
+    {
+        self
+    }
+
+    /// Sets the softness of the joint's linear part. See [`JointSoftness`].
+    fn with_linear_softness(self, softness: SoftnessParameters) -> Self
+    where
+        Self: Sized,
+    {
+        let joint_softness = self.softness().with_linear(softness);
+        self.with_softness(joint_softness)
+    }
+
+    /// Sets the softness of the joint's angular part. See [`JointSoftness`].
+    fn with_angular_softness(self, softness: SoftnessParameters) -> Self
+    where
+        Self: Sized,
+    {
+        let joint_softness = self.softness().with_angular(softness);
+        self.with_softness(joint_softness)
+    }
+
     /// Sets the attachment point on the first body.
     fn with_local_anchor_1(self, anchor: Vector) -> Self;
 
@@ -120,6 +205,24 @@ pub trait Joint: Component + PositionConstraint + AngularConstraint {
     /// Sets the angular velocity damping caused by the joint.
     fn with_angular_velocity_damping(self, damping: Scalar) -> Self;
 
+    /// Sets whether collisions between the bodies connected by the joint are disabled.
+    ///
+    /// Joints that don't support this are returned unchanged.
+    #[allow(unused_variables)]
+    fn with_collision_disabled(self, disabled: bool) -> Self
+    where
+        Self: Sized,
+    {
+        self
+    }
+
+    /// Returns the frequency and damping ratio based [softness](JointSoftness) of the joint.
+    ///
+    /// Joints that don't support softness are [rigid](JointSoftness::RIGID).
+    fn softness(&self) -> JointSoftness {
+        JointSoftness::RIGID
+    }
+
     /// Returns the local attachment point on the first body.
     fn local_anchor_1(&self) -> Vector;
 
@@ -132,6 +235,39 @@ pub trait Joint: Component + PositionConstraint + AngularConstraint {
     /// Returns the angular velocity damping of the joint.
     fn damping_angular(&self) -> Scalar;
 
+    /// Returns `true` if collisions between the bodies connected by the joint are disabled.
+    ///
+    /// Like for the built-in joints, this is `true` by default.
+    fn collision_disabled(&self) -> bool {
+        true
+    }
+
+    /// Returns the force exerted by the joint during the last substep.
+    ///
+    /// This is used for [breaking joints](BreakableJoint) and [`JointForces`].
+    fn force(&self) -> Vector {
+        Vector::ZERO
+    }
+
+    /// Returns the torque exerted by the joint during the last substep.
+    ///
+    /// This is used for [breaking joints](BreakableJoint) and [`JointForces`].
+    fn torque(&self) -> Torque {
+        Torque::ZERO
+    }
+
+    /// Applies velocity corrections caused by the joint's [motor](JointMotor), if it has one.
+    ///
+    /// This is called after the positional constraints have been solved and velocities have been updated.
+    #[allow(unused_variables)]
+    fn apply_motor(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        dt: Scalar,
+    ) {
+    }
+
     /// Applies a positional correction that aligns the positions of the local attachment points `r1` and `r2`.
     ///
     /// Returns the force exerted by the alignment.
@@ -145,6 +281,27 @@ pub trait Joint: Component + PositionConstraint + AngularConstraint {
         lagrange: &mut Scalar,
         compliance: Scalar,
         dt: Scalar,
+    ) -> Vector {
+        self.align_position_with_softness(body1, body2, r1, r2, lagrange, compliance, None, dt)
+    }
+
+    /// Applies a positional correction that aligns the positions of the local attachment points `r1` and `r2`.
+    ///
+    /// If `softness` is set, the correction is applied like a damped spring
+    /// with the given [`SoftnessParameters`], and the `compliance` is ignored.
+    ///
+    /// Returns the force exerted by the alignment.
+    #[allow(clippy::too_many_arguments)]
+    fn align_position_with_softness(
+        &self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        r1: Vector,
+        r2: Vector,
+        lagrange: &mut Scalar,
+        compliance: Scalar,
+        softness: Option<SoftnessParameters>,
+        dt: Scalar,
     ) -> Vector {
         let world_r1 = *body1.rotation * r1;
         let world_r2 = *body2.rotation * r2;
@@ -163,8 +320,20 @@ pub trait Joint: Component + PositionConstraint + AngularConstraint {
         let w2 = PositionConstraint::compute_generalized_inverse_mass(self, body2, world_r2, dir);
 
         // Compute Lagrange multiplier update
-        let delta_lagrange =
-            self.compute_lagrange_update(*lagrange, magnitude, &[w1, w2], compliance, dt);
+        let delta_lagrange = if let Some(softness) = softness {
+            let magnitude_dot =
+                dir.dot(body1.velocity_at_point(world_r1) - body2.velocity_at_point(world_r2));
+            self.compute_soft_lagrange_update(
+                *lagrange,
+                magnitude,
+                magnitude_dot,
+                &[w1, w2],
+                softness,
+                dt,
+            )
+        } else {
+            self.compute_lagrange_update(*lagrange, magnitude, &[w1, w2], compliance, dt)
+        };
         *lagrange += delta_lagrange;
 
         // Apply positional correction to align the positions of the bodies
@@ -182,6 +351,82 @@ pub trait Joint: Component + PositionConstraint + AngularConstraint {
     }
 }
 
+/// The frequency and damping ratio based softness of a [joint](self), configured separately
+/// for the joint's linear and angular parts.
+///
+/// Unlike compliance, [`SoftnessParameters`] don't depend on the masses of the connected bodies
+/// or the time step, which makes springy joints much more predictable to tune.
+/// A frequency of 5 Hz means that the joint oscillates roughly five times per second
+/// regardless of what it is attached to, and a damping ratio of 1 stops the oscillation
+/// as quickly as possible without overshooting.
+///
+/// If a part has no softness, the joint's compliance is used for it instead, and if the compliance
+/// is zero, that part of the joint is rigid.
+///
+/// Both the default XPBD solver and the [impulse-based joint solver](impulse) support softness.
+/// With XPBD, the velocities used for damping are the ones from the start of the substep.
+///
+/// # Example
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     let chassis = commands.spawn(RigidBody::Dynamic).id();
+///     let wheel = commands.spawn(RigidBody::Dynamic).id();
+///
+///     // A springy suspension that oscillates at 4 Hz and is slightly underdamped.
+///     commands.spawn(
+///         PrismaticJoint::new(chassis, wheel)
+///             .with_linear_softness(SoftnessParameters::new(0.7, 4.0)),
+///     );
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Default, PartialEq)]
+pub struct JointSoftness {
+    /// The softness of the joint's linear part, such as keeping the attachment points together.
+    pub linear: Option<SoftnessParameters>,
+    /// The softness of the joint's angular part, such as aligning the bodies or limiting their angle.
+    pub angular: Option<SoftnessParameters>,
+}
+
+impl JointSoftness {
+    /// A `JointSoftness` without softness for the linear or angular part.
+    pub const RIGID: Self = Self {
+        linear: None,
+        angular: None,
+    };
+
+    /// Creates a new `JointSoftness` with the given softness for the linear and angular parts.
+    pub const fn new(linear: SoftnessParameters, angular: SoftnessParameters) -> Self {
+        Self {
+            linear: Some(linear),
+            angular: Some(angular),
+        }
+    }
+
+    /// Sets the softness of the linear part.
+    pub const fn with_linear(self, softness: SoftnessParameters) -> Self {
+        Self {
+            linear: Some(softness),
+            ..self
+        }
+    }
+
+    /// Sets the softness of the angular part.
+    pub const fn with_angular(self, softness: SoftnessParameters) -> Self {
+        Self {
+            angular: Some(softness),
+            ..self
+        }
+    }
+}
+
 /// A limit that indicates that the distance between two points should be between `min` and `max`.
 #[derive(Clone, Copy, Debug, PartialEq, Reflect)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
@@ -338,3 +583,420 @@ impl AngleLimit {
         None
     }
 }
+
+/// A motor that drives the relative motion of two bodies along or around a joint's free axis.
+///
+/// Motors can be used for things like vehicle wheels, doors and robot arms. They are supported by
+/// [`RevoluteJoint`] and [`PrismaticJoint`], where they drive the relative angle or translation
+/// along the joint's free axis respectively, and by each axis of a [`GenericJoint`].
+///
+/// A motor behaves like a damped spring that pulls the position towards the `target_position`
+/// and the velocity towards the `target_velocity`. The force (or torque) it can apply is limited
+/// by `max_force`.
+///
+/// - A *velocity motor* only has damping, and drives the bodies at the `target_velocity`.
+///   See [`JointMotor::velocity`].
+/// - A *position motor*, also known as a *servo*, has stiffness, and drives the bodies towards
+///   the `target_position`. See [`JointMotor::position`].
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     let frame = commands.spawn(RigidBody::Static).id();
+///     let wheel = commands.spawn(RigidBody::Dynamic).id();
+///
+///     // Spin the wheel at 5 radians per second, with a maximum torque of 100 Nm.
+///     commands.spawn(
+///         RevoluteJoint::new(frame, wheel)
+///             .with_motor(JointMotor::velocity(5.0).with_max_force(100.0)),
+///     );
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct JointMotor {
+    /// The target relative velocity along or around the free axis.
+    pub target_velocity: Scalar,
+    /// The target relative position along or around the free axis.
+    ///
+    /// Only has an effect if `stiffness` is non-zero.
+    ///
+    /// For rotational motors, the relative angle of the bodies is measured in the `[-pi, pi]` range,
+    /// so the motor can't track multiple turns. Targets outside of this range are wrapped to it,
+    /// and the motor drives the bodies towards the target along the shortest arc.
+    pub target_position: Scalar,
+    /// The stiffness of the spring pulling the bodies towards the `target_position`.
+    pub stiffness: Scalar,
+    /// The damping driving the relative velocity towards the `target_velocity`.
+    ///
+    /// Infinite damping makes the motor drive the bodies at exactly the `target_velocity`,
+    /// as long as `max_force` isn't exceeded.
+    pub damping: Scalar,
+    /// The maximum force (or torque for rotational motors) that the motor can apply.
+    pub max_force: Scalar,
+}
+
+impl JointMotor {
+    /// Creates a new velocity motor that drives the bodies at the given `target_velocity`.
+    ///
+    /// The motor has infinite damping and no stiffness.
+    pub const fn velocity(target_velocity: Scalar) -> Self {
+        Self {
+            target_velocity,
+            target_position: 0.0,
+            stiffness: 0.0,
+            damping: Scalar::INFINITY,
+            max_force: Scalar::MAX,
+        }
+    }
+
+    /// Creates a new position motor that drives the bodies towards the given `target_position`
+    /// with the given `stiffness` and `damping`.
+    pub const fn position(target_position: Scalar, stiffness: Scalar, damping: Scalar) -> Self {
+        Self {
+            target_velocity: 0.0,
+            target_position,
+            stiffness,
+            damping,
+            max_force: Scalar::MAX,
+        }
+    }
+
+    /// Sets the maximum force (or torque for rotational motors) that the motor can apply.
+    pub const fn with_max_force(self, max_force: Scalar) -> Self {
+        Self { max_force, ..self }
+    }
+
+    /// Computes the impulse applied by the motor along or around the free axis,
+    /// given the current relative `position` and `velocity` of the bodies,
+    /// the generalized `inverse_mass` of the bodies along the axis, and the time step `dt`.
+    ///
+    /// The spring is integrated implicitly, so it is stable even for very high stiffness.
+    pub fn compute_impulse(
+        &self,
+        position: Scalar,
+        velocity: Scalar,
+        inverse_mass: Scalar,
+        dt: Scalar,
+    ) -> Scalar {
+        if inverse_mass <= Scalar::EPSILON || dt <= Scalar::EPSILON {
+            return 0.0;
+        }
+
+        let impulse = if self.damping.is_infinite() {
+            // Rigid velocity motor.
+            (self.target_velocity - velocity) / inverse_mass
+        } else {
+            let denominator = self.damping + dt * self.stiffness;
+
+            if denominator <= Scalar::EPSILON {
+                return 0.0;
+            }
+
+            // Solve the implicit spring-damper equation for the impulse.
+            // `gamma` acts as softness and `bias` feeds the position error into the velocity.
+            let gamma = 1.0 / (dt * denominator);
+            let bias = self.stiffness / denominator * (self.target_position - position);
+            let target_velocity = self.damping / denominator * self.target_velocity;
+
+            (target_velocity + bias - velocity) / (inverse_mass + gamma)
+        };
+
+        let max_impulse = self.max_force.max(0.0) * dt;
+        impulse.clamp(-max_impulse, max_impulse)
+    }
+
+    /// Returns the relative `angle` of the bodies, offset by full turns such that it is within
+    /// half a turn of the `target_position`.
+    ///
+    /// Angles are measured in the `[-pi, pi]` range, so without this, the position error would jump
+    /// by a full turn when the angle wraps around, and the motor would drive the bodies along the longer arc.
+    fn shortest_arc_angle(&self, angle: Scalar) -> Scalar {
+        let error = (self.target_position - angle + PI).rem_euclid(TAU) - PI;
+        self.target_position - error
+    }
+
+    /// Applies the motor impulse along the world-space `axis` to the linear velocities of the bodies,
+    /// given the current relative `position` of the bodies along the axis.
+    pub(crate) fn apply_linear(
+        &self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        axis: Vector,
+        position: Scalar,
+        dt: Scalar,
+    ) {
+        let velocity = (body2.linear_velocity.0 - body1.linear_velocity.0).dot(axis);
+
+        let inv_mass1 = body1.effective_inv_mass() * axis;
+        let inv_mass2 = body2.effective_inv_mass() * axis;
+        let inverse_mass = axis.dot(inv_mass1) + axis.dot(inv_mass2);
+
+        let impulse = self.compute_impulse(position, velocity, inverse_mass, dt);
+
+        body1.linear_velocity.0 -= inv_mass1 * impulse;
+        body2.linear_velocity.0 += inv_mass2 * impulse;
+    }
+
+    /// Applies the motor impulse to the angular velocities of the bodies,
+    /// given the current relative `angle` of the bodies.
+    #[cfg(feature = "2d")]
+    pub(crate) fn apply_angular(
+        &self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        angle: Scalar,
+        dt: Scalar,
+    ) {
+        let inv_inertia1 = body1.effective_world_inv_inertia();
+        let inv_inertia2 = body2.effective_world_inv_inertia();
+        let velocity = body2.angular_velocity.0 - body1.angular_velocity.0;
+        let angle = self.shortest_arc_angle(angle);
+
+        let impulse = self.compute_impulse(angle, velocity, inv_inertia1 + inv_inertia2, dt);
+
+        body1.angular_velocity.0 -= inv_inertia1 * impulse;
+        body2.angular_velocity.0 += inv_inertia2 * impulse;
+    }
+
+    /// Applies the motor impulse around the world-space `axis` to the angular velocities of the bodies,
+    /// given the current relative `angle` of the bodies around the axis.
+    #[cfg(feature = "3d")]
+    pub(crate) fn apply_angular(
+        &self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        axis: Vector,
+        angle: Scalar,
+        dt: Scalar,
+    ) {
+        let velocity = (body2.angular_velocity.0 - body1.angular_velocity.0).dot(axis);
+        let angle = self.shortest_arc_angle(angle);
+
+        let inv_inertia1 = body1.effective_world_inv_inertia() * axis;
+        let inv_inertia2 = body2.effective_world_inv_inertia() * axis;
+        let inverse_mass = axis.dot(inv_inertia1) + axis.dot(inv_inertia2);
+
+        let impulse = self.compute_impulse(angle, velocity, inverse_mass, dt);
+
+        body1.angular_velocity.0 -= inv_inertia1 * impulse;
+        body2.angular_velocity.0 += inv_inertia2 * impulse;
+    }
+}
+
+/// A marker component that disables a [joint](self) without removing it.
+///
+/// Disabled joints are not solved, and don't apply damping, motor forces or [collision filtering](JointCollisionFilter).
+/// Removing the component enables the joint again.
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Grab;
+///
+/// fn release_grabs(mut commands: Commands, query: Query<Entity, (With<Grab>, Without<JointDisabled>)>) {
+///     for entity in &query {
+///         commands.entity(entity).insert(JointDisabled);
+///     }
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub struct JointDisabled;
+
+/// Pairs of bodies connected by [joints](self) that have collisions between them disabled.
+///
+/// Pairs in the filter are skipped by the [broad phase](BroadPhasePlugin), so no contacts are generated for them.
+///
+/// The filter is rebuilt before the broad phase every physics step, so removing or [disabling](JointDisabled)
+/// a joint restores collisions between its bodies.
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+pub struct JointCollisionFilter(HashSet<(Entity, Entity)>);
+
+impl JointCollisionFilter {
+    /// Returns `true` if collisions between `entity1` and `entity2` are disabled by a joint.
+    pub fn contains(&self, entity1: Entity, entity2: Entity) -> bool {
+        self.0.contains(&Self::key(entity1, entity2))
+    }
+
+    /// Disables collisions between `entity1` and `entity2`.
+    pub fn insert(&mut self, entity1: Entity, entity2: Entity) {
+        self.0.insert(Self::key(entity1, entity2));
+    }
+
+    /// Removes all pairs from the filter.
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Returns the number of pairs in the filter.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the filter contains no pairs.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn key(entity1: Entity, entity2: Entity) -> (Entity, Entity) {
+        if entity1 < entity2 {
+            (entity1, entity2)
+        } else {
+            (entity2, entity1)
+        }
+    }
+}
+
+/// The world-space force and torque exerted by a [joint](self) during the last physics step.
+///
+/// The values are computed from the Lagrange multipliers or impulses of the joint's constraints
+/// in each substep, and averaged over all of the substeps of the physics step.
+///
+/// This component is not added automatically. Add it to a joint entity to have it updated.
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     let entity1 = commands.spawn(RigidBody::Static).id();
+///     let entity2 = commands.spawn(RigidBody::Dynamic).id();
+///
+///     commands.spawn((FixedJoint::new(entity1, entity2), JointForces::default()));
+/// }
+///
+/// fn print_joint_forces(query: Query<&JointForces>) {
+///     for joint_forces in &query {
+///         println!("Force: {}, torque: {}", joint_forces.force, joint_forces.torque);
+///     }
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub struct JointForces {
+    /// The force exerted by the joint.
+    pub force: Vector,
+    /// The torque exerted by the joint.
+    pub torque: Torque,
+}
+
+/// A component that makes a [joint](self) break when the force or torque exerted by it
+/// exceeds a given threshold.
+///
+/// When a joint is broken, a [`JointBroken`] event is sent, and the [`JointBreakAction`]
+/// determines what happens to the joint entity.
+///
+/// ```
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     let entity1 = commands.spawn(RigidBody::Dynamic).id();
+///     let entity2 = commands.spawn(RigidBody::Dynamic).id();
+///
+///     // A fixed joint that breaks when the force exceeds 500 N or the torque exceeds 200 Nm.
+///     commands.spawn((
+///         FixedJoint::new(entity1, entity2),
+///         BreakableJoint::new(500.0, 200.0),
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, PartialEq)]
+pub struct BreakableJoint {
+    /// The maximum force the joint can exert before breaking.
+    pub max_force: Scalar,
+    /// The maximum torque the joint can exert before breaking.
+    pub max_torque: Scalar,
+    /// Determines what happens to the joint entity when the joint breaks.
+    pub action: JointBreakAction,
+}
+
+impl BreakableJoint {
+    /// Creates a new [`BreakableJoint`] with the given force and torque thresholds.
+    pub const fn new(max_force: Scalar, max_torque: Scalar) -> Self {
+        Self {
+            max_force,
+            max_torque,
+            action: JointBreakAction::Despawn,
+        }
+    }
+
+    /// Sets what happens to the joint entity when the joint breaks.
+    pub const fn with_action(self, action: JointBreakAction) -> Self {
+        Self { action, ..self }
+    }
+
+    /// Returns `true` if the given force or torque exceeds the thresholds.
+    pub fn is_exceeded_by(&self, force: Vector, torque: Torque) -> bool {
+        #[cfg(feature = "2d")]
+        let torque = torque.abs();
+        #[cfg(feature = "3d")]
+        let torque = torque.length();
+        force.length() > self.max_force || torque > self.max_torque
+    }
+}
+
+/// Determines what happens to a [`BreakableJoint`] when it breaks.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub enum JointBreakAction {
+    /// The joint entity is despawned.
+    #[default]
+    Despawn,
+    /// The joint component is removed, but the joint entity is kept.
+    RemoveJoint,
+}
+
+/// An event that is sent when a [`BreakableJoint`] breaks.
+///
+/// ```no_run
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn print_broken_joints(mut joint_broken_reader: EventReader<JointBroken>) {
+///     for event in joint_broken_reader.read() {
+///         println!(
+///             "Joint {:?} between {:?} and {:?} broke with a force of {}",
+///             event.joint_entity,
+///             event.entity1,
+///             event.entity2,
+///             event.force.length(),
+///         );
+///     }
+/// }
+/// ```
+#[derive(Event, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct JointBroken {
+    /// The entity of the joint that broke.
+    pub joint_entity: Entity,
+    /// First entity constrained by the joint.
+    pub entity1: Entity,
+    /// Second entity constrained by the joint.
+    pub entity2: Entity,
+    /// The force exerted by the joint when it broke.
+    pub force: Vector,
+    /// The torque exerted by the joint when it broke.
+    pub torque: Torque,
+}
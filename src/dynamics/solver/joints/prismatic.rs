@@ -1,6 +1,9 @@
 //! [`PrismaticJoint`] component.
 
-use crate::{dynamics::solver::xpbd::*, prelude::*};
+use crate::{
+    dynamics::solver::{joints::impulse::*, softness_parameters::SoftnessCoefficients, xpbd::*},
+    prelude::*,
+};
 use bevy::{
     ecs::{
         entity::{EntityMapper, MapEntities},
@@ -29,6 +32,10 @@ pub struct PrismaticJoint {
     pub free_axis: Vector,
     /// The extents of the allowed relative translation along the free axis.
     pub free_axis_limits: Option<DistanceLimit>,
+    /// A motor that drives the relative translation of the bodies along the free axis.
+    pub motor: Option<JointMotor>,
+    /// If `true`, the bodies connected by the joint don't collide with each other. `true` by default.
+    pub disable_collision: bool,
     /// Linear damping applied by the joint.
     pub damping_linear: Scalar,
     /// Angular damping applied by the joint.
@@ -39,10 +46,20 @@ pub struct PrismaticJoint {
     pub align_lagrange: Scalar,
     /// The joint's compliance, the inverse of stiffness, has the unit meters / Newton.
     pub compliance: Scalar,
+    /// The frequency and damping ratio based softness of the joint's linear and angular parts.
+    /// If set, it takes priority over the `compliance`.
+    pub softness: JointSoftness,
     /// The force exerted by the joint.
     pub force: Vector,
     /// The torque exerted by the joint when aligning the bodies.
     pub align_torque: Torque,
+    /// The accumulated linear impulse perpendicular to the free axis,
+    /// used by the [impulse-based joint solver](impulse).
+    pub linear_impulse: Vector,
+    /// The accumulated angular impulse used by the [impulse-based joint solver](impulse).
+    pub angular_impulse: AngularImpulse,
+    /// The accumulated impulse of the free axis limits used by the [impulse-based joint solver](impulse).
+    pub free_axis_limit_impulse: Scalar,
 }
 
 impl XpbdConstraint<2> for PrismaticJoint {
@@ -62,8 +79,15 @@ impl XpbdConstraint<2> for PrismaticJoint {
         // Align orientations
         let difference = self.get_rotation_difference(&body1.rotation, &body2.rotation);
         let mut lagrange = self.align_lagrange;
-        self.align_torque =
-            self.align_orientation(body1, body2, difference, &mut lagrange, compliance, dt);
+        self.align_torque = self.align_orientation_with_softness(
+            body1,
+            body2,
+            difference,
+            &mut lagrange,
+            compliance,
+            self.softness.angular,
+            dt,
+        );
         self.align_lagrange = lagrange;
 
         // Constrain the relative positions of the bodies, only allowing translation along one free axis
@@ -71,6 +95,115 @@ impl XpbdConstraint<2> for PrismaticJoint {
     }
 }
 
+impl ImpulseJoint for PrismaticJoint {
+    fn warm_start(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        coefficient: Scalar,
+    ) {
+        let anchors = JointAnchors::new(body1, body2, self.local_anchor1, self.local_anchor2);
+        let axis1 = *body1.rotation * self.free_axis;
+        let linear_impulse = self.linear_impulse + self.free_axis_limit_impulse * axis1;
+
+        apply_angular_impulse(body1, body2, coefficient * self.angular_impulse);
+        apply_linear_impulse(
+            body1,
+            body2,
+            coefficient * linear_impulse,
+            anchors.r1 + anchors.separation,
+            anchors.r2,
+        );
+    }
+
+    fn solve_impulses(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        softness: SoftnessCoefficients,
+        delta_secs: Scalar,
+        use_bias: bool,
+    ) {
+        let linear_softness =
+            ConstraintSoftness::new(self.softness.linear, self.compliance, softness, delta_secs);
+        let angular_softness =
+            ConstraintSoftness::new(self.softness.angular, self.compliance, softness, delta_secs);
+
+        // Align orientations
+        let error = rotation_error(&body1.rotation, &body2.rotation);
+        solve_angular_lock(
+            body1,
+            body2,
+            error,
+            &mut self.angular_impulse,
+            angular_softness,
+            delta_secs,
+            use_bias,
+        );
+        self.align_torque = self.angular_impulse / delta_secs;
+
+        let anchors = JointAnchors::new(body1, body2, self.local_anchor1, self.local_anchor2);
+        let axis1 = *body1.rotation * self.free_axis;
+
+        // The linear constraints are applied at the attachment point of the second body.
+        let r1 = anchors.r1 + anchors.separation;
+        let r2 = anchors.r2;
+
+        // Limit the relative translation along the free axis
+        if let Some(limits) = self.free_axis_limits {
+            solve_linear_constraint(
+                body1,
+                body2,
+                axis1,
+                r1,
+                r2,
+                anchors.separation.dot(axis1),
+                limits,
+                &mut self.free_axis_limit_impulse,
+                linear_softness,
+                delta_secs,
+                use_bias,
+            );
+        } else {
+            self.free_axis_limit_impulse = 0.0;
+        }
+
+        // Constrain the relative positions of the bodies, only allowing translation along one free axis
+        #[cfg(feature = "2d")]
+        let perpendicular_axes = [Vector::new(axis1.y, -axis1.x)];
+        #[cfg(feature = "3d")]
+        let perpendicular_axes = {
+            let axis2 = axis1.any_orthogonal_vector().normalize();
+            [axis2, axis1.cross(axis2)]
+        };
+
+        let mut linear_impulse = Vector::ZERO;
+        for axis in perpendicular_axes {
+            let mut impulse = self.linear_impulse.dot(axis);
+            solve_linear_constraint(
+                body1,
+                body2,
+                axis,
+                r1,
+                r2,
+                anchors.separation.dot(axis),
+                DistanceLimit::ZERO,
+                &mut impulse,
+                linear_softness,
+                delta_secs,
+                use_bias,
+            );
+            linear_impulse += impulse * axis;
+        }
+        self.linear_impulse = linear_impulse;
+
+        self.force = impulse_to_force(
+            self.linear_impulse + self.free_axis_limit_impulse * axis1,
+            delta_secs,
+        );
+    }
+}
+
 impl Joint for PrismaticJoint {
     fn new(entity1: Entity, entity2: Entity) -> Self {
         Self {
@@ -80,16 +213,25 @@ impl Joint for PrismaticJoint {
             local_anchor2: Vector::ZERO,
             free_axis: Vector::X,
             free_axis_limits: None,
+            motor: None,
+            disable_collision: true,
             damping_linear: 1.0,
             damping_angular: 1.0,
             position_lagrange: 0.0,
             align_lagrange: 0.0,
             compliance: 0.0,
+            softness: JointSoftness::RIGID,
             force: Vector::ZERO,
             #[cfg(feature = "2d")]
             align_torque: 0.0,
             #[cfg(feature = "3d")]
             align_torque: Vector::ZERO,
+            linear_impulse: Vector::ZERO,
+            #[cfg(feature = "2d")]
+            angular_impulse: 0.0,
+            #[cfg(feature = "3d")]
+            angular_impulse: Vector::ZERO,
+            free_axis_limit_impulse: 0.0,
         }
     }
 
@@ -97,6 +239,10 @@ impl Joint for PrismaticJoint {
         Self { compliance, ..self }
     }
 
+    fn with_softness(self, softness: JointSoftness) -> Self {
+        Self { softness, ..self }
+    }
+
     fn with_local_anchor_1(self, anchor: Vector) -> Self {
         Self {
             local_anchor1: anchor,
@@ -125,6 +271,17 @@ impl Joint for PrismaticJoint {
         }
     }
 
+    fn with_collision_disabled(self, disabled: bool) -> Self {
+        Self {
+            disable_collision: disabled,
+            ..self
+        }
+    }
+
+    fn softness(&self) -> JointSoftness {
+        self.softness
+    }
+
     fn local_anchor_1(&self) -> Vector {
         self.local_anchor1
     }
@@ -140,6 +297,39 @@ impl Joint for PrismaticJoint {
     fn damping_angular(&self) -> Scalar {
         self.damping_angular
     }
+
+    fn collision_disabled(&self) -> bool {
+        self.disable_collision
+    }
+
+    fn force(&self) -> Vector {
+        self.force
+    }
+
+    fn torque(&self) -> Torque {
+        self.align_torque
+    }
+
+    fn apply_motor(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        dt: Scalar,
+    ) {
+        let Some(motor) = self.motor else {
+            return;
+        };
+
+        let world_r1 = *body1.rotation * self.local_anchor1;
+        let world_r2 = *body2.rotation * self.local_anchor2;
+        let axis = *body1.rotation * self.free_axis;
+
+        // Relative translation of the anchors along the free axis.
+        let position =
+            (body2.current_position() + world_r2 - body1.current_position() - world_r1).dot(axis);
+
+        motor.apply_linear(body1, body2, axis, position, dt);
+    }
 }
 
 impl PrismaticJoint {
@@ -207,13 +397,26 @@ impl PrismaticJoint {
         let w2 = PositionConstraint::compute_generalized_inverse_mass(self, body2, world_r2, dir);
 
         // Compute Lagrange multiplier update
-        let delta_lagrange = self.compute_lagrange_update(
-            self.position_lagrange,
-            magnitude,
-            &[w1, w2],
-            self.compliance,
-            dt,
-        );
+        let delta_lagrange = if let Some(softness) = self.softness.linear {
+            let magnitude_dot =
+                dir.dot(body1.velocity_at_point(world_r1) - body2.velocity_at_point(world_r2));
+            self.compute_soft_lagrange_update(
+                self.position_lagrange,
+                magnitude,
+                magnitude_dot,
+                &[w1, w2],
+                softness,
+                dt,
+            )
+        } else {
+            self.compute_lagrange_update(
+                self.position_lagrange,
+                magnitude,
+                &[w1, w2],
+                self.compliance,
+                dt,
+            )
+        };
         self.position_lagrange += delta_lagrange;
 
         // Apply positional correction to align the positions of the bodies
@@ -246,6 +449,14 @@ impl PrismaticJoint {
         }
     }
 
+    /// Sets the motor that drives the relative translation of the bodies along the free axis.
+    pub fn with_motor(self, motor: JointMotor) -> Self {
+        Self {
+            motor: Some(motor),
+            ..self
+        }
+    }
+
     #[cfg(feature = "2d")]
     fn get_rotation_difference(&self, rot1: &Rotation, rot2: &Rotation) -> Scalar {
         rot1.angle_between(*rot2)
@@ -1,6 +1,9 @@
 //! [`RevoluteJoint`] component.
 
-use crate::{dynamics::solver::xpbd::*, prelude::*};
+use crate::{
+    dynamics::solver::{joints::impulse::*, softness_parameters::SoftnessCoefficients, xpbd::*},
+    prelude::*,
+};
 use bevy::{
     ecs::{
         entity::{EntityMapper, MapEntities},
@@ -35,6 +38,10 @@ pub struct RevoluteJoint {
     pub aligned_axis: Vector,
     /// The extents of the allowed relative rotation of the bodies around the `aligned_axis`.
     pub angle_limit: Option<AngleLimit>,
+    /// A motor that drives the relative rotation of the bodies around the `aligned_axis`.
+    pub motor: Option<JointMotor>,
+    /// If `true`, the bodies connected by the joint don't collide with each other. `true` by default.
+    pub disable_collision: bool,
     /// Linear damping applied by the joint.
     pub damping_linear: Scalar,
     /// Angular damping applied by the joint.
@@ -47,12 +54,22 @@ pub struct RevoluteJoint {
     pub angle_limit_lagrange: Scalar,
     /// The joint's compliance, the inverse of stiffness, has the unit meters / Newton.
     pub compliance: Scalar,
+    /// The frequency and damping ratio based softness of the joint's linear and angular parts.
+    /// If set, it takes priority over the `compliance`.
+    pub softness: JointSoftness,
     /// The force exerted by the joint.
     pub force: Vector,
     /// The torque exerted by the joint when aligning the bodies.
     pub align_torque: Torque,
     /// The torque exerted by the joint when limiting the relative rotation of the bodies around the `aligned_axis`.
     pub angle_limit_torque: Torque,
+    /// The accumulated linear impulse used by the [impulse-based joint solver](impulse).
+    pub linear_impulse: Vector,
+    /// The accumulated angular impulse aligning the bodies, used by the [impulse-based joint solver](impulse).
+    #[cfg(feature = "3d")]
+    pub align_impulse: Vector,
+    /// The accumulated impulse of the angle limit used by the [impulse-based joint solver](impulse).
+    pub angle_limit_impulse: Scalar,
 }
 
 impl XpbdConstraint<2> for RevoluteJoint {
@@ -68,15 +85,21 @@ impl XpbdConstraint<2> for RevoluteJoint {
 
     fn solve(&mut self, bodies: [&mut RigidBodyQueryItem; 2], dt: Scalar) {
         let [body1, body2] = bodies;
-        let compliance = self.compliance;
 
         #[cfg(feature = "3d")]
         {
             // Constrain the relative rotation of the bodies, only allowing rotation around one free axis
             let difference = self.get_rotation_difference(&body1.rotation, &body2.rotation);
             let mut lagrange = self.align_lagrange;
-            self.align_torque =
-                self.align_orientation(body1, body2, difference, &mut lagrange, compliance, dt);
+            self.align_torque = self.align_orientation_with_softness(
+                body1,
+                body2,
+                difference,
+                &mut lagrange,
+                self.compliance,
+                self.softness.angular,
+                dt,
+            );
             self.align_lagrange = lagrange;
         }
 
@@ -85,19 +108,135 @@ impl XpbdConstraint<2> for RevoluteJoint {
 
         // Align positions
         let mut lagrange = self.position_lagrange;
-        self.force = self.align_position(
+        self.force = self.align_position_with_softness(
             body1,
             body2,
             self.local_anchor1,
             self.local_anchor2,
             &mut lagrange,
-            compliance,
+            self.compliance,
+            self.softness.linear,
             dt,
         );
         self.position_lagrange = lagrange;
     }
 }
 
+impl ImpulseJoint for RevoluteJoint {
+    fn warm_start(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        coefficient: Scalar,
+    ) {
+        let anchors = JointAnchors::new(body1, body2, self.local_anchor1, self.local_anchor2);
+
+        #[cfg(feature = "2d")]
+        let angular_impulse = self.angle_limit_impulse;
+        #[cfg(feature = "3d")]
+        let angular_impulse =
+            self.align_impulse + self.angle_limit_impulse * (*body1.rotation * self.aligned_axis);
+
+        apply_angular_impulse(body1, body2, coefficient * angular_impulse);
+        apply_linear_impulse(
+            body1,
+            body2,
+            coefficient * self.linear_impulse,
+            anchors.r1,
+            anchors.r2,
+        );
+    }
+
+    fn solve_impulses(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        softness: SoftnessCoefficients,
+        delta_secs: Scalar,
+        use_bias: bool,
+    ) {
+        let linear_softness =
+            ConstraintSoftness::new(self.softness.linear, self.compliance, softness, delta_secs);
+        let angular_softness =
+            ConstraintSoftness::new(self.softness.angular, self.compliance, softness, delta_secs);
+
+        #[cfg(feature = "3d")]
+        {
+            // Constrain the relative rotation of the bodies, only allowing rotation around one free axis
+            let a1 = *body1.rotation * self.aligned_axis;
+            let a2 = *body2.rotation * self.aligned_axis;
+            solve_axis_alignment(
+                body1,
+                body2,
+                a1,
+                a2,
+                &mut self.align_impulse,
+                angular_softness,
+                delta_secs,
+                use_bias,
+            );
+            self.align_torque = self.align_impulse / delta_secs;
+        }
+
+        // Apply angle limits when rotating around the free axis
+        if let Some(angle_limit) = self.angle_limit {
+            #[cfg(feature = "2d")]
+            {
+                let angle = body1.rotation.angle_between(*body2.rotation);
+                solve_angular_constraint(
+                    body1,
+                    body2,
+                    angle,
+                    angle_limit,
+                    &mut self.angle_limit_impulse,
+                    angular_softness,
+                    delta_secs,
+                    use_bias,
+                );
+                self.angle_limit_torque = self.angle_limit_impulse / delta_secs;
+            }
+            #[cfg(feature = "3d")]
+            {
+                // [a1, b1, b2], where [a, b] are perpendicular unit axes on the bodies.
+                let a1 = *body1.rotation * self.aligned_axis;
+                let b1 = *body1.rotation * self.aligned_axis.any_orthonormal_vector();
+                let b2 = *body2.rotation * self.aligned_axis.any_orthonormal_vector();
+
+                // Relative angle of the bodies around the aligned axis in the [-pi, pi] range.
+                let angle = b1.cross(b2).dot(a1).atan2(b1.dot(b2));
+
+                solve_angular_constraint(
+                    body1,
+                    body2,
+                    a1,
+                    angle,
+                    angle_limit,
+                    &mut self.angle_limit_impulse,
+                    angular_softness,
+                    delta_secs,
+                    use_bias,
+                );
+                self.angle_limit_torque = self.angle_limit_impulse * a1 / delta_secs;
+            }
+        } else {
+            self.angle_limit_impulse = 0.0;
+        }
+
+        // Align positions
+        let anchors = JointAnchors::new(body1, body2, self.local_anchor1, self.local_anchor2);
+        solve_point_constraint(
+            body1,
+            body2,
+            &anchors,
+            &mut self.linear_impulse,
+            linear_softness,
+            delta_secs,
+            use_bias,
+        );
+        self.force = impulse_to_force(self.linear_impulse, delta_secs);
+    }
+}
+
 impl Joint for RevoluteJoint {
     fn new(entity1: Entity, entity2: Entity) -> Self {
         Self {
@@ -107,12 +246,15 @@ impl Joint for RevoluteJoint {
             local_anchor2: Vector::ZERO,
             aligned_axis: Vector3::Z,
             angle_limit: None,
+            motor: None,
+            disable_collision: true,
             damping_linear: 1.0,
             damping_angular: 1.0,
             position_lagrange: 0.0,
             align_lagrange: 0.0,
             angle_limit_lagrange: 0.0,
             compliance: 0.0,
+            softness: JointSoftness::RIGID,
             force: Vector::ZERO,
             #[cfg(feature = "2d")]
             align_torque: 0.0,
@@ -122,6 +264,10 @@ impl Joint for RevoluteJoint {
             angle_limit_torque: 0.0,
             #[cfg(feature = "3d")]
             angle_limit_torque: Vector::ZERO,
+            linear_impulse: Vector::ZERO,
+            #[cfg(feature = "3d")]
+            align_impulse: Vector::ZERO,
+            angle_limit_impulse: 0.0,
         }
     }
 
@@ -129,6 +275,10 @@ impl Joint for RevoluteJoint {
         Self { compliance, ..self }
     }
 
+    fn with_softness(self, softness: JointSoftness) -> Self {
+        Self { softness, ..self }
+    }
+
     fn with_local_anchor_1(self, anchor: Vector) -> Self {
         Self {
             local_anchor1: anchor,
@@ -157,6 +307,17 @@ impl Joint for RevoluteJoint {
         }
     }
 
+    fn with_collision_disabled(self, disabled: bool) -> Self {
+        Self {
+            disable_collision: disabled,
+            ..self
+        }
+    }
+
+    fn softness(&self) -> JointSoftness {
+        self.softness
+    }
+
     fn local_anchor_1(&self) -> Vector {
         self.local_anchor1
     }
@@ -172,6 +333,47 @@ impl Joint for RevoluteJoint {
     fn damping_angular(&self) -> Scalar {
         self.damping_angular
     }
+
+    fn collision_disabled(&self) -> bool {
+        self.disable_collision
+    }
+
+    fn force(&self) -> Vector {
+        self.force
+    }
+
+    fn torque(&self) -> Torque {
+        self.align_torque + self.angle_limit_torque
+    }
+
+    fn apply_motor(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        dt: Scalar,
+    ) {
+        let Some(motor) = self.motor else {
+            return;
+        };
+
+        #[cfg(feature = "2d")]
+        {
+            let angle = body1.rotation.angle_between(*body2.rotation);
+            motor.apply_angular(body1, body2, angle, dt);
+        }
+        #[cfg(feature = "3d")]
+        {
+            // [a1, b1, b2], where [a, b] are perpendicular unit axes on the bodies.
+            let a1 = *body1.rotation * self.aligned_axis;
+            let b1 = *body1.rotation * self.aligned_axis.any_orthonormal_vector();
+            let b2 = *body2.rotation * self.aligned_axis.any_orthonormal_vector();
+
+            // Relative angle of the bodies around the aligned axis in the [-pi, pi] range.
+            let angle = b1.cross(b2).dot(a1).atan2(b1.dot(b2));
+
+            motor.apply_angular(body1, body2, a1, angle, dt);
+        }
+    }
 }
 
 impl RevoluteJoint {
@@ -192,6 +394,14 @@ impl RevoluteJoint {
         }
     }
 
+    /// Sets the motor that drives the relative rotation of the bodies around the `aligned_axis`.
+    pub fn with_motor(self, motor: JointMotor) -> Self {
+        Self {
+            motor: Some(motor),
+            ..self
+        }
+    }
+
     #[cfg(feature = "3d")]
     fn get_rotation_difference(&self, rot1: &Rotation, rot2: &Rotation) -> Vector3 {
         let a1 = rot1 * self.aligned_axis;
@@ -225,8 +435,15 @@ impl RevoluteJoint {
         };
 
         let mut lagrange = self.angle_limit_lagrange;
-        let torque =
-            self.align_orientation(body1, body2, correction, &mut lagrange, self.compliance, dt);
+        let torque = self.align_orientation_with_softness(
+            body1,
+            body2,
+            correction,
+            &mut lagrange,
+            self.compliance,
+            self.softness.angular,
+            dt,
+        );
         self.angle_limit_lagrange = lagrange;
         torque
     }
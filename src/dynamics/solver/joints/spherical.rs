@@ -1,6 +1,9 @@
 //! [`SphericalJoint`] component.
 
-use crate::{dynamics::solver::xpbd::*, prelude::*};
+use crate::{
+    dynamics::solver::{joints::impulse::*, softness_parameters::SoftnessCoefficients, xpbd::*},
+    prelude::*,
+};
 use bevy::{
     ecs::{
         entity::{EntityMapper, MapEntities},
@@ -33,6 +36,8 @@ pub struct SphericalJoint {
     pub swing_limit: Option<AngleLimit>,
     /// The extents of the allowed relative rotation of the bodies around the `twist_axis`.
     pub twist_limit: Option<AngleLimit>,
+    /// If `true`, the bodies connected by the joint don't collide with each other. `true` by default.
+    pub disable_collision: bool,
     /// Linear damping applied by the joint.
     pub damping_linear: Scalar,
     /// Angular damping applied by the joint.
@@ -45,12 +50,21 @@ pub struct SphericalJoint {
     pub twist_lagrange: Scalar,
     /// The joint's compliance, the inverse of stiffness, has the unit meters / Newton.
     pub compliance: Scalar,
+    /// The frequency and damping ratio based softness of the joint's linear and angular parts.
+    /// If set, it takes priority over the `compliance`.
+    pub softness: JointSoftness,
     /// The force exerted by the joint.
     pub force: Vector,
     /// The torque exerted by the joint when limiting the relative rotation of the bodies around the `swing_axis`.
     pub swing_torque: Torque,
     /// The torque exerted by the joint when limiting the relative rotation of the bodies around the `twist_axis`.
     pub twist_torque: Torque,
+    /// The accumulated linear impulse used by the [impulse-based joint solver](impulse).
+    pub linear_impulse: Vector,
+    /// The accumulated impulse of the swing limits used by the [impulse-based joint solver](impulse).
+    pub swing_impulse: Scalar,
+    /// The accumulated impulse of the twist limits used by the [impulse-based joint solver](impulse).
+    pub twist_impulse: Scalar,
 }
 
 impl XpbdConstraint<2> for SphericalJoint {
@@ -70,13 +84,14 @@ impl XpbdConstraint<2> for SphericalJoint {
 
         // Align positions
         let mut lagrange = self.position_lagrange;
-        self.force = self.align_position(
+        self.force = self.align_position_with_softness(
             body1,
             body2,
             self.local_anchor1,
             self.local_anchor2,
             &mut lagrange,
             compliance,
+            self.softness.linear,
             dt,
         );
         self.position_lagrange = lagrange;
@@ -89,6 +104,107 @@ impl XpbdConstraint<2> for SphericalJoint {
     }
 }
 
+impl ImpulseJoint for SphericalJoint {
+    fn warm_start(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        coefficient: Scalar,
+    ) {
+        let anchors = JointAnchors::new(body1, body2, self.local_anchor1, self.local_anchor2);
+
+        let mut angular_impulse = Vector::ZERO;
+        if let Some((axis, _)) = self.swing_axis_and_angle(&body1.rotation, &body2.rotation) {
+            angular_impulse += self.swing_impulse * axis;
+        }
+        if let Some((axis, _)) = self.twist_axis_and_angle(&body1.rotation, &body2.rotation) {
+            angular_impulse += self.twist_impulse * axis;
+        }
+
+        apply_linear_impulse(
+            body1,
+            body2,
+            coefficient * self.linear_impulse,
+            anchors.r1,
+            anchors.r2,
+        );
+        apply_angular_impulse(body1, body2, coefficient * angular_impulse);
+    }
+
+    fn solve_impulses(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        softness: SoftnessCoefficients,
+        delta_secs: Scalar,
+        use_bias: bool,
+    ) {
+        let linear_softness =
+            ConstraintSoftness::new(self.softness.linear, self.compliance, softness, delta_secs);
+        let angular_softness =
+            ConstraintSoftness::new(self.softness.angular, self.compliance, softness, delta_secs);
+
+        // Apply swing limits
+        self.swing_torque = Vector::ZERO;
+        match (
+            self.swing_limit,
+            self.swing_axis_and_angle(&body1.rotation, &body2.rotation),
+        ) {
+            (Some(limit), Some((axis, angle))) => {
+                solve_angular_constraint(
+                    body1,
+                    body2,
+                    axis,
+                    angle,
+                    limit,
+                    &mut self.swing_impulse,
+                    angular_softness,
+                    delta_secs,
+                    use_bias,
+                );
+                self.swing_torque = self.swing_impulse * axis / delta_secs;
+            }
+            _ => self.swing_impulse = 0.0,
+        }
+
+        // Apply twist limits
+        self.twist_torque = Vector::ZERO;
+        match (
+            self.twist_limit,
+            self.twist_axis_and_angle(&body1.rotation, &body2.rotation),
+        ) {
+            (Some(limit), Some((axis, angle))) => {
+                solve_angular_constraint(
+                    body1,
+                    body2,
+                    axis,
+                    angle,
+                    limit,
+                    &mut self.twist_impulse,
+                    angular_softness,
+                    delta_secs,
+                    use_bias,
+                );
+                self.twist_torque = self.twist_impulse * axis / delta_secs;
+            }
+            _ => self.twist_impulse = 0.0,
+        }
+
+        // Align positions
+        let anchors = JointAnchors::new(body1, body2, self.local_anchor1, self.local_anchor2);
+        solve_point_constraint(
+            body1,
+            body2,
+            &anchors,
+            &mut self.linear_impulse,
+            linear_softness,
+            delta_secs,
+            use_bias,
+        );
+        self.force = impulse_to_force(self.linear_impulse, delta_secs);
+    }
+}
+
 impl Joint for SphericalJoint {
     fn new(entity1: Entity, entity2: Entity) -> Self {
         Self {
@@ -100,12 +216,14 @@ impl Joint for SphericalJoint {
             twist_axis: Vector3::Y,
             swing_limit: None,
             twist_limit: None,
+            disable_collision: true,
             damping_linear: 1.0,
             damping_angular: 1.0,
             position_lagrange: 0.0,
             swing_lagrange: 0.0,
             twist_lagrange: 0.0,
             compliance: 0.0,
+            softness: JointSoftness::RIGID,
             force: Vector::ZERO,
             #[cfg(feature = "2d")]
             swing_torque: 0.0,
@@ -115,6 +233,9 @@ impl Joint for SphericalJoint {
             twist_torque: 0.0,
             #[cfg(feature = "3d")]
             twist_torque: Vector::ZERO,
+            linear_impulse: Vector::ZERO,
+            swing_impulse: 0.0,
+            twist_impulse: 0.0,
         }
     }
 
@@ -122,6 +243,10 @@ impl Joint for SphericalJoint {
         Self { compliance, ..self }
     }
 
+    fn with_softness(self, softness: JointSoftness) -> Self {
+        Self { softness, ..self }
+    }
+
     fn with_local_anchor_1(self, anchor: Vector) -> Self {
         Self {
             local_anchor1: anchor,
@@ -150,6 +275,17 @@ impl Joint for SphericalJoint {
         }
     }
 
+    fn with_collision_disabled(self, disabled: bool) -> Self {
+        Self {
+            disable_collision: disabled,
+            ..self
+        }
+    }
+
+    fn softness(&self) -> JointSoftness {
+        self.softness
+    }
+
     fn local_anchor_1(&self) -> Vector {
         self.local_anchor1
     }
@@ -165,6 +301,18 @@ impl Joint for SphericalJoint {
     fn damping_angular(&self) -> Scalar {
         self.damping_angular
     }
+
+    fn collision_disabled(&self) -> bool {
+        self.disable_collision
+    }
+
+    fn force(&self) -> Vector {
+        self.force
+    }
+
+    fn torque(&self) -> Torque {
+        self.swing_torque + self.twist_torque
+    }
 }
 
 impl SphericalJoint {
@@ -185,6 +333,38 @@ impl SphericalJoint {
         }
     }
 
+    /// Returns the world-space axis that the bodies swing around and the swing angle in the `[0, pi]` range,
+    /// or `None` if the swing axes of the bodies are parallel.
+    fn swing_axis_and_angle(&self, rot1: &Rotation, rot2: &Rotation) -> Option<(Vector, Scalar)> {
+        let a1 = *rot1 * self.swing_axis;
+        let a2 = *rot2 * self.swing_axis;
+
+        let n = a1.cross(a2);
+        let n_magnitude = n.length();
+
+        if n_magnitude <= Scalar::EPSILON {
+            return None;
+        }
+
+        Some((n / n_magnitude, n_magnitude.atan2(a1.dot(a2))))
+    }
+
+    /// Returns the world-space axis that the bodies twist around and the twist angle in the `[-pi, pi]` range,
+    /// or `None` if the axis is undefined.
+    fn twist_axis_and_angle(&self, rot1: &Rotation, rot2: &Rotation) -> Option<(Vector, Scalar)> {
+        let a1 = *rot1 * self.swing_axis;
+        let a2 = *rot2 * self.swing_axis;
+
+        let b1 = *rot1 * self.twist_axis;
+        let b2 = *rot2 * self.twist_axis;
+
+        let n = (a1 + a2).try_normalize()?;
+        let n1 = (b1 - n.dot(b1) * n).try_normalize()?;
+        let n2 = (b2 - n.dot(b2) * n).try_normalize()?;
+
+        Some((n, n1.cross(n2).dot(n).atan2(n1.dot(n2))))
+    }
+
     /// Applies angle limits to limit the relative rotation of the bodies around the `swing_axis`.
     fn apply_swing_limits(
         &mut self,
@@ -207,12 +387,13 @@ impl SphericalJoint {
 
             if let Some(correction) = joint_limit.compute_correction(n, a1, a2, PI) {
                 let mut lagrange = self.swing_lagrange;
-                let torque = self.align_orientation(
+                let torque = self.align_orientation_with_softness(
                     body1,
                     body2,
                     correction,
                     &mut lagrange,
                     self.compliance,
+                    self.softness.angular,
                     dt,
                 );
                 self.swing_lagrange = lagrange;
@@ -261,12 +442,13 @@ impl SphericalJoint {
 
             if let Some(correction) = joint_limit.compute_correction(n, n1, n2, max_correction) {
                 let mut lagrange = self.twist_lagrange;
-                let torque = self.align_orientation(
+                let torque = self.align_orientation_with_softness(
                     body1,
                     body2,
                     correction,
                     &mut lagrange,
                     self.compliance,
+                    self.softness.angular,
                     dt,
                 );
                 self.twist_lagrange = lagrange;
@@ -3,16 +3,23 @@
 //! See [`SolverPlugin`].
 
 pub mod contact;
+pub mod graph_coloring;
 pub mod joints;
 pub mod softness_parameters;
 pub mod xpbd;
 
 use crate::prelude::*;
 use bevy::prelude::*;
+#[cfg(all(feature = "parallel", not(feature = "enhanced-determinism")))]
+use {
+    crate::dynamics::rigid_body::RigidBodyCopy,
+    bevy::{ecs::entity::EntityHashSet, tasks::ComputeTaskPool},
+};
 
 use self::{
     contact::ContactConstraint,
     dynamics::integrator::IntegrationSet,
+    graph_coloring::ContactConstraintColors,
     softness_parameters::{SoftnessCoefficients, SoftnessParameters},
 };
 
@@ -25,11 +32,17 @@ use self::{
 /// The solver primarily uses TGS Soft, an impulse-based solver with substepping and [soft constraints](softness_parameters).
 /// Warm starting is used to improve convergence, along with a relaxation pass to reduce overshooting.
 ///
+/// With the `parallel` feature, contact constraints are split into groups of independent constraints
+/// using [graph coloring](graph_coloring), and the constraints of each group are warm started, solved,
+/// and relaxed in parallel. The `enhanced-determinism` feature disables this to keep the order of the constraints stable.
+///
 /// [Speculative collision](dynamics::ccd#speculative-collision) is used by default to prevent tunneling.
 /// Optional [sweep-based Continuous Collision Detection (CCD)](dynamics::ccd#swept-ccd) is handled by the [`CcdPlugin`].
 ///
-/// [Joints](joints) and user constraints are currently solved using [Extended Position-Based Dynamics (XPBD)](xpbd).
-/// In the future, they may transition to an impulse-based approach as well.
+/// [Joints](joints) are solved using [Extended Position-Based Dynamics (XPBD)](xpbd) by default.
+/// If [`SolverConfig::joint_solver`] is set to [`JointSolverMethod::Impulse`], they are instead
+/// solved together with contacts using the [impulse-based joint solver](joints::impulse).
+/// User constraints are currently solved using XPBD.
 ///
 /// ## Steps
 ///
@@ -72,7 +85,11 @@ impl Plugin for SolverPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<SolverConfig>()
             .init_resource::<ContactSoftnessCoefficients>()
-            .init_resource::<ContactConstraints>();
+            .init_resource::<JointSoftnessCoefficients>()
+            .init_resource::<ContactConstraints>()
+            .init_resource::<ContactConstraintColors>()
+            .init_resource::<JointCollisionFilter>()
+            .add_event::<JointBroken>();
 
         if !app.world().contains_resource::<PhysicsLengthUnit>() {
             app.insert_resource(PhysicsLengthUnit(self.length_unit));
@@ -83,7 +100,23 @@ impl Plugin for SolverPlugin {
             .get_schedule_mut(PhysicsSchedule)
             .expect("add PhysicsSchedule first");
 
-        physics.add_systems(update_contact_softness.before(PhysicsStepSet::NarrowPhase));
+        physics.add_systems(update_softness_coefficients.before(PhysicsStepSet::NarrowPhase));
+
+        // Collect the bodies connected by joints that have collisions disabled.
+        physics.add_systems(
+            (
+                |mut filter: ResMut<JointCollisionFilter>| filter.clear(),
+                update_joint_collision_filter::<FixedJoint>,
+                update_joint_collision_filter::<GenericJoint>,
+                update_joint_collision_filter::<RevoluteJoint>,
+                #[cfg(feature = "3d")]
+                update_joint_collision_filter::<SphericalJoint>,
+                update_joint_collision_filter::<PrismaticJoint>,
+                update_joint_collision_filter::<DistanceJoint>,
+            )
+                .chain()
+                .before(PhysicsStepSet::BroadPhase),
+        );
 
         // See `SolverSet` for what each system set is responsible for.
         physics.configure_sets(
@@ -109,6 +142,11 @@ impl Plugin for SolverPlugin {
             .in_set(SolverSet::PreSubstep),
         );
 
+        // Split the contact constraints into colors that can be solved in parallel.
+        #[cfg(all(feature = "parallel", not(feature = "enhanced-determinism")))]
+        physics
+            .add_systems(graph_coloring::color_contact_constraints.in_set(SolverSet::PreSubstep));
+
         // Finalize the positions of bodies by applying the `AccumulatedTranslation`.
         // This runs after the substepping loop.
         physics.add_systems(
@@ -120,6 +158,9 @@ impl Plugin for SolverPlugin {
         // Apply restitution.
         physics.add_systems(solve_restitution.in_set(SolverSet::Restitution));
 
+        // Reset the forces exerted by joints before they are accumulated over the substeps.
+        physics.add_systems(reset_joint_forces.in_set(SolverSet::PreSubstep));
+
         // Store the current contact impulses for the next frame's warm starting.
         physics.add_systems(store_contact_impulses.in_set(SolverSet::StoreContactImpulses));
 
@@ -146,19 +187,49 @@ impl Plugin for SolverPlugin {
         // Warm start the impulses.
         // This applies the impulses stored from the previous substep,
         // which improves convergence.
-        substeps.add_systems(warm_start.in_set(SubstepSolverSet::WarmStart));
+        substeps.add_systems(
+            (
+                (
+                    warm_start_joints::<FixedJoint>,
+                    warm_start_joints::<GenericJoint>,
+                    warm_start_joints::<RevoluteJoint>,
+                    #[cfg(feature = "3d")]
+                    warm_start_joints::<SphericalJoint>,
+                    warm_start_joints::<PrismaticJoint>,
+                    warm_start_joints::<DistanceJoint>,
+                )
+                    .chain()
+                    .run_if(uses_joint_solver(JointSolverMethod::Impulse)),
+                warm_start,
+            )
+                .chain()
+                .in_set(SubstepSolverSet::WarmStart),
+        );
 
         // Solve velocities using a position bias.
         substeps.add_systems(
             (
+                (
+                    solve_joints::<FixedJoint>,
+                    solve_joints::<GenericJoint>,
+                    solve_joints::<RevoluteJoint>,
+                    #[cfg(feature = "3d")]
+                    solve_joints::<SphericalJoint>,
+                    solve_joints::<PrismaticJoint>,
+                    solve_joints::<DistanceJoint>,
+                )
+                    .chain()
+                    .run_if(uses_joint_solver(JointSolverMethod::Impulse)),
                 |mut bodies: Query<RigidBodyQuery>,
                  mut constraints: ResMut<ContactConstraints>,
+                 colors: Res<ContactConstraintColors>,
                  solver_config: Res<SolverConfig>,
                  length_unit: Res<PhysicsLengthUnit>,
                  time: Res<Time>| {
                     solve_contacts(
                         &mut bodies,
                         &mut constraints.0,
+                        &colors,
                         time.delta_seconds_adjusted(),
                         1,
                         true,
@@ -166,6 +237,7 @@ impl Plugin for SolverPlugin {
                     );
                 },
             )
+                .chain()
                 .in_set(SubstepSolverSet::SolveConstraints),
         );
 
@@ -173,14 +245,27 @@ impl Plugin for SolverPlugin {
         // This reduces overshooting caused by warm starting.
         substeps.add_systems(
             (
+                (
+                    relax_joints::<FixedJoint>,
+                    relax_joints::<GenericJoint>,
+                    relax_joints::<RevoluteJoint>,
+                    #[cfg(feature = "3d")]
+                    relax_joints::<SphericalJoint>,
+                    relax_joints::<PrismaticJoint>,
+                    relax_joints::<DistanceJoint>,
+                )
+                    .chain()
+                    .run_if(uses_joint_solver(JointSolverMethod::Impulse)),
                 |mut bodies: Query<RigidBodyQuery>,
                  mut constraints: ResMut<ContactConstraints>,
+                 colors: Res<ContactConstraintColors>,
                  solver_config: Res<SolverConfig>,
                  length_unit: Res<PhysicsLengthUnit>,
                  time: Res<Time>| {
                     solve_contacts(
                         &mut bodies,
                         &mut constraints.0,
+                        &colors,
                         time.delta_seconds_adjusted(),
                         1,
                         false,
@@ -188,6 +273,7 @@ impl Plugin for SolverPlugin {
                     );
                 },
             )
+                .chain()
                 .in_set(SubstepSolverSet::Relax),
         );
 
@@ -207,28 +293,60 @@ impl Plugin for SolverPlugin {
                         previous_rotation.0 = *rotation;
                     }
                 },
-                xpbd::solve_constraint::<FixedJoint, 2>,
-                xpbd::solve_constraint::<RevoluteJoint, 2>,
-                #[cfg(feature = "3d")]
-                xpbd::solve_constraint::<SphericalJoint, 2>,
-                xpbd::solve_constraint::<PrismaticJoint, 2>,
-                xpbd::solve_constraint::<DistanceJoint, 2>,
+                (
+                    xpbd::solve_constraint::<FixedJoint, 2>,
+                    xpbd::solve_constraint::<GenericJoint, 2>,
+                    xpbd::solve_constraint::<RevoluteJoint, 2>,
+                    #[cfg(feature = "3d")]
+                    xpbd::solve_constraint::<SphericalJoint, 2>,
+                    xpbd::solve_constraint::<PrismaticJoint, 2>,
+                    xpbd::solve_constraint::<DistanceJoint, 2>,
+                )
+                    .chain()
+                    .run_if(uses_joint_solver(JointSolverMethod::Xpbd)),
             )
                 .chain()
                 .in_set(SubstepSolverSet::SolveXpbdConstraints),
         );
 
+        // Accumulate the forces exerted by joints during each substep.
+        substeps.add_systems(
+            (
+                accumulate_joint_forces::<FixedJoint>,
+                accumulate_joint_forces::<GenericJoint>,
+                accumulate_joint_forces::<RevoluteJoint>,
+                #[cfg(feature = "3d")]
+                accumulate_joint_forces::<SphericalJoint>,
+                accumulate_joint_forces::<PrismaticJoint>,
+                accumulate_joint_forces::<DistanceJoint>,
+            )
+                .chain()
+                .after(SubstepSolverSet::SolveXpbdConstraints)
+                .before(SubstepSolverSet::SolveUserConstraints),
+        );
+
         // Perform XPBD velocity updates after constraint solving.
         substeps.add_systems(
             (
                 xpbd::project_linear_velocity,
                 xpbd::project_angular_velocity,
                 joint_damping::<FixedJoint>,
+                joint_damping::<GenericJoint>,
                 joint_damping::<RevoluteJoint>,
                 #[cfg(feature = "3d")]
                 joint_damping::<SphericalJoint>,
                 joint_damping::<PrismaticJoint>,
                 joint_damping::<DistanceJoint>,
+                joint_motors::<RevoluteJoint>,
+                joint_motors::<PrismaticJoint>,
+                joint_motors::<GenericJoint>,
+                break_joints::<FixedJoint>,
+                break_joints::<GenericJoint>,
+                break_joints::<RevoluteJoint>,
+                #[cfg(feature = "3d")]
+                break_joints::<SphericalJoint>,
+                break_joints::<PrismaticJoint>,
+                break_joints::<DistanceJoint>,
             )
                 .chain()
                 .in_set(SubstepSolverSet::XpbdVelocityProjection),
@@ -326,7 +444,7 @@ pub enum SolverSet {
 /// 3. Solve constraints with bias ([`SubstepSolverSet::SolveConstraints`])
 /// 4. Integrate positions ([`IntegrationSet::Position`])
 /// 5. Solve constraints without bias to relax velocities ([`SubstepSolverSet::Relax`])
-/// 6. Solve joints using Extended Position-Based Dynamics (XPBD). ([`SubstepSolverSet::SolveXpbdConstraints`])
+/// 6. Solve joints using Extended Position-Based Dynamics (XPBD) unless the [impulse-based joint solver](joints::impulse) is used. ([`SubstepSolverSet::SolveXpbdConstraints`])
 /// 7. Solve user-defined constraints. ([`SubstepSolverSet::SolveUserConstraints`])
 /// 8. Update velocities after XPBD constraint solving. ([`SubstepSolverSet::XpbdVelocityProjection`])
 #[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -344,6 +462,9 @@ pub enum SubstepSolverSet {
     /// and impulses. This reduces overshooting caused by [warm starting](SubstepSolverSet::WarmStart).
     Relax,
     /// Solves joints using Extended Position-Based Dynamics (XPBD).
+    ///
+    /// If [`SolverConfig::joint_solver`] is set to [`JointSolverMethod::Impulse`], joints are instead
+    /// solved in [`SubstepSolverSet::SolveConstraints`] and [`SubstepSolverSet::Relax`].
     SolveXpbdConstraints,
     /// A system set for user constraints.
     SolveUserConstraints,
@@ -431,6 +552,20 @@ pub struct SolverConfig {
     ///
     /// Default: `1`
     pub restitution_iterations: usize,
+
+    /// The method used for solving [joints](joints).
+    ///
+    /// Default: [`JointSolverMethod::Xpbd`]
+    pub joint_solver: JointSolverMethod,
+
+    /// The damping ratio used for stabilizing rigid joints
+    /// with the [impulse-based joint solver](joints::impulse).
+    ///
+    /// Joints use twice the frequency of contacts, scaled by
+    /// [`contact_frequency_factor`](Self::contact_frequency_factor).
+    ///
+    /// Default: `2.0`
+    pub joint_damping_ratio: Scalar,
 }
 
 impl Default for SolverConfig {
@@ -442,10 +577,34 @@ impl Default for SolverConfig {
             warm_start_coefficient: 1.0,
             restitution_threshold: 1.0,
             restitution_iterations: 1,
+            joint_solver: JointSolverMethod::default(),
+            joint_damping_ratio: 2.0,
         }
     }
 }
 
+/// The method used for solving [joints](joints). See [`SolverConfig::joint_solver`].
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub enum JointSolverMethod {
+    /// Joints are solved using [Extended Position-Based Dynamics (XPBD)](xpbd)
+    /// after contacts have been solved.
+    #[default]
+    Xpbd,
+    /// Joints are solved together with contacts using the [impulse-based joint solver](joints::impulse),
+    /// with warm starting and soft constraints.
+    ///
+    /// This handles large mass ratios, like heavy objects hanging from chains, much better.
+    Impulse,
+}
+
+/// A run condition that returns `true` if the given [`JointSolverMethod`] is used.
+pub fn uses_joint_solver(method: JointSolverMethod) -> impl Fn(Res<SolverConfig>) -> bool {
+    move |config: Res<SolverConfig>| config.joint_solver == method
+}
+
 /// The [`SoftnessCoefficients`] used for contacts.
 ///
 /// **Note**: This resource is updated automatically and not intended to be modified manually.
@@ -468,8 +627,23 @@ impl Default for ContactSoftnessCoefficients {
     }
 }
 
-fn update_contact_softness(
+/// The [`SoftnessCoefficients`] used for rigid joints in the [impulse-based joint solver](joints::impulse).
+///
+/// **Note**: This resource is updated automatically and not intended to be modified manually.
+/// Use the [`SolverConfig`] resource instead for tuning joint behavior.
+#[derive(Resource, Clone, Copy, Deref, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct JointSoftnessCoefficients(pub SoftnessCoefficients);
+
+impl Default for JointSoftnessCoefficients {
+    fn default() -> Self {
+        Self(SoftnessParameters::new(2.0, 60.0).compute_coefficients(1.0 / 60.0))
+    }
+}
+
+fn update_softness_coefficients(
     mut coefficients: ResMut<ContactSoftnessCoefficients>,
+    mut joint_coefficients: ResMut<JointSoftnessCoefficients>,
     solver_config: Res<SolverConfig>,
     physics_time: Res<Time<Physics>>,
     substep_time: Res<Time<Substeps>>,
@@ -491,6 +665,9 @@ fn update_contact_softness(
         coefficients.non_dynamic =
             SoftnessParameters::new(solver_config.contact_damping_ratio, 2.0 * hz)
                 .compute_coefficients(h);
+
+        joint_coefficients.0 = SoftnessParameters::new(solver_config.joint_damping_ratio, 2.0 * hz)
+            .compute_coefficients(h);
     }
 }
 
@@ -504,29 +681,29 @@ pub struct ContactConstraints(pub Vec<ContactConstraint>);
 fn warm_start(
     mut bodies: Query<RigidBodyQuery>,
     mut constraints: ResMut<ContactConstraints>,
+    colors: Res<ContactConstraintColors>,
     solver_config: Res<SolverConfig>,
 ) {
-    for constraint in constraints.iter_mut() {
-        debug_assert!(!constraint.points.is_empty());
-
-        let Ok([mut body1, mut body2]) =
-            bodies.get_many_mut([constraint.entity1, constraint.entity2])
-        else {
-            continue;
-        };
-
-        let normal = constraint.normal;
-        let tangent_directions =
-            constraint.tangent_directions(body1.linear_velocity.0, body2.linear_velocity.0);
-
-        constraint.warm_start(
-            &mut body1,
-            &mut body2,
-            normal,
-            tangent_directions,
-            solver_config.warm_start_coefficient,
-        );
-    }
+    for_each_contact_constraint(
+        &mut bodies,
+        &mut constraints,
+        &colors,
+        |constraint, body1, body2| {
+            debug_assert!(!constraint.points.is_empty());
+
+            let normal = constraint.normal;
+            let tangent_directions =
+                constraint.tangent_directions(body1.linear_velocity.0, body2.linear_velocity.0);
+
+            constraint.warm_start(
+                body1,
+                body2,
+                normal,
+                tangent_directions,
+                solver_config.warm_start_coefficient,
+            );
+        },
+    );
 }
 
 /// Solves contacts by iterating through the given contact constraints
@@ -546,27 +723,149 @@ fn warm_start(
 fn solve_contacts(
     bodies: &mut Query<RigidBodyQuery>,
     constraints: &mut [ContactConstraint],
+    colors: &ContactConstraintColors,
     delta_secs: Scalar,
     iterations: usize,
     use_bias: bool,
     max_overlap_solve_speed: Scalar,
 ) {
     for _ in 0..iterations {
-        for constraint in &mut *constraints {
-            let Ok([mut body1, mut body2]) =
-                bodies.get_many_mut([constraint.entity1, constraint.entity2])
-            else {
-                continue;
+        for_each_contact_constraint(bodies, constraints, colors, |constraint, body1, body2| {
+            constraint.solve(body1, body2, delta_secs, use_bias, max_overlap_solve_speed);
+        });
+    }
+}
+
+/// Calls `f` for each contact constraint along with the two bodies it affects.
+///
+/// With the `parallel` feature, the constraints of each [color](graph_coloring) are processed
+/// in parallel, one color at a time. The overflow constraints that couldn't be colored
+/// are processed serially afterwards.
+fn for_each_contact_constraint(
+    bodies: &mut Query<RigidBodyQuery>,
+    constraints: &mut [ContactConstraint],
+    #[cfg_attr(
+        not(all(feature = "parallel", not(feature = "enhanced-determinism"))),
+        allow(unused_variables)
+    )]
+    colors: &ContactConstraintColors,
+    f: impl Fn(&mut ContactConstraint, &mut RigidBodyQueryItem, &mut RigidBodyQueryItem) + Sync,
+) {
+    #[cfg(all(feature = "parallel", not(feature = "enhanced-determinism")))]
+    let constraints = match colors.split(constraints) {
+        Some((colored_constraints, overflow)) => {
+            // The minimum number of constraints processed by a single task.
+            // Small colors are processed without spawning tasks to reduce overhead.
+            const MIN_BATCH_SIZE: usize = 32;
+
+            let task_pool = ComputeTaskPool::get();
+            let bodies = &*bodies;
+            let shared_bodies = bodies.to_readonly();
+
+            let solve_batch = |batch: &mut [ContactConstraint]| {
+                for constraint in batch {
+                    let (mut copy1, mut copy2) = (None, None);
+                    // SAFETY: No two constraints of the same color share a dynamic body,
+                    //         so no other task accesses the same dynamic bodies.
+                    let (Some(mut body1), Some(mut body2)) = (unsafe {
+                        (
+                            get_colored_body(
+                                bodies,
+                                &shared_bodies,
+                                colors,
+                                constraint.entity1,
+                                &mut copy1,
+                            ),
+                            get_colored_body(
+                                bodies,
+                                &shared_bodies,
+                                colors,
+                                constraint.entity2,
+                                &mut copy2,
+                            ),
+                        )
+                    }) else {
+                        continue;
+                    };
+
+                    f(constraint, &mut body1, &mut body2);
+                }
             };
 
-            constraint.solve(
-                &mut body1,
-                &mut body2,
-                delta_secs,
-                use_bias,
-                max_overlap_solve_speed,
-            );
+            for color in colored_constraints {
+                // SAFETY: `solve_batch` accesses the dynamic bodies of its constraints with `Query::get_unchecked`,
+                //         and only reads the shared static and kinematic bodies. This is sound as long as
+                //         no two constraints of the same color share a dynamic body.
+                debug_assert!(
+                    {
+                        let mut dynamic_bodies = EntityHashSet::default();
+                        color.iter().all(|constraint| {
+                            [constraint.entity1, constraint.entity2]
+                                .into_iter()
+                                .all(|entity| {
+                                    colors.is_shared(entity) || dynamic_bodies.insert(entity)
+                                })
+                        })
+                    },
+                    "contact constraints of the same color must not share a dynamic body"
+                );
+
+                if color.len() <= MIN_BATCH_SIZE {
+                    solve_batch(color);
+                    continue;
+                }
+
+                let batch_size = color
+                    .len()
+                    .div_ceil(task_pool.thread_num())
+                    .max(MIN_BATCH_SIZE);
+
+                task_pool.scope(|scope| {
+                    for batch in color.chunks_mut(batch_size) {
+                        scope.spawn(async move { solve_batch(batch) });
+                    }
+                });
+            }
+
+            overflow
         }
+        None => constraints,
+    };
+
+    for constraint in constraints {
+        let Ok([mut body1, mut body2]) =
+            bodies.get_many_mut([constraint.entity1, constraint.entity2])
+        else {
+            continue;
+        };
+
+        f(constraint, &mut body1, &mut body2);
+    }
+}
+
+/// Returns the body with the given entity for a constraint of a [color](graph_coloring).
+///
+/// [Shared](ContactConstraintColors::is_shared) static and kinematic bodies are read from `shared_bodies`
+/// and copied into `copy`, so that the returned item doesn't alias the same body in other constraints
+/// of the color. Other bodies are accessed mutably.
+///
+/// # Safety
+///
+/// Unless the body is shared, it must not be accessed anywhere else while the returned item is alive.
+#[cfg(all(feature = "parallel", not(feature = "enhanced-determinism")))]
+unsafe fn get_colored_body<'a, 'w>(
+    bodies: &'a Query<RigidBodyQuery>,
+    shared_bodies: &'w Query<RigidBodyQueryReadOnly>,
+    colors: &ContactConstraintColors,
+    entity: Entity,
+    copy: &'a mut Option<RigidBodyCopy<'w>>,
+) -> Option<RigidBodyQueryItem<'a>> {
+    if colors.is_shared(entity) {
+        let body = shared_bodies.get(entity).ok()?;
+        Some(copy.insert(RigidBodyCopy::new(body)).as_item())
+    } else {
+        // SAFETY: The caller guarantees that the body is not accessed anywhere else.
+        unsafe { bodies.get_unchecked(entity).ok() }
     }
 }
 
@@ -670,6 +969,103 @@ fn apply_translation(
     }
 }
 
+/// Warm starts [joints](joints) solved by the [impulse-based joint solver](joints::impulse)
+/// by applying the impulses accumulated during the previous substep.
+///
+/// See [`SubstepSolverSet::WarmStart`] for more information.
+pub fn warm_start_joints<T: ImpulseJoint>(
+    mut commands: Commands,
+    mut bodies: Query<RigidBodyQuery>,
+    mut joints: Query<&mut T, (Without<RigidBody>, Without<JointDisabled>)>,
+    solver_config: Res<SolverConfig>,
+) {
+    for mut joint in &mut joints {
+        let Ok([mut body1, mut body2]) = bodies.get_many_mut(joint.entities()) else {
+            continue;
+        };
+
+        if !is_joint_active(&body1, &body2) {
+            continue;
+        }
+
+        // At least one of the bodies is active, so wake up any sleeping bodies.
+        for body in [&mut body1, &mut body2] {
+            body.time_sleeping.0 = 0.0;
+
+            if body.is_sleeping {
+                commands.entity(body.entity).remove::<Sleeping>();
+            }
+        }
+
+        joint.warm_start(&mut body1, &mut body2, solver_config.warm_start_coefficient);
+    }
+}
+
+/// Solves [joints](joints) using the [impulse-based joint solver](joints::impulse) with a position bias.
+///
+/// See [`SubstepSolverSet::SolveConstraints`] for more information.
+pub fn solve_joints<T: ImpulseJoint>(
+    bodies: Query<RigidBodyQuery>,
+    joints: Query<&mut T, (Without<RigidBody>, Without<JointDisabled>)>,
+    softness: Res<JointSoftnessCoefficients>,
+    time: Res<Time>,
+) {
+    solve_impulse_joints(
+        bodies,
+        joints,
+        softness.0,
+        time.delta_seconds_adjusted(),
+        true,
+    );
+}
+
+/// Solves [joints](joints) using the [impulse-based joint solver](joints::impulse) without a position bias
+/// to relax the biased velocities and impulses.
+///
+/// See [`SubstepSolverSet::Relax`] for more information.
+pub fn relax_joints<T: ImpulseJoint>(
+    bodies: Query<RigidBodyQuery>,
+    joints: Query<&mut T, (Without<RigidBody>, Without<JointDisabled>)>,
+    softness: Res<JointSoftnessCoefficients>,
+    time: Res<Time>,
+) {
+    solve_impulse_joints(
+        bodies,
+        joints,
+        softness.0,
+        time.delta_seconds_adjusted(),
+        false,
+    );
+}
+
+fn solve_impulse_joints<T: ImpulseJoint>(
+    mut bodies: Query<RigidBodyQuery>,
+    mut joints: Query<&mut T, (Without<RigidBody>, Without<JointDisabled>)>,
+    softness: SoftnessCoefficients,
+    delta_secs: Scalar,
+    use_bias: bool,
+) {
+    for mut joint in &mut joints {
+        let Ok([mut body1, mut body2]) = bodies.get_many_mut(joint.entities()) else {
+            continue;
+        };
+
+        if !is_joint_active(&body1, &body2) {
+            continue;
+        }
+
+        joint.solve_impulses(&mut body1, &mut body2, softness, delta_secs, use_bias);
+    }
+}
+
+/// Returns `true` if at least one of the bodies is dynamic and at least one of the bodies is awake.
+fn is_joint_active(body1: &RigidBodyQueryItem, body2: &RigidBodyQueryItem) -> bool {
+    let none_dynamic = !body1.rb.is_dynamic() && !body2.rb.is_dynamic();
+    let all_inactive =
+        (body1.rb.is_static() || body1.is_sleeping) && (body2.rb.is_static() || body2.is_sleeping);
+    !none_dynamic && !all_inactive
+}
+
 /// Applies velocity corrections caused by joint damping.
 #[allow(clippy::type_complexity)]
 pub fn joint_damping<T: Joint>(
@@ -683,7 +1079,7 @@ pub fn joint_damping<T: Joint>(
         ),
         Without<Sleeping>,
     >,
-    joints: Query<&T, Without<RigidBody>>,
+    joints: Query<&T, (Without<RigidBody>, Without<JointDisabled>)>,
     time: Res<Time>,
 ) {
     let delta_secs = time.delta_seconds_adjusted();
@@ -727,3 +1123,108 @@ pub fn joint_damping<T: Joint>(
         }
     }
 }
+
+/// Applies velocity corrections caused by [joint motors](JointMotor).
+pub fn joint_motors<T: Joint>(
+    mut commands: Commands,
+    mut bodies: Query<RigidBodyQuery>,
+    mut joints: Query<&mut T, (Without<RigidBody>, Without<JointDisabled>)>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for mut joint in &mut joints {
+        if let Ok([mut body1, mut body2]) = bodies.get_many_mut(joint.entities()) {
+            let none_dynamic = !body1.rb.is_dynamic() && !body2.rb.is_dynamic();
+            let all_inactive = [&body1, &body2]
+                .iter()
+                .all(|body| body.rb.is_static() || body.is_sleeping);
+
+            // No motor if none of the bodies is dynamic,
+            // or if all of the bodies are either static or sleeping
+            if none_dynamic || all_inactive {
+                continue;
+            }
+
+            // At least one of the bodies is active, so wake up any sleeping bodies
+            for body in [&mut body1, &mut body2] {
+                body.time_sleeping.0 = 0.0;
+
+                if body.is_sleeping {
+                    commands.entity(body.entity).remove::<Sleeping>();
+                }
+            }
+
+            joint.apply_motor(&mut body1, &mut body2, delta_secs);
+        }
+    }
+}
+
+/// Breaks [joints](joints) with a [`BreakableJoint`] component if the force or torque exerted
+/// by them exceeds the threshold, and sends [`JointBroken`] events.
+pub fn break_joints<T: Joint>(
+    mut commands: Commands,
+    joints: Query<(Entity, &T, &BreakableJoint), Without<JointDisabled>>,
+    mut joint_broken_events: EventWriter<JointBroken>,
+) {
+    for (entity, joint, breakable) in &joints {
+        let force = joint.force();
+        let torque = joint.torque();
+
+        if !breakable.is_exceeded_by(force, torque) {
+            continue;
+        }
+
+        match breakable.action {
+            JointBreakAction::Despawn => commands.entity(entity).despawn(),
+            JointBreakAction::RemoveJoint => {
+                commands.entity(entity).remove::<(T, BreakableJoint)>();
+            }
+        }
+
+        let [entity1, entity2] = joint.entities();
+        joint_broken_events.send(JointBroken {
+            joint_entity: entity,
+            entity1,
+            entity2,
+            force,
+            torque,
+        });
+    }
+}
+
+/// Resets the [`JointForces`] of [joints](joints) before the substepping loop.
+pub fn reset_joint_forces(mut joints: Query<&mut JointForces>) {
+    for mut joint_forces in &mut joints {
+        *joint_forces = JointForces::default();
+    }
+}
+
+/// Adds the force and torque exerted by [joints](joints) during the current substep
+/// to their [`JointForces`], averaged over the number of substeps.
+///
+/// The forces of [disabled](JointDisabled) joints are zero.
+pub fn accumulate_joint_forces<T: Joint>(
+    mut joints: Query<(&T, &mut JointForces), Without<JointDisabled>>,
+    substep_count: Res<SubstepCount>,
+) {
+    let substep_count = substep_count.0.max(1) as Scalar;
+
+    for (joint, mut joint_forces) in &mut joints {
+        joint_forces.force += joint.force() / substep_count;
+        joint_forces.torque += joint.torque() / substep_count;
+    }
+}
+
+/// Adds the bodies connected by [joints](joints) that have collisions disabled to the [`JointCollisionFilter`].
+pub fn update_joint_collision_filter<T: Joint>(
+    joints: Query<&T, Without<JointDisabled>>,
+    mut filter: ResMut<JointCollisionFilter>,
+) {
+    for joint in &joints {
+        if joint.collision_disabled() {
+            let [entity1, entity2] = joint.entities();
+            filter.insert(entity1, entity2);
+        }
+    }
+}
@@ -6,13 +6,16 @@
 //!
 #![doc = include_str!("README.md")]
 
-use bevy::reflect::Reflect;
+use bevy::prelude::*;
 
 use crate::{Scalar, TAU};
 
 /// Soft constraint tuning parameters used for dampening
 /// constraint response and controlling stiffness.
 #[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
 pub struct SoftnessParameters {
     /// 2x the damping ratio (zeta ζ). Controls the amount of oscillation.
     ///
@@ -113,6 +113,27 @@ pub trait AngularConstraint: XpbdConstraint<2> {
         lagrange: &mut Scalar,
         compliance: Scalar,
         dt: Scalar,
+    ) -> Torque {
+        self.align_orientation_with_softness(body1, body2, angle, lagrange, compliance, None, dt)
+    }
+
+    /// Applies an angular correction that aligns the orientation of the bodies.
+    ///
+    /// If `softness` is set, the correction is applied like a damped spring
+    /// with the given [`SoftnessParameters`], and the `compliance` is ignored.
+    ///
+    /// Returns the torque exerted by the alignment.
+    #[cfg(feature = "2d")]
+    #[allow(clippy::too_many_arguments)]
+    fn align_orientation_with_softness(
+        &self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        angle: Scalar,
+        lagrange: &mut Scalar,
+        compliance: Scalar,
+        softness: Option<SoftnessParameters>,
+        dt: Scalar,
     ) -> Torque {
         if angle.abs() <= Scalar::EPSILON {
             return Torque::ZERO;
@@ -123,7 +144,12 @@ pub trait AngularConstraint: XpbdConstraint<2> {
         let w = [w1, w2];
 
         // Compute Lagrange multiplier update
-        let delta_lagrange = self.compute_lagrange_update(*lagrange, angle, &w, compliance, dt);
+        let delta_lagrange = if let Some(softness) = softness {
+            let angle_dot = body2.angular_velocity.0 - body1.angular_velocity.0;
+            self.compute_soft_lagrange_update(*lagrange, angle, angle_dot, &w, softness, dt)
+        } else {
+            self.compute_lagrange_update(*lagrange, angle, &w, compliance, dt)
+        };
         *lagrange += delta_lagrange;
 
         // Apply angular correction to aling the bodies
@@ -145,6 +171,35 @@ pub trait AngularConstraint: XpbdConstraint<2> {
         lagrange: &mut Scalar,
         compliance: Scalar,
         dt: Scalar,
+    ) -> Torque {
+        self.align_orientation_with_softness(
+            body1,
+            body2,
+            rotation_difference,
+            lagrange,
+            compliance,
+            None,
+            dt,
+        )
+    }
+
+    /// Applies an angular correction that aligns the orientation of the bodies.
+    ///
+    /// If `softness` is set, the correction is applied like a damped spring
+    /// with the given [`SoftnessParameters`], and the `compliance` is ignored.
+    ///
+    /// Returns the torque exerted by the alignment.
+    #[cfg(feature = "3d")]
+    #[allow(clippy::too_many_arguments)]
+    fn align_orientation_with_softness(
+        &self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        rotation_difference: Vector,
+        lagrange: &mut Scalar,
+        compliance: Scalar,
+        softness: Option<SoftnessParameters>,
+        dt: Scalar,
     ) -> Torque {
         let angle = rotation_difference.length();
 
@@ -160,7 +215,12 @@ pub trait AngularConstraint: XpbdConstraint<2> {
         let w = [w1, w2];
 
         // Compute Lagrange multiplier update
-        let delta_lagrange = self.compute_lagrange_update(*lagrange, angle, &w, compliance, dt);
+        let delta_lagrange = if let Some(softness) = softness {
+            let angle_dot = axis.dot(body2.angular_velocity.0 - body1.angular_velocity.0);
+            self.compute_soft_lagrange_update(*lagrange, angle, angle_dot, &w, softness, dt)
+        } else {
+            self.compute_lagrange_update(*lagrange, angle, &w, compliance, dt)
+        };
         *lagrange += delta_lagrange;
 
         // Apply angular correction to aling the bodies
@@ -32,6 +32,7 @@
 #![cfg_attr(feature = "3d", doc = "    - [`SphericalJoint`]")]
 //!     - [`RevoluteJoint`]
 //!     - [`PrismaticJoint`]
+//!     - [`GenericJoint`]
 //!
 //! Avian's [`ContactConstraint`](dynamics::solver::contact::ContactConstraint)
 //! is impulse-based instead.
@@ -319,6 +320,49 @@ pub trait XpbdConstraint<const ENTITY_COUNT: usize>: MapEntities {
         (-c - tilde_compliance * lagrange) / (w_sum + tilde_compliance)
     }
 
+    /// Computes how much a constraint's [Lagrange multiplier](self#lagrange-multipliers) changes when projecting
+    /// a soft constraint with the given [`SoftnessParameters`] for all participating particles.
+    /// The constraint gradients are assumed to be unit-length.
+    ///
+    /// `c` is a scalar value returned by the [constraint function](self#constraint-functions),
+    /// and `c_dot` is its rate of change based on the velocities of the particles.
+    ///
+    /// The compliance is scaled by the sum of the inverse masses so that the constraint oscillates
+    /// at the configured frequency regardless of the masses, and the damping ratio is applied
+    /// using the damping term from the XPBD paper.
+    fn compute_soft_lagrange_update(
+        &self,
+        lagrange: Scalar,
+        c: Scalar,
+        c_dot: Scalar,
+        inverse_masses: &[Scalar],
+        softness: SoftnessParameters,
+        dt: Scalar,
+    ) -> Scalar {
+        // Compute the sum of all inverse masses.
+        // The gradients are unit length, so they don't need to be considered.
+        let w_sum: Scalar = inverse_masses.iter().copied().sum();
+
+        // The frequency multiplied by the time step.
+        let omega_h = softness.angular_frequency() * dt;
+
+        // Avoid division by zero. A spring with a frequency of zero has no stiffness.
+        if w_sum <= Scalar::EPSILON || omega_h <= Scalar::EPSILON {
+            return 0.0;
+        }
+
+        // The stiffness of the spring is k = m * ω^2, so the compliance is a = w / ω^2,
+        // and tilde_a = a/h^2
+        let tilde_compliance = w_sum / omega_h.powi(2);
+
+        // With the damping coefficient b = 2 * ζ * m * ω, gamma = a * b / h = 2 * ζ / (ω * h) (Eq. 26)
+        let gamma = softness.damping_ratio() * 2.0 / omega_h;
+
+        // The change of `c` during the time step is approximated as `c_dot * dt`.
+        (-c - tilde_compliance * lagrange - gamma * c_dot * dt)
+            / ((1.0 + gamma) * w_sum + tilde_compliance)
+    }
+
     /// Sets the constraint's [Lagrange multipliers](self#lagrange-multipliers) to 0.
     fn clear_lagrange_multipliers(&mut self);
 }
@@ -351,7 +395,7 @@ pub trait XpbdConstraint<const ENTITY_COUNT: usize>: MapEntities {
 pub fn solve_constraint<C: XpbdConstraint<ENTITY_COUNT> + Component, const ENTITY_COUNT: usize>(
     mut commands: Commands,
     mut bodies: Query<RigidBodyQuery>,
-    mut constraints: Query<&mut C, Without<RigidBody>>,
+    mut constraints: Query<&mut C, (Without<RigidBody>, Without<JointDisabled>)>,
     time: Res<Time>,
 ) {
     let delta_secs = time.delta_seconds_adjusted();
@@ -0,0 +1,610 @@
+//! Vehicles with raycast wheels.
+//!
+//! A [`Wheel`] is attached to a dynamic chassis [`RigidBody`] at a local [anchor](Wheel::anchor).
+//! Instead of simulating the wheel as a separate body, a ray is cast along the suspension each substep
+//! to find the ground, and the suspension, drive, braking and friction forces are applied to the chassis
+//! at the contact point. This is cheaper and much more stable than using jointed wheel bodies,
+//! and it is the approach used by most games.
+//!
+//! Each wheel has:
+//!
+//! - A spring-damper suspension with a [rest length](Wheel::rest_length),
+//!   [stiffness](Wheel::stiffness) and [damping](Wheel::damping)
+//! - A [drive torque](Wheel::drive_torque) and [brake torque](Wheel::brake_torque)
+//!   that change the spin of the wheel
+#![cfg_attr(
+    feature = "3d",
+    doc = "- A [steering angle](Wheel::steering_angle) around the suspension axis"
+)]
+//! - Friction computed from [slip curves](SlipCurve) that map the slip of the wheel to a friction coefficient
+//!
+//! The wheel entity is separate from the chassis, so it can also be used for rendering the wheel.
+//! The current state of the wheel, like its contact with the ground, suspension length and spin,
+//! is stored in the [`WheelState`] component that is added automatically.
+//!
+//! ## Example
+//!
+//! ```
+#![cfg_attr(feature = "2d", doc = "use avian2d::{math::*, prelude::*};")]
+#![cfg_attr(feature = "3d", doc = "use avian3d::{math::*, prelude::*};")]
+//! use bevy::prelude::*;
+//!
+//! fn setup(mut commands: Commands) {
+//!     let chassis = commands
+//!         .spawn((
+//!             RigidBody::Dynamic,
+#![cfg_attr(feature = "2d", doc = "            Collider::rectangle(4.0, 1.0),")]
+#![cfg_attr(feature = "3d", doc = "            Collider::cuboid(2.0, 1.0, 4.0),")]
+//!         ))
+//!         .id();
+//!
+//!     // Spawn the wheels.
+#![cfg_attr(
+    feature = "2d",
+    doc = "    for x in [-1.5, 1.5] {
+        commands.spawn(Wheel::new(chassis, Vector::new(x, -0.5), 0.4));
+    }"
+)]
+#![cfg_attr(
+    feature = "3d",
+    doc = "    for (x, z) in [(-1.0, -1.5), (1.0, -1.5), (-1.0, 1.5), (1.0, 1.5)] {
+        commands.spawn(Wheel::new(chassis, Vector::new(x, -0.5, z), 0.4));
+    }"
+)]
+//! }
+//!
+//! fn drive(keyboard_input: Res<ButtonInput<KeyCode>>, mut wheels: Query<&mut Wheel>) {
+//!     for mut wheel in &mut wheels {
+//!         wheel.drive_torque = if keyboard_input.pressed(KeyCode::KeyW) { 50.0 } else { 0.0 };
+//!         wheel.brake_torque = if keyboard_input.pressed(KeyCode::Space) { 100.0 } else { 0.0 };
+//!     }
+//! }
+//! ```
+
+use crate::{
+    dynamics::{integrator::IntegrationSet, solver::SubstepSolverSet},
+    prelude::*,
+    prepare::PrepareSet,
+};
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        intern::Interned,
+        reflect::ReflectMapEntities,
+        schedule::ScheduleLabel,
+    },
+    prelude::*,
+};
+
+/// A plugin for simulating [vehicles](self) with raycast [wheels](Wheel).
+pub struct VehiclePlugin {
+    schedule: Interned<dyn ScheduleLabel>,
+}
+
+impl VehiclePlugin {
+    /// Creates a [`VehiclePlugin`] with the schedule that is used for running the [`PhysicsSchedule`].
+    ///
+    /// The default schedule is `PostUpdate`.
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+        }
+    }
+}
+
+impl Default for VehiclePlugin {
+    fn default() -> Self {
+        Self::new(PostUpdate)
+    }
+}
+
+impl Plugin for VehiclePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Wheel>()
+            .register_type::<WheelState>()
+            .register_type::<WheelContact>()
+            .register_type::<SlipCurve>();
+
+        app.add_systems(self.schedule, init_wheels.in_set(PrepareSet::PreInit));
+
+        // Get the `PhysicsSchedule`, and panic if it doesn't exist.
+        let physics = app
+            .get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first");
+
+        physics.add_systems(wake_driven_vehicles.in_set(PhysicsStepSet::First));
+
+        // Get the `SubstepSchedule`, and panic if it doesn't exist.
+        let substeps = app
+            .get_schedule_mut(SubstepSchedule)
+            .expect("add SubstepSchedule first");
+
+        substeps.add_systems(
+            apply_wheel_forces
+                .after(IntegrationSet::Velocity)
+                .before(SubstepSolverSet::WarmStart),
+        );
+    }
+}
+
+/// A raycast wheel attached to a chassis [`RigidBody`]. See the [module-level documentation](self).
+///
+/// The [`WheelState`] component is added automatically.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, MapEntities, PartialEq)]
+pub struct Wheel {
+    /// The dynamic rigid body that the wheel is attached to.
+    pub chassis: Entity,
+    /// The attachment point of the suspension in the local space of the chassis.
+    pub anchor: Vector,
+    /// The direction in which the suspension extends in the local space of the chassis.
+    ///
+    /// The negative y-axis by default.
+    pub suspension_direction: Vector,
+    /// The forward direction of the wheel in the local space of the chassis,
+    /// before [steering](Self::steering_angle) is applied.
+    ///
+    #[cfg_attr(feature = "2d", doc = "The x-axis by default.")]
+    #[cfg_attr(feature = "3d", doc = "The negative z-axis by default.")]
+    pub forward: Vector,
+    /// The radius of the wheel.
+    pub radius: Scalar,
+    /// The length of the suspension when it is not compressed.
+    ///
+    /// `0.3` by default.
+    pub rest_length: Scalar,
+    /// The stiffness of the suspension spring, the force per unit of compression.
+    ///
+    /// `100.0` by default.
+    pub stiffness: Scalar,
+    /// The damping of the suspension, the force per unit of compression speed.
+    ///
+    /// `10.0` by default.
+    pub damping: Scalar,
+    /// The moment of inertia of the wheel around its axle.
+    ///
+    /// `1.0` by default.
+    pub inertia: Scalar,
+    /// The friction curve in the forward direction of the wheel, mapping the slip ratio to a friction coefficient.
+    pub longitudinal_friction: SlipCurve,
+    /// The friction curve in the sideways direction of the wheel, mapping the slip angle in radians
+    /// to a friction coefficient.
+    #[cfg(feature = "3d")]
+    pub lateral_friction: SlipCurve,
+    /// The steering angle of the wheel in radians around the suspension axis.
+    #[cfg(feature = "3d")]
+    pub steering_angle: Scalar,
+    /// The torque spinning the wheel forward. Negative values spin it backwards.
+    pub drive_torque: Scalar,
+    /// The torque slowing down the spin of the wheel.
+    pub brake_torque: Scalar,
+}
+
+impl Wheel {
+    /// Creates a new [`Wheel`] with the given radius attached to the `chassis` at the given local `anchor`.
+    pub fn new(chassis: Entity, anchor: Vector, radius: Scalar) -> Self {
+        Self {
+            chassis,
+            anchor,
+            suspension_direction: Vector::NEG_Y,
+            #[cfg(feature = "2d")]
+            forward: Vector::X,
+            #[cfg(feature = "3d")]
+            forward: Vector::NEG_Z,
+            radius,
+            rest_length: 0.3,
+            stiffness: 100.0,
+            damping: 10.0,
+            inertia: 1.0,
+            longitudinal_friction: SlipCurve::new(0.2, 1.0, 0.8, 0.75),
+            #[cfg(feature = "3d")]
+            lateral_friction: SlipCurve::new(0.2, 1.0, 0.5, 0.75),
+            #[cfg(feature = "3d")]
+            steering_angle: 0.0,
+            drive_torque: 0.0,
+            brake_torque: 0.0,
+        }
+    }
+
+    /// Sets the direction in which the suspension extends in the local space of the chassis.
+    pub fn with_suspension_direction(mut self, direction: Vector) -> Self {
+        self.suspension_direction = direction;
+        self
+    }
+
+    /// Sets the forward direction of the wheel in the local space of the chassis.
+    pub fn with_forward(mut self, forward: Vector) -> Self {
+        self.forward = forward;
+        self
+    }
+
+    /// Sets the rest length, stiffness and damping of the suspension.
+    pub fn with_suspension(
+        mut self,
+        rest_length: Scalar,
+        stiffness: Scalar,
+        damping: Scalar,
+    ) -> Self {
+        self.rest_length = rest_length;
+        self.stiffness = stiffness;
+        self.damping = damping;
+        self
+    }
+
+    /// Sets the moment of inertia of the wheel around its axle.
+    pub fn with_inertia(mut self, inertia: Scalar) -> Self {
+        self.inertia = inertia;
+        self
+    }
+
+    /// Sets the friction curve in the forward direction of the wheel.
+    pub fn with_longitudinal_friction(mut self, curve: SlipCurve) -> Self {
+        self.longitudinal_friction = curve;
+        self
+    }
+
+    /// Sets the friction curve in the sideways direction of the wheel.
+    #[cfg(feature = "3d")]
+    pub fn with_lateral_friction(mut self, curve: SlipCurve) -> Self {
+        self.lateral_friction = curve;
+        self
+    }
+}
+
+impl MapEntities for Wheel {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.chassis = entity_mapper.map_entity(self.chassis);
+    }
+}
+
+/// A friction curve mapping the slip of a [`Wheel`] to a friction coefficient.
+///
+/// The coefficient rises linearly from zero to the [extremum](Self::extremum_value) at the
+/// [extremum slip](Self::extremum_slip), and then changes linearly to the [asymptote](Self::asymptote_value)
+/// at the [asymptote slip](Self::asymptote_slip), staying constant after that. The coefficient is multiplied
+/// by the suspension force to get the maximum friction force.
+///
+/// The peak at the extremum models how tires grip best at a small amount of slip,
+/// and lose some of their grip when they start skidding.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct SlipCurve {
+    /// The slip at which the friction coefficient is highest.
+    pub extremum_slip: Scalar,
+    /// The highest friction coefficient.
+    pub extremum_value: Scalar,
+    /// The slip after which the friction coefficient stays constant.
+    pub asymptote_slip: Scalar,
+    /// The friction coefficient when the wheel is skidding.
+    pub asymptote_value: Scalar,
+}
+
+impl SlipCurve {
+    /// Creates a new [`SlipCurve`] with the given extremum and asymptote points.
+    pub const fn new(
+        extremum_slip: Scalar,
+        extremum_value: Scalar,
+        asymptote_slip: Scalar,
+        asymptote_value: Scalar,
+    ) -> Self {
+        Self {
+            extremum_slip,
+            extremum_value,
+            asymptote_slip,
+            asymptote_value,
+        }
+    }
+
+    /// Evaluates the friction coefficient at the given slip. The sign of the slip is ignored.
+    pub fn evaluate(&self, slip: Scalar) -> Scalar {
+        let slip = slip.abs();
+        if slip < self.extremum_slip {
+            self.extremum_value * slip / self.extremum_slip
+        } else if slip < self.asymptote_slip {
+            let t = (slip - self.extremum_slip) / (self.asymptote_slip - self.extremum_slip);
+            self.extremum_value + (self.asymptote_value - self.extremum_value) * t
+        } else {
+            self.asymptote_value
+        }
+    }
+}
+
+/// The current state of a [`Wheel`], updated every substep.
+#[derive(Component, Clone, Debug, Default, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub struct WheelState {
+    /// The contact between the wheel and the ground, if the wheel is touching the ground.
+    pub contact: Option<WheelContact>,
+    /// The current length of the suspension.
+    pub suspension_length: Scalar,
+    /// The force applied by the suspension along the suspension axis.
+    pub suspension_force: Scalar,
+    /// The friction force applied in the forward direction of the wheel.
+    pub longitudinal_force: Scalar,
+    /// The friction force applied in the sideways direction of the wheel.
+    #[cfg(feature = "3d")]
+    pub lateral_force: Scalar,
+    /// The longitudinal slip ratio of the wheel.
+    pub longitudinal_slip: Scalar,
+    /// The lateral slip angle of the wheel in radians.
+    #[cfg(feature = "3d")]
+    pub lateral_slip: Scalar,
+    /// The angular velocity of the wheel around its axle. Positive values roll the wheel forward.
+    pub angular_velocity: Scalar,
+    /// The rotation angle of the wheel around its axle in radians, in the range `[-π, π]`.
+    pub rotation: Scalar,
+}
+
+/// Information about the contact between a [`Wheel`] and the ground.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, PartialEq)]
+pub struct WheelContact {
+    /// The collider entity that the wheel is touching.
+    pub entity: Entity,
+    /// The contact point in world space.
+    pub point: Vector,
+    /// The surface normal of the ground at the contact point in world space.
+    pub normal: Vector,
+}
+
+/// Adds [`WheelState`] to new [wheels](Wheel).
+fn init_wheels(mut commands: Commands, wheels: Query<Entity, (Added<Wheel>, Without<WheelState>)>) {
+    for entity in &wheels {
+        commands.entity(entity).insert(WheelState::default());
+    }
+}
+
+/// Wakes up sleeping chassis bodies when their [wheels](Wheel) are changed, for example to apply a drive torque.
+fn wake_driven_vehicles(
+    mut commands: Commands,
+    wheels: Query<&Wheel, Changed<Wheel>>,
+    mut bodies: Query<&mut TimeSleeping, With<Sleeping>>,
+) {
+    for wheel in &wheels {
+        if let Ok(mut time_sleeping) = bodies.get_mut(wheel.chassis) {
+            commands.entity(wheel.chassis).remove::<Sleeping>();
+            time_sleeping.0 = 0.0;
+        }
+    }
+}
+
+/// Casts the suspension rays of [wheels](Wheel) and applies the suspension and friction impulses
+/// to the chassis and the ground.
+#[allow(clippy::type_complexity)]
+fn apply_wheel_forces(
+    mut wheels: Query<(&Wheel, &mut WheelState)>,
+    // The spatial query reads the positions of bodies, so it can't be used while the bodies are borrowed.
+    mut params: ParamSet<(Query<RigidBodyQuery>, SpatialQuery)>,
+    collider_parents: Query<&ColliderParent>,
+    sensors: Query<(), With<Sensor>>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    if delta_secs == 0.0 {
+        return;
+    }
+
+    for (wheel, mut state) in &mut wheels {
+        let (origin, down) = {
+            let bodies = params.p0();
+            let Ok(chassis) = bodies.get(wheel.chassis) else {
+                continue;
+            };
+            if !chassis.rb.is_dynamic() || chassis.is_sleeping {
+                continue;
+            }
+            let rotation = *chassis.rotation;
+            (
+                chassis.current_position() + rotation * wheel.anchor,
+                (rotation * wheel.suspension_direction).normalize_or_zero(),
+            )
+        };
+        let up = -down;
+
+        // Find the ground below the wheel, ignoring sensors and the colliders of the chassis.
+        let hit = params.p1().cast_ray_predicate(
+            origin,
+            Dir::new_unchecked(down.f32()),
+            wheel.rest_length + wheel.radius,
+            true,
+            SpatialQueryFilter::default(),
+            &|entity| {
+                !sensors.contains(entity)
+                    && collider_parents
+                        .get(entity)
+                        .map_or(true, |parent| parent.get() != wheel.chassis)
+            },
+        );
+
+        let Some(hit) = hit else {
+            // The wheel is in the air and spins freely.
+            spin_wheel(wheel, &mut state, delta_secs);
+            state.rotation = wrap_angle(state.rotation + state.angular_velocity * delta_secs);
+            state.contact = None;
+            state.suspension_length = wheel.rest_length;
+            state.suspension_force = 0.0;
+            state.longitudinal_force = 0.0;
+            state.longitudinal_slip = 0.0;
+            #[cfg(feature = "3d")]
+            {
+                state.lateral_force = 0.0;
+                state.lateral_slip = 0.0;
+            }
+            continue;
+        };
+
+        let mut bodies = params.p0();
+        let Ok(chassis) = bodies.get(wheel.chassis) else {
+            continue;
+        };
+        let rotation = *chassis.rotation;
+        let center_of_mass = chassis.current_position() + rotation * chassis.center_of_mass.0;
+
+        let point = origin + down * hit.time_of_impact;
+        let normal = hit.normal;
+        let r = point - center_of_mass;
+        let chassis_velocity = chassis.velocity_at_point(r);
+
+        // The ground can be moving, like a moving platform or another vehicle.
+        let ground_entity = collider_parents
+            .get(hit.entity)
+            .map(|parent| parent.get())
+            .ok();
+        let ground_velocity = ground_entity
+            .and_then(|entity| bodies.get(entity).ok())
+            .map_or(Vector::ZERO, |ground| {
+                let ground_center_of_mass =
+                    ground.current_position() + *ground.rotation * ground.center_of_mass.0;
+                ground.velocity_at_point(point - ground_center_of_mass)
+            });
+        let relative_velocity = chassis_velocity - ground_velocity;
+
+        // Compute the suspension force with a spring-damper.
+        let suspension_length = (hit.time_of_impact - wheel.radius).clamp(0.0, wheel.rest_length);
+        let compression = wheel.rest_length - suspension_length;
+        let suspension_force =
+            (wheel.stiffness * compression - wheel.damping * relative_velocity.dot(up)).max(0.0);
+        let max_friction_impulse = suspension_force * delta_secs;
+        let mut impulse = up * max_friction_impulse;
+
+        // Compute the forward and sideways directions of the wheel along the ground.
+        #[cfg(feature = "2d")]
+        let forward = rotation * wheel.forward;
+        #[cfg(feature = "3d")]
+        let forward = rotation
+            * (Quaternion::from_axis_angle(
+                -wheel.suspension_direction.normalize_or_zero(),
+                wheel.steering_angle,
+            ) * wheel.forward);
+        let forward = (forward - normal * forward.dot(normal)).normalize_or_zero();
+
+        // Spin the wheel with the drive and brake torques.
+        spin_wheel(wheel, &mut state, delta_secs);
+
+        // Apply longitudinal friction based on the difference between the speed of the wheel's surface
+        // and the speed of the ground. The impulse is clamped so that it doesn't overshoot the slip.
+        let forward_speed = relative_velocity.dot(forward);
+        let surface_speed = state.angular_velocity * wheel.radius;
+        let slip_speed = surface_speed - forward_speed;
+        let slip_ratio = slip_speed
+            / forward_speed
+                .abs()
+                .max(surface_speed.abs())
+                .max(Scalar::EPSILON);
+        let inverse_mass = generalized_inverse_mass(&chassis, r, forward)
+            + wheel.radius * wheel.radius / wheel.inertia;
+        let longitudinal_impulse = (wheel.longitudinal_friction.evaluate(slip_ratio)
+            * max_friction_impulse)
+            .min(slip_speed.abs() / inverse_mass)
+            * slip_speed.signum();
+        state.angular_velocity -= longitudinal_impulse * wheel.radius / wheel.inertia;
+        impulse += forward * longitudinal_impulse;
+
+        // Apply lateral friction based on the slip angle.
+        #[cfg(feature = "3d")]
+        {
+            let side = normal.cross(forward);
+            let side_speed = relative_velocity.dot(side);
+            let slip_angle = side_speed.atan2(forward_speed.abs());
+            let inverse_mass = generalized_inverse_mass(&chassis, r, side);
+            let lateral_impulse = -(wheel.lateral_friction.evaluate(slip_angle)
+                * max_friction_impulse)
+                .min(side_speed.abs() / inverse_mass)
+                * side_speed.signum();
+            impulse += side * lateral_impulse;
+
+            state.lateral_force = lateral_impulse / delta_secs;
+            state.lateral_slip = slip_angle;
+        }
+
+        state.rotation = wrap_angle(state.rotation + state.angular_velocity * delta_secs);
+        state.contact = Some(WheelContact {
+            entity: hit.entity,
+            point,
+            normal,
+        });
+        state.suspension_length = suspension_length;
+        state.suspension_force = suspension_force;
+        state.longitudinal_force = longitudinal_impulse / delta_secs;
+        state.longitudinal_slip = slip_ratio;
+
+        // Apply the impulse to the chassis, and the opposite impulse to the ground.
+        if let Ok(mut chassis) = bodies.get_mut(wheel.chassis) {
+            apply_impulse_at_point(&mut chassis, impulse, r);
+        }
+        if let Some(mut ground) = ground_entity
+            .filter(|&entity| entity != wheel.chassis)
+            .and_then(|entity| bodies.get_mut(entity).ok())
+        {
+            if ground.rb.is_dynamic() && !ground.is_sleeping {
+                let ground_center_of_mass =
+                    ground.current_position() + *ground.rotation * ground.center_of_mass.0;
+                apply_impulse_at_point(&mut ground, -impulse, point - ground_center_of_mass);
+            }
+        }
+    }
+}
+
+/// Applies the drive and brake torques of the wheel to its angular velocity.
+fn spin_wheel(wheel: &Wheel, state: &mut WheelState, delta_secs: Scalar) {
+    state.angular_velocity += wheel.drive_torque / wheel.inertia * delta_secs;
+
+    // Braking slows the wheel down, but never reverses its spin.
+    let brake = wheel.brake_torque.abs() / wheel.inertia * delta_secs;
+    state.angular_velocity = if state.angular_velocity.abs() <= brake {
+        0.0
+    } else {
+        state.angular_velocity - brake * state.angular_velocity.signum()
+    };
+}
+
+/// Computes the inverse of the mass felt by an impulse along `direction` at the offset `r`
+/// from the center of mass of the body.
+fn generalized_inverse_mass(
+    body: &RigidBodyQueryReadOnlyItem,
+    r: Vector,
+    direction: Vector,
+) -> Scalar {
+    let inverse_mass = body.effective_inv_mass().dot(direction * direction);
+
+    #[cfg(feature = "2d")]
+    {
+        let r_cross_dir = r.perp_dot(direction);
+        inverse_mass + body.effective_world_inv_inertia() * r_cross_dir * r_cross_dir
+    }
+    #[cfg(feature = "3d")]
+    {
+        let r_cross_dir = r.cross(direction);
+        inverse_mass + r_cross_dir.dot(body.effective_world_inv_inertia() * r_cross_dir)
+    }
+}
+
+/// Applies an `impulse` at the offset `r` from the center of mass of the body.
+fn apply_impulse_at_point(body: &mut RigidBodyQueryItem, impulse: Vector, r: Vector) {
+    let inverse_mass = body.effective_inv_mass();
+    let inverse_inertia = body.effective_world_inv_inertia();
+
+    body.linear_velocity.0 += impulse * inverse_mass;
+    #[cfg(feature = "2d")]
+    {
+        body.angular_velocity.0 += inverse_inertia * r.perp_dot(impulse);
+    }
+    #[cfg(feature = "3d")]
+    {
+        body.angular_velocity.0 += inverse_inertia * r.cross(impulse);
+    }
+}
+
+/// Wraps the angle to the range `[-π, π]`.
+fn wrap_angle(angle: Scalar) -> Scalar {
+    (angle + PI).rem_euclid(2.0 * PI) - PI
+}
@@ -62,7 +62,8 @@
 //! | `enhanced-determinism` | Enables increased determinism.                                                                                                   | No                      |
 //! | `parallel`             | Enables some extra multithreading, which improves performance for larger simulations but can add some overhead for smaller ones. | Yes                     |
 //! | `simd`                 | Enables [SIMD] optimizations.                                                                                                    | No                      |
-//! | `serialize`            | Enables support for serialization and deserialization using Serde.                                                               | No                      |
+//! | `physics-material`     | Enables [`PhysicsMaterial`] assets that can be shared by colliders. Enables Bevy's `bevy_asset` feature.                          | Yes                     |
+//! | `serialize`            | Enables support for serialization and deserialization using Serde, and loading [`PhysicsMaterial`]s from RON files.              | No                      |
 //!
 //! [SIMD]: https://en.wikipedia.org/wiki/Single_instruction,_multiple_data
 //!
@@ -123,6 +124,7 @@
 //!     - [Linear](LinearVelocity) and [angular](AngularVelocity) velocity
 //!     - [Forces](ExternalForce), [torque](ExternalTorque), and [linear](ExternalImpulse) and [angular](ExternalAngularImpulse) impulses
 //! - [Gravity] and [gravity scale](GravityScale)
+//! - [Gravity fields](dynamics::integrator::gravity_fields): [radial gravity](RadialGravity), [gravity zones](GravityZone) and [per-body overrides](GravityOverride)
 //! - [Mass properties](RigidBody#mass-properties)
 //! - [Linear](LinearDamping) and [angular](AngularDamping) velocity damping
 //! - [Lock translational and rotational axes](LockedAxes)
@@ -140,6 +142,8 @@
 //!     - [Creation](Collider#creation)
 //!     - [Density](ColliderDensity)
 //!     - [Friction] and [restitution](Restitution) (bounciness)
+//!     - [Physics materials](PhysicsMaterial)
+//!     - [Anisotropic friction](AnisotropicFriction) and [surface velocity](SurfaceVelocity) (conveyor belts)
 //!     - [Collision layers](CollisionLayers)
 //!     - [Sensors](Sensor)
 #![cfg_attr(
@@ -161,9 +165,25 @@
 //!     - [Prismatic joint](PrismaticJoint)
 //!     - [Revolute joint](RevoluteJoint)
 #![cfg_attr(feature = "3d", doc = "    - [Spherical joint](SphericalJoint)")]
+//!     - [Generic joint](GenericJoint)
+//!     - [Joint motors](JointMotor)
+//! - [Ragdolls](dynamics::ragdoll)
 //! - [Custom XPBD constraints](dynamics::solver::xpbd#constraints) (advanced)
 //!
-//! Joint motors and articulations are not supported yet, but they will be implemented in a future release.
+//! Articulations are not supported yet, but they will be implemented in a future release.
+//!
+//! ### Character controllers
+//!
+//! - [Kinematic character controller](dynamics::character_controller)
+//!
+//! ### Vehicles
+//!
+//! - [Raycast wheels](dynamics::vehicle)
+//!
+//! ### Fluids
+//!
+//! - [Buoyancy and drag](dynamics::buoyancy)
+//! - [Aerodynamic drag and lift](dynamics::aerodynamics)
 //!
 //! ### Spatial queries
 //!
@@ -180,6 +200,7 @@
 //! - [Gravity]
 //! - [Physics timestep](Physics#usage)
 //! - [Physics speed](Physics#physics-speed)
+//! - [Transform interpolation and extrapolation](PhysicsInterpolationPlugin)
 //! - [Configure simulation fidelity with substeps](SubstepCount)
 //! - [Render physics objects for debugging](PhysicsDebugPlugin)
 //!
@@ -470,6 +491,10 @@ pub use type_registration::PhysicsTypeRegistrationPlugin;
 
 /// Re-exports common components, bundles, resources, plugins and types.
 pub mod prelude {
+    #[cfg(feature = "physics-material")]
+    pub use crate::collision::physics_material::{
+        PhysicsMaterial, PhysicsMaterialPair, PhysicsMaterialPairs, PhysicsMaterialPlugin,
+    };
     #[cfg(feature = "debug-plugin")]
     pub use crate::debug_render::*;
     #[cfg(feature = "default-collider")]
@@ -477,11 +502,15 @@ pub mod prelude {
     pub use crate::{
         collision::{
             self,
-            broad_phase::{BroadCollisionPairs, BroadPhasePlugin},
+            broad_phase::{
+                BroadCollisionPairs, BroadPhaseConfig, BroadPhaseMethod, BroadPhasePlugin,
+                ColliderTree, ColliderTreeProxy,
+            },
             collider::{ColliderBackendPlugin, ColliderHierarchyPlugin},
             contact_reporting::{
                 Collision, CollisionEnded, CollisionStarted, ContactReportingPlugin,
             },
+            hooks::{ActiveCollisionHooks, CollisionHooks},
             narrow_phase::{NarrowPhaseConfig, NarrowPhasePlugin},
             *,
         },
@@ -490,9 +519,14 @@ pub mod prelude {
         prepare::{init_transforms, update_mass_properties, PrepareConfig, PreparePlugin},
         schedule::*,
         spatial_query::{self, *},
-        sync::SyncPlugin,
+        sync::{
+            interpolation::{
+                PhysicsInterpolationPlugin, TransformExtrapolation, TransformInterpolation,
+            },
+            SyncPlugin,
+        },
         type_registration::PhysicsTypeRegistrationPlugin,
-        PhysicsPlugins,
+        PhysicsPlugins, PhysicsPluginsWithHooks,
     };
     pub(crate) use crate::{
         math::*,
@@ -506,8 +540,12 @@ mod utils;
 #[cfg(test)]
 mod tests;
 
+use std::marker::PhantomData;
+
 use bevy::{
-    app::PluginGroupBuilder, ecs::intern::Interned, ecs::schedule::ScheduleLabel, prelude::*,
+    app::PluginGroupBuilder,
+    ecs::{intern::Interned, schedule::ScheduleLabel, system::SystemParamItem},
+    prelude::*,
 };
 #[allow(unused_imports)]
 use prelude::*;
@@ -525,13 +563,22 @@ use prelude::*;
 /// | [`ColliderHierarchyPlugin`]       | Handles transform propagation and [`ColliderParent`] updates for colliders.                                                                                |
 /// | [`BroadPhasePlugin`]              | Collects pairs of potentially colliding entities into [`BroadCollisionPairs`] using [AABB](ColliderAabb) intersection checks.                              |
 /// | [`NarrowPhasePlugin`]             | Computes contacts between entities and sends collision events.                                                                                             |
+/// | [`PhysicsMaterialPlugin`]         | Registers [`PhysicsMaterial`] assets and keeps the density of colliders in sync with their materials.                                                      |
 /// | [`ContactReportingPlugin`]        | Sends collision events and updates [`CollidingEntities`].                                                                                                  |
 /// | [`IntegratorPlugin`]              | Handles motion caused by velocity, and applies external forces and gravity.                                                                                |
+/// | [`GravityFieldPlugin`]            | Computes the [`LocalGravity`] of bodies affected by [gravity fields](dynamics::integrator::gravity_fields).                                                |
+/// | [`AerodynamicsPlugin`]            | Applies [aerodynamic](dynamics::aerodynamics) drag and lift to bodies with the [`Aerodynamics`] component.                                                 |
 /// | [`SolverPlugin`]                  | Manages and solves contacts, [joints](dynamics::solver::joints), and other constraints.                                                                    |
 /// | [`CcdPlugin`]                     | Performs sweep-based [Continuous Collision Detection](dynamics::ccd) for bodies with the [`SweptCcd`] component.                                           |
+/// | [`IslandPlugin`]                  | Groups bodies into [simulation islands](dynamics::islands) that sleep together and are solved in parallel.                                                 |
 /// | [`SleepingPlugin`]                | Manages sleeping and waking for bodies, automatically deactivating them to save computational resources.                                                   |
+/// | [`RagdollPlugin`]                 | Constructs [ragdolls](dynamics::ragdoll) from bone hierarchies and blends between their animated and simulated poses.                                      |
+/// | [`CharacterControllerPlugin`]     | Moves [kinematic character controllers](dynamics::character_controller), sliding along obstacles and detecting the ground.                                 |
+/// | [`BuoyancyPlugin`]                | Applies buoyancy and drag to bodies submerged in [fluid volumes](dynamics::buoyancy).                                                                      |
+/// | [`VehiclePlugin`]                 | Simulates [vehicles](dynamics::vehicle) with raycast wheels, applying suspension, drive and friction forces to the chassis.                                |
 /// | [`SpatialQueryPlugin`]            | Handles spatial queries like [raycasting](spatial_query#raycasting) and [shapecasting](spatial_query#shapecasting).                                        |
 /// | [`SyncPlugin`]                    | Keeps [`Position`] and [`Rotation`] in sync with `Transform`.                                                                                              |
+/// | [`PhysicsInterpolationPlugin`]    | Eases `Transform` between physics steps for bodies with [`TransformInterpolation`] or [`TransformExtrapolation`].                                          |
 /// | [`PhysicsDebugPlugin`]            | Renders physics objects and events like [AABBs](ColliderAabb) and [contacts](Collision) for debugging purposes (only with `debug-plugin` feature enabled). |
 ///
 /// Refer to the documentation of the plugins for more information about their responsibilities and implementations.
@@ -706,6 +753,21 @@ impl PhysicsPlugins {
         self.length_unit = unit;
         self
     }
+
+    /// Adds the given [`CollisionHooks`] for filtering collision pairs
+    /// and modifying contacts inside the [narrow phase](NarrowPhasePlugin).
+    ///
+    /// Hooks are only called for colliders with the [`ActiveCollisionHooks`] component.
+    /// See [`CollisionHooks`] for more information and an example.
+    pub fn with_collision_hooks<H: CollisionHooks + 'static>(self) -> PhysicsPluginsWithHooks<H>
+    where
+        for<'w, 's> SystemParamItem<'w, 's, H>: CollisionHooks,
+    {
+        PhysicsPluginsWithHooks {
+            plugins: self,
+            _phantom: PhantomData,
+        }
+    }
 }
 
 impl Default for PhysicsPlugins {
@@ -728,16 +790,57 @@ impl PluginGroup for PhysicsPlugins {
         ))]
         let builder = builder
             .add(ColliderBackendPlugin::<Collider>::new(self.schedule))
-            .add(NarrowPhasePlugin::<Collider>::default());
+            .add(NarrowPhasePlugin::<Collider>::default())
+            .add(RagdollPlugin::new(self.schedule))
+            .add(CharacterControllerPlugin::new(self.schedule))
+            .add(VehiclePlugin::new(self.schedule))
+            .add(BuoyancyPlugin);
+
+        #[cfg(feature = "physics-material")]
+        let builder = builder.add(PhysicsMaterialPlugin::new(self.schedule));
 
         builder
             .add(BroadPhasePlugin)
             .add(ContactReportingPlugin)
             .add(IntegratorPlugin::default())
+            .add(GravityFieldPlugin)
+            .add(AerodynamicsPlugin::new(self.schedule))
             .add(SolverPlugin::new_with_length_unit(self.length_unit))
             .add(CcdPlugin::new(self.schedule))
+            .add(IslandPlugin)
             .add(SleepingPlugin)
             .add(SpatialQueryPlugin::new(self.schedule))
             .add(SyncPlugin::new(self.schedule))
+            .add(PhysicsInterpolationPlugin::new(self.schedule))
+    }
+}
+
+/// A [`PhysicsPlugins`] plugin group with user-defined [`CollisionHooks`].
+///
+/// This is created using [`PhysicsPlugins::with_collision_hooks`].
+pub struct PhysicsPluginsWithHooks<H: CollisionHooks> {
+    plugins: PhysicsPlugins,
+    _phantom: PhantomData<H>,
+}
+
+impl<H: CollisionHooks + 'static> PluginGroup for PhysicsPluginsWithHooks<H>
+where
+    for<'w, 's> SystemParamItem<'w, 's, H>: CollisionHooks,
+{
+    fn build(self) -> PluginGroupBuilder {
+        let builder = self.plugins.build();
+
+        // Replace the default narrow phase with one that uses the hooks.
+        #[cfg(all(
+            feature = "default-collider",
+            any(feature = "parry-f32", feature = "parry-f64")
+        ))]
+        let builder = builder
+            .disable::<NarrowPhasePlugin<Collider>>()
+            .add_after::<NarrowPhasePlugin<Collider>, _>(
+                NarrowPhasePlugin::<Collider, H>::default(),
+            );
+
+        builder
     }
 }
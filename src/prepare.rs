@@ -416,6 +416,7 @@ fn init_rigid_bodies(
             *restitution.unwrap_or(&Restitution::default()),
             *friction.unwrap_or(&Friction::default()),
             *time_sleeping.unwrap_or(&TimeSleeping::default()),
+            LocalGravity::default(),
         ));
     }
 }
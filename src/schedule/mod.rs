@@ -145,6 +145,10 @@ pub struct SubstepSchedule;
 /// The schedule is empty by default and runs in
 /// [`NarrowPhaseSet::PostProcess`](collision::narrow_phase::NarrowPhaseSet::PostProcess).
 ///
+/// Systems in this schedule run serially over all collisions after the narrow phase.
+/// For per-pair filtering and contact modification, consider using [`CollisionHooks`] instead,
+/// as they are called by the narrow phase inside of its parallel loop.
+///
 /// ## Example
 ///
 /// Below is an example of how you could add a system that filters collisions.
@@ -176,9 +176,12 @@ use bevy::{
     prelude::*,
 };
 
-/// Initializes the [`SpatialQueryPipeline`] resource and handles component-based [spatial queries](spatial_query)
+/// Keeps the [`SpatialQueryPipeline`] up to date and handles component-based [spatial queries](spatial_query)
 /// like [raycasting](spatial_query#raycasting) and [shapecasting](spatial_query#shapecasting) with
 /// [`RayCaster`] and [`ShapeCaster`].
+///
+/// Spatial queries are performed against the [`ColliderTree`], which is shared with the [`BroadPhasePlugin`].
+/// Without the broad phase, the tree is only updated for colliders that have been added or changed.
 pub struct SpatialQueryPlugin {
     schedule: Interned<dyn ScheduleLabel>,
 }
@@ -206,7 +209,7 @@ impl Plugin for SpatialQueryPlugin {
             feature = "default-collider",
             any(feature = "parry-f32", feature = "parry-f64")
         ))]
-        app.init_resource::<SpatialQueryPipeline>();
+        app.init_resource::<ColliderTree>();
 
         app.add_systems(self.schedule, init_ray_hits.in_set(PrepareSet::PreInit));
 
@@ -1,151 +1,109 @@
-use std::sync::Arc;
-
 use crate::prelude::*;
-use bevy::{prelude::*, utils::HashMap};
+use bevy::{ecs::system::SystemParam, prelude::*};
 use parry::{
-    bounding_volume::Aabb,
     math::Isometry,
-    partitioning::Qbvh,
-    query::{
-        details::{
-            NormalConstraints, RayCompositeShapeToiAndNormalBestFirstVisitor,
-            TOICompositeShapeShapeBestFirstVisitor,
-        },
-        point::PointCompositeShapeProjBestFirstVisitor,
-        visitors::{
-            BoundingVolumeIntersectionsVisitor, PointIntersectionsVisitor, RayIntersectionsVisitor,
-        },
-        DefaultQueryDispatcher, QueryDispatcher, ShapeCastOptions,
-    },
-    shape::{Shape, TypedSimdCompositeShape},
+    query::{DefaultQueryDispatcher, QueryDispatcher, ShapeCastOptions},
 };
 
-/// A resource for the spatial query pipeline.
+/// A system parameter for the spatial query pipeline.
 ///
-/// The pipeline maintains a quaternary bounding volume hierarchy `Qbvh` of the world's colliders
-/// as an acceleration structure for spatial queries.
-#[derive(Resource, Clone)]
-pub struct SpatialQueryPipeline {
-    pub(crate) qbvh: Qbvh<u32>,
-    pub(crate) dispatcher: Arc<dyn QueryDispatcher>,
-    pub(crate) colliders: HashMap<Entity, (Isometry<Scalar>, Collider, CollisionLayers)>,
-    pub(crate) entity_generations: HashMap<u32, u32>,
-}
-
-impl Default for SpatialQueryPipeline {
-    fn default() -> Self {
-        Self {
-            qbvh: Qbvh::new(),
-            dispatcher: Arc::new(DefaultQueryDispatcher),
-            colliders: HashMap::default(),
-            entity_generations: HashMap::default(),
-        }
-    }
+/// The pipeline performs spatial queries against the [`ColliderTree`], the incrementally updated
+/// bounding volume hierarchy that is shared with the [broad phase](BroadPhasePlugin).
+/// Because the tree persists across frames, only colliders that have moved need to be updated,
+/// and large numbers of static colliders don't add any per-frame cost.
+///
+/// The tree only stores conservative AABBs, so the actual collider shapes and transforms
+/// are read from the ECS when a query reaches a leaf.
+///
+/// Most users should use the [`SpatialQuery`] system parameter instead.
+///
+/// ## Migration
+///
+/// This used to be a [`Resource`] with its own copy of every collider. It is now a [`SystemParam`],
+/// so it can't be accessed with `Res<SpatialQueryPipeline>` or `ResMut<SpatialQueryPipeline>` anymore:
+///
+/// - In systems, use `SpatialQueryPipeline` (or [`SpatialQuery`]) directly as a system parameter.
+/// - Outside of systems, use a [`SystemState`](bevy::ecs::system::SystemState) to access it from the [`World`].
+/// - [`update`](Self::update) no longer takes the colliders as arguments, and `SpatialQueryPipeline::new`
+///   has been removed, as the [`ColliderTree`] resource is initialized by the plugins.
+///
+/// The query methods are unchanged.
+#[derive(SystemParam)]
+pub struct SpatialQueryPipeline<'w, 's> {
+    colliders: Query<
+        'w,
+        's,
+        (
+            &'static Position,
+            &'static Rotation,
+            &'static Collider,
+            Option<&'static CollisionLayers>,
+        ),
+    >,
+    #[allow(clippy::type_complexity)]
+    changed_colliders: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static Position,
+            &'static Rotation,
+            &'static Collider,
+            Option<&'static ColliderParent>,
+            Option<&'static CollisionLayers>,
+        ),
+        Or<(Changed<Position>, Changed<Rotation>, Changed<Collider>)>,
+    >,
+    removed_colliders: RemovedComponents<'w, 's, Collider>,
+    /// The [`ColliderTree`] that queries are performed against.
+    pub collider_tree: ResMut<'w, ColliderTree>,
 }
 
-impl SpatialQueryPipeline {
-    /// Creates a new [`SpatialQueryPipeline`].
-    pub fn new() -> SpatialQueryPipeline {
-        SpatialQueryPipeline::default()
-    }
-
-    pub(crate) fn as_composite_shape(
-        &self,
-        query_filter: SpatialQueryFilter,
-    ) -> QueryPipelineAsCompositeShape {
-        QueryPipelineAsCompositeShape {
-            pipeline: self,
-            colliders: &self.colliders,
-            query_filter,
-        }
-    }
-
-    pub(crate) fn as_composite_shape_with_predicate<'a>(
-        &'a self,
-        query_filter: SpatialQueryFilter,
-        predicate: &'a dyn Fn(Entity) -> bool,
-    ) -> QueryPipelineAsCompositeShapeWithPredicate {
-        QueryPipelineAsCompositeShapeWithPredicate {
-            pipeline: self,
-            colliders: &self.colliders,
-            query_filter,
-            predicate,
-        }
-    }
-
-    /// Updates the associated acceleration structures with a new set of entities.
-    pub fn update<'a>(
-        &mut self,
-        colliders: impl Iterator<
-            Item = (
-                Entity,
-                &'a Position,
-                &'a Rotation,
-                &'a Collider,
-                Option<&'a CollisionLayers>,
-            ),
-        >,
-        added_colliders: impl Iterator<Item = Entity>,
-    ) {
-        let colliders = colliders
-            .map(|(entity, position, rotation, collider, layers)| {
-                (
-                    entity,
-                    (
-                        make_isometry(position.0, *rotation),
-                        collider.clone(),
-                        layers.map_or(CollisionLayers::default(), |layers| *layers),
-                    ),
-                )
-            })
-            .collect();
-
-        self.update_internal(colliders, added_colliders)
-    }
-
-    fn update_internal(
-        &mut self,
-        colliders: HashMap<Entity, (Isometry<Scalar>, Collider, CollisionLayers)>,
-        added: impl Iterator<Item = Entity>,
-    ) {
-        self.colliders = colliders;
-
-        // Insert or update generations of added entities
-        for added in added {
-            let index = added.index();
-            if let Some(generation) = self.entity_generations.get_mut(&index) {
-                *generation = added.generation();
-            } else {
-                self.entity_generations.insert(index, added.generation());
-            }
+impl<'w, 's> SpatialQueryPipeline<'w, 's> {
+    /// Refits the [`ColliderTree`] for colliders that have been added, moved, or modified
+    /// since the last update, and removes colliders that no longer exist.
+    ///
+    /// The tree is fully updated by the broad phase once per physics step, so this only needs to handle
+    /// the changes made after that, like the movement of bodies during the solver.
+    pub fn update(&mut self) {
+        for entity in self.removed_colliders.read() {
+            self.collider_tree.remove(entity);
         }
 
-        struct DataGenerator<'a>(
-            &'a HashMap<Entity, (Isometry<Scalar>, Collider, CollisionLayers)>,
-        );
-
-        impl<'a> parry::partitioning::QbvhDataGenerator<u32> for DataGenerator<'a> {
-            fn size_hint(&self) -> usize {
-                self.0.len()
+        for (entity, position, rotation, collider, parent, layers) in &self.changed_colliders {
+            let aabb = collider.aabb(position.0, *rotation);
+            if !aabb.min.is_finite() || !aabb.max.is_finite() {
+                continue;
             }
 
-            #[inline(always)]
-            fn for_each(&mut self, mut f: impl FnMut(u32, parry::bounding_volume::Aabb)) {
-                for (entity, co) in self.0.iter() {
-                    // Compute and return AABB
-                    let (iso, shape, _) = co;
-                    let aabb = shape.shape_scaled().compute_aabb(iso);
-                    f(entity.index(), aabb)
-                }
-            }
+            let data = match self.collider_tree.get(entity) {
+                Some(data) => ColliderTreeProxy { aabb, ..*data },
+                None => ColliderTreeProxy {
+                    entity,
+                    parent: parent.map_or(ColliderParent(entity), |p| *p),
+                    aabb,
+                    layers: layers.map_or(CollisionLayers::default(), |layers| *layers),
+                    store_intersections: false,
+                    is_inactive: false,
+                },
+            };
+            self.collider_tree.insert_or_update(data);
         }
-
-        self.qbvh
-            .clear_and_rebuild(DataGenerator(&self.colliders), 0.01);
     }
 
-    pub(crate) fn entity_from_index(&self, index: u32) -> Entity {
-        entity_from_index_and_gen(index, *self.entity_generations.get(&index).unwrap())
+    /// Returns the isometry, shape, and collision layers of the given collider entity,
+    /// or `None` if the entity doesn't match the `query_filter`.
+    #[inline]
+    fn collider(
+        &self,
+        entity: Entity,
+        query_filter: &SpatialQueryFilter,
+    ) -> Option<(Isometry<Scalar>, &Collider)> {
+        let (position, rotation, collider, layers) = self.colliders.get(entity).ok()?;
+        let layers = layers.map_or(CollisionLayers::default(), |layers| *layers);
+        query_filter
+            .test(entity, layers)
+            .then(|| (make_isometry(position.0, *rotation), collider))
     }
 
     /// Casts a [ray](spatial_query#raycasting) and computes the closest [hit](RayHitData) with a collider.
@@ -169,22 +127,14 @@ impl SpatialQueryPipeline {
         solid: bool,
         query_filter: SpatialQueryFilter,
     ) -> Option<RayHitData> {
-        let pipeline_shape = self.as_composite_shape(query_filter);
-        let ray = parry::query::Ray::new(origin.into(), direction.adjust_precision().into());
-        let mut visitor = RayCompositeShapeToiAndNormalBestFirstVisitor::new(
-            &pipeline_shape,
-            &ray,
+        self.cast_ray_predicate(
+            origin,
+            direction,
             max_time_of_impact,
             solid,
-        );
-
-        self.qbvh
-            .traverse_best_first(&mut visitor)
-            .map(|(_, (entity_index, hit))| RayHitData {
-                entity: self.entity_from_index(entity_index),
-                time_of_impact: hit.time_of_impact,
-                normal: hit.normal.into(),
-            })
+            query_filter,
+            &|_| true,
+        )
     }
 
     /// Casts a [ray](spatial_query#raycasting) and computes the closest [hit](RayHitData) with a collider.
@@ -211,22 +161,35 @@ impl SpatialQueryPipeline {
         query_filter: SpatialQueryFilter,
         predicate: &dyn Fn(Entity) -> bool,
     ) -> Option<RayHitData> {
-        let pipeline_shape = self.as_composite_shape_with_predicate(query_filter, predicate);
         let ray = parry::query::Ray::new(origin.into(), direction.adjust_precision().into());
-        let mut visitor = RayCompositeShapeToiAndNormalBestFirstVisitor::new(
-            &pipeline_shape,
-            &ray,
-            max_time_of_impact,
-            solid,
-        );
-
-        self.qbvh
-            .traverse_best_first(&mut visitor)
-            .map(|(_, (entity_index, hit))| RayHitData {
-                entity: self.entity_from_index(entity_index),
-                time_of_impact: hit.time_of_impact,
-                normal: hit.normal.into(),
-            })
+        let inv_direction = direction.adjust_precision().recip();
+
+        self.collider_tree
+            .tree()
+            .traverse_best_first(
+                |aabb| ray_aabb_time_of_impact(origin, inv_direction, aabb, max_time_of_impact),
+                |_, proxy, best_time_of_impact| {
+                    if !predicate(proxy.entity) {
+                        return None;
+                    }
+                    let (iso, collider) = self.collider(proxy.entity, &query_filter)?;
+                    let hit = collider.shape_scaled().cast_ray_and_get_normal(
+                        &iso,
+                        &ray,
+                        best_time_of_impact.min(max_time_of_impact),
+                        solid,
+                    )?;
+                    Some((
+                        hit.time_of_impact,
+                        RayHitData {
+                            entity: proxy.entity,
+                            time_of_impact: hit.time_of_impact,
+                            normal: hit.normal.into(),
+                        },
+                    ))
+                },
+            )
+            .map(|(_, hit)| hit)
     }
 
     /// Casts a [ray](spatial_query#raycasting) and computes all [hits](RayHitData) until `max_hits` is reached.
@@ -294,36 +257,32 @@ impl SpatialQueryPipeline {
         query_filter: SpatialQueryFilter,
         mut callback: impl FnMut(RayHitData) -> bool,
     ) {
-        let colliders = &self.colliders;
-
         let ray = parry::query::Ray::new(origin.into(), direction.adjust_precision().into());
+        let inv_direction = direction.adjust_precision().recip();
 
-        let mut leaf_callback = &mut |entity_index: &u32| {
-            let entity = self.entity_from_index(*entity_index);
-            if let Some((iso, shape, layers)) = colliders.get(&entity) {
-                if query_filter.test(entity, *layers) {
-                    if let Some(hit) = shape.shape_scaled().cast_ray_and_get_normal(
-                        iso,
-                        &ray,
-                        max_time_of_impact,
-                        solid,
-                    ) {
-                        let hit = RayHitData {
-                            entity,
-                            time_of_impact: hit.time_of_impact,
-                            normal: hit.normal.into(),
-                        };
-
-                        return callback(hit);
-                    }
+        self.collider_tree.tree().query(
+            |aabb| {
+                ray_aabb_time_of_impact(origin, inv_direction, aabb, max_time_of_impact).is_some()
+            },
+            |_, proxy| {
+                let Some((iso, collider)) = self.collider(proxy.entity, &query_filter) else {
+                    return true;
+                };
+                if let Some(hit) = collider.shape_scaled().cast_ray_and_get_normal(
+                    &iso,
+                    &ray,
+                    max_time_of_impact,
+                    solid,
+                ) {
+                    return callback(RayHitData {
+                        entity: proxy.entity,
+                        time_of_impact: hit.time_of_impact,
+                        normal: hit.normal.into(),
+                    });
                 }
-            }
-            true
-        };
-
-        let mut visitor =
-            RayIntersectionsVisitor::new(&ray, max_time_of_impact, &mut leaf_callback);
-        self.qbvh.traverse_depth_first(&mut visitor);
+                true
+            },
+        );
     }
 
     /// Casts a [shape](spatial_query#shapecasting) with a given rotation and computes the closest [hit](ShapeHits)
@@ -365,32 +324,76 @@ impl SpatialQueryPipeline {
             rotation = Rotation::from(shape_rotation);
         }
 
-        let shape_isometry = make_isometry(origin, rotation);
-        let shape_direction = direction.adjust_precision().into();
-        let pipeline_shape = self.as_composite_shape(query_filter);
-        let mut visitor = TOICompositeShapeShapeBestFirstVisitor::new(
-            &*self.dispatcher,
-            &shape_isometry,
-            &shape_direction,
-            &pipeline_shape,
-            &**shape.shape_scaled(),
-            ShapeCastOptions {
-                max_time_of_impact,
-                stop_at_penetration: !ignore_origin_penetration,
-                ..default()
-            },
-        );
+        self.cast_shape_internal(
+            shape,
+            &make_isometry(origin, rotation),
+            direction,
+            max_time_of_impact,
+            ignore_origin_penetration,
+            &query_filter,
+        )
+    }
 
-        self.qbvh
-            .traverse_best_first(&mut visitor)
-            .map(|(_, (entity_index, hit))| ShapeHitData {
-                entity: self.entity_from_index(entity_index),
-                time_of_impact: hit.time_of_impact,
-                point1: hit.witness1.into(),
-                point2: hit.witness2.into(),
-                normal1: hit.normal1.into(),
-                normal2: hit.normal2.into(),
-            })
+    /// Casts the given `shape` and computes the closest hit with a collider.
+    pub(crate) fn cast_shape_internal(
+        &self,
+        shape: &Collider,
+        shape_isometry: &Isometry<Scalar>,
+        direction: Dir,
+        max_time_of_impact: Scalar,
+        ignore_origin_penetration: bool,
+        query_filter: &SpatialQueryFilter,
+    ) -> Option<ShapeHitData> {
+        let shape_direction = direction.adjust_precision().into();
+        let inv_direction = direction.adjust_precision().recip();
+
+        // Sweeping the shape against an AABB is equivalent to casting a ray from the center
+        // of the shape's AABB against the AABB enlarged by the shape's half-extents.
+        let shape_aabb = shape.shape_scaled().compute_aabb(shape_isometry);
+        let shape_center: Vector = shape_aabb.center().into();
+        let shape_half_extents: Vector = shape_aabb.half_extents().into();
+
+        self.collider_tree
+            .tree()
+            .traverse_best_first(
+                |aabb| {
+                    ray_aabb_time_of_impact(
+                        shape_center,
+                        inv_direction,
+                        &aabb.grow(shape_half_extents),
+                        max_time_of_impact,
+                    )
+                },
+                |_, proxy, best_time_of_impact| {
+                    let (iso, collider) = self.collider(proxy.entity, query_filter)?;
+                    let hit = DefaultQueryDispatcher
+                        .cast_shapes(
+                            &iso.inv_mul(shape_isometry),
+                            &iso.inverse_transform_vector(&shape_direction),
+                            &**collider.shape_scaled(),
+                            &**shape.shape_scaled(),
+                            ShapeCastOptions {
+                                max_time_of_impact: best_time_of_impact.min(max_time_of_impact),
+                                stop_at_penetration: !ignore_origin_penetration,
+                                ..default()
+                            },
+                        )
+                        .ok()??
+                        .transform1_by(&iso);
+                    Some((
+                        hit.time_of_impact,
+                        ShapeHitData {
+                            entity: proxy.entity,
+                            time_of_impact: hit.time_of_impact,
+                            point1: hit.witness1.into(),
+                            point2: hit.witness2.into(),
+                            normal1: hit.normal1.into(),
+                            normal2: hit.normal2.into(),
+                        },
+                    ))
+                },
+            )
+            .map(|(_, hit)| hit)
     }
 
     /// Casts a [shape](spatial_query#shapecasting) with a given rotation and computes computes all [hits](ShapeHitData)
@@ -481,41 +484,18 @@ impl SpatialQueryPipeline {
         }
 
         let shape_isometry = make_isometry(origin, rotation);
-        let shape_direction = direction.adjust_precision().into();
 
-        loop {
-            let pipeline_shape = self.as_composite_shape(query_filter.clone());
-            let mut visitor = TOICompositeShapeShapeBestFirstVisitor::new(
-                &*self.dispatcher,
-                &shape_isometry,
-                &shape_direction,
-                &pipeline_shape,
-                &**shape.shape_scaled(),
-                ShapeCastOptions {
-                    max_time_of_impact,
-                    stop_at_penetration: !ignore_origin_penetration,
-                    ..default()
-                },
-            );
+        while let Some(hit) = self.cast_shape_internal(
+            shape,
+            &shape_isometry,
+            direction,
+            max_time_of_impact,
+            ignore_origin_penetration,
+            &query_filter,
+        ) {
+            query_filter.excluded_entities.insert(hit.entity);
 
-            if let Some(hit) =
-                self.qbvh
-                    .traverse_best_first(&mut visitor)
-                    .map(|(_, (entity_index, hit))| ShapeHitData {
-                        entity: self.entity_from_index(entity_index),
-                        time_of_impact: hit.time_of_impact,
-                        point1: hit.witness1.into(),
-                        point2: hit.witness2.into(),
-                        normal1: hit.normal1.into(),
-                        normal2: hit.normal2.into(),
-                    })
-            {
-                query_filter.excluded_entities.insert(hit.entity);
-
-                if !callback(hit) {
-                    break;
-                }
-            } else {
+            if !callback(hit) {
                 break;
             }
         }
@@ -538,18 +518,37 @@ impl SpatialQueryPipeline {
         solid: bool,
         query_filter: SpatialQueryFilter,
     ) -> Option<PointProjection> {
-        let point = point.into();
-        let pipeline_shape = self.as_composite_shape(query_filter);
-        let mut visitor =
-            PointCompositeShapeProjBestFirstVisitor::new(&pipeline_shape, &point, solid);
-
-        self.qbvh
-            .traverse_best_first(&mut visitor)
-            .map(|(_, (projection, entity_index))| PointProjection {
-                entity: self.entity_from_index(entity_index),
-                point: projection.point.into(),
-                is_inside: projection.is_inside,
-            })
+        let parry_point = point.into();
+
+        self.collider_tree
+            .tree()
+            .traverse_best_first(
+                |aabb| {
+                    Some(
+                        (aabb.min - point)
+                            .max(point - aabb.max)
+                            .max(Vector::ZERO)
+                            .length(),
+                    )
+                },
+                |_, proxy, _| {
+                    let (iso, collider) = self.collider(proxy.entity, &query_filter)?;
+                    let projection =
+                        collider
+                            .shape_scaled()
+                            .project_point(&iso, &parry_point, solid);
+                    let projected_point: Vector = projection.point.into();
+                    Some((
+                        projected_point.distance(point),
+                        PointProjection {
+                            entity: proxy.entity,
+                            point: projected_point,
+                            is_inside: projection.is_inside,
+                        },
+                    ))
+                },
+            )
+            .map(|(_, projection)| projection)
     }
 
     /// An [intersection test](spatial_query#intersection-tests) that finds all entities with a [collider](Collider)
@@ -591,22 +590,19 @@ impl SpatialQueryPipeline {
         query_filter: SpatialQueryFilter,
         mut callback: impl FnMut(Entity) -> bool,
     ) {
-        let point = point.into();
-
-        let mut leaf_callback = &mut |entity_index: &u32| {
-            let entity = self.entity_from_index(*entity_index);
-            if let Some((isometry, shape, layers)) = self.colliders.get(&entity) {
-                if query_filter.test(entity, *layers)
-                    && shape.shape_scaled().contains_point(isometry, &point)
-                {
-                    return callback(entity);
+        let parry_point = point.into();
+
+        self.collider_tree.tree().query(
+            |aabb| aabb.min.cmple(point).all() && aabb.max.cmpge(point).all(),
+            |_, proxy| {
+                if let Some((iso, collider)) = self.collider(proxy.entity, &query_filter) {
+                    if collider.shape_scaled().contains_point(&iso, &parry_point) {
+                        return callback(proxy.entity);
+                    }
                 }
-            }
-            true
-        };
-
-        let mut visitor = PointIntersectionsVisitor::new(&point, &mut leaf_callback);
-        self.qbvh.traverse_depth_first(&mut visitor);
+                true
+            },
+        );
     }
 
     /// An [intersection test](spatial_query#intersection-tests) that finds all entities with a [`ColliderAabb`]
@@ -632,19 +628,16 @@ impl SpatialQueryPipeline {
         aabb: ColliderAabb,
         mut callback: impl FnMut(Entity) -> bool,
     ) {
-        let mut leaf_callback = |entity_index: &u32| {
-            let entity = self.entity_from_index(*entity_index);
-            callback(entity)
-        };
-
-        let mut visitor = BoundingVolumeIntersectionsVisitor::new(
-            &Aabb {
-                mins: aabb.min.into(),
-                maxs: aabb.max.into(),
-            },
-            &mut leaf_callback,
-        );
-        self.qbvh.traverse_depth_first(&mut visitor);
+        self.collider_tree.query_aabb(aabb, |proxy| {
+            // The AABBs in the tree are enlarged, so test against the actual AABB of the collider.
+            let Ok((position, rotation, collider, _)) = self.colliders.get(proxy.entity) else {
+                return true;
+            };
+            if collider.aabb(position.0, *rotation).intersects(&aabb) {
+                return callback(proxy.entity);
+            }
+            true
+        });
     }
 
     /// An [intersection test](spatial_query#intersection-tests) that finds all entities with a [`Collider`]
@@ -700,7 +693,6 @@ impl SpatialQueryPipeline {
         query_filter: SpatialQueryFilter,
         mut callback: impl FnMut(Entity) -> bool,
     ) {
-        let colliders = &self.colliders;
         let rotation: Rotation;
         #[cfg(feature = "2d")]
         {
@@ -714,127 +706,45 @@ impl SpatialQueryPipeline {
         let shape_isometry = make_isometry(shape_position, rotation);
         let inverse_shape_isometry = shape_isometry.inverse();
 
-        let dispatcher = &*self.dispatcher;
-
-        let mut leaf_callback = &mut |entity_index: &u32| {
-            let entity = self.entity_from_index(*entity_index);
-
-            if let Some((collider_isometry, collider, layers)) = colliders.get(&entity) {
-                if query_filter.test(entity, *layers) {
+        self.collider_tree
+            .query_aabb(shape.aabb(shape_position, rotation), |proxy| {
+                if let Some((collider_isometry, collider)) =
+                    self.collider(proxy.entity, &query_filter)
+                {
                     let isometry = inverse_shape_isometry * collider_isometry;
 
-                    if dispatcher.intersection_test(
+                    if DefaultQueryDispatcher.intersection_test(
                         &isometry,
                         &**shape.shape_scaled(),
                         &**collider.shape_scaled(),
                     ) == Ok(true)
                     {
-                        return callback(entity);
+                        return callback(proxy.entity);
                     }
                 }
-            }
-            true
-        };
-
-        let shape_aabb = shape.shape_scaled().compute_aabb(&shape_isometry);
-        let mut visitor = BoundingVolumeIntersectionsVisitor::new(&shape_aabb, &mut leaf_callback);
-        self.qbvh.traverse_depth_first(&mut visitor);
-    }
-}
-
-pub(crate) struct QueryPipelineAsCompositeShape<'a> {
-    colliders: &'a HashMap<Entity, (Isometry<Scalar>, Collider, CollisionLayers)>,
-    pipeline: &'a SpatialQueryPipeline,
-    query_filter: SpatialQueryFilter,
-}
-
-impl<'a> TypedSimdCompositeShape for QueryPipelineAsCompositeShape<'a> {
-    type PartShape = dyn Shape;
-    type PartNormalConstraints = dyn NormalConstraints;
-    type PartId = u32;
-
-    fn map_typed_part_at(
-        &self,
-        shape_id: Self::PartId,
-        mut f: impl FnMut(
-            Option<&Isometry<Scalar>>,
-            &Self::PartShape,
-            Option<&Self::PartNormalConstraints>,
-        ),
-    ) {
-        if let Some((entity, (iso, shape, layers))) =
-            self.colliders.get_key_value(&entity_from_index_and_gen(
-                shape_id,
-                *self.pipeline.entity_generations.get(&shape_id).unwrap(),
-            ))
-        {
-            if self.query_filter.test(*entity, *layers) {
-                f(Some(iso), &**shape.shape_scaled(), None);
-            }
-        }
-    }
-
-    fn map_untyped_part_at(
-        &self,
-        shape_id: Self::PartId,
-        f: impl FnMut(Option<&Isometry<Scalar>>, &dyn Shape, Option<&dyn NormalConstraints>),
-    ) {
-        self.map_typed_part_at(shape_id, f);
-    }
-
-    fn typed_qbvh(&self) -> &Qbvh<Self::PartId> {
-        &self.pipeline.qbvh
-    }
-}
-
-pub(crate) struct QueryPipelineAsCompositeShapeWithPredicate<'a, 'b> {
-    colliders: &'a HashMap<Entity, (Isometry<Scalar>, Collider, CollisionLayers)>,
-    pipeline: &'a SpatialQueryPipeline,
-    query_filter: SpatialQueryFilter,
-    predicate: &'b dyn Fn(Entity) -> bool,
-}
-
-impl<'a, 'b> TypedSimdCompositeShape for QueryPipelineAsCompositeShapeWithPredicate<'a, 'b> {
-    type PartShape = dyn Shape;
-    type PartNormalConstraints = dyn NormalConstraints;
-    type PartId = u32;
-
-    fn map_typed_part_at(
-        &self,
-        shape_id: Self::PartId,
-        mut f: impl FnMut(
-            Option<&Isometry<Scalar>>,
-            &Self::PartShape,
-            Option<&Self::PartNormalConstraints>,
-        ),
-    ) {
-        if let Some((entity, (iso, shape, layers))) =
-            self.colliders.get_key_value(&entity_from_index_and_gen(
-                shape_id,
-                *self.pipeline.entity_generations.get(&shape_id).unwrap(),
-            ))
-        {
-            if self.query_filter.test(*entity, *layers) && (self.predicate)(*entity) {
-                f(Some(iso), &**shape.shape_scaled(), None);
-            }
-        }
-    }
-
-    fn map_untyped_part_at(
-        &self,
-        shape_id: Self::PartId,
-        f: impl FnMut(Option<&Isometry<Scalar>>, &dyn Shape, Option<&dyn NormalConstraints>),
-    ) {
-        self.map_typed_part_at(shape_id, f);
-    }
-
-    fn typed_qbvh(&self) -> &Qbvh<Self::PartId> {
-        &self.pipeline.qbvh
+                true
+            });
     }
 }
 
-fn entity_from_index_and_gen(index: u32, generation: u32) -> bevy::prelude::Entity {
-    bevy::prelude::Entity::from_bits((generation as u64) << 32 | index as u64)
+/// Computes the time of impact of a ray with an AABB using the slab method,
+/// or `None` if the ray doesn't hit the AABB within `max_time_of_impact`.
+///
+/// If the ray origin is inside of the AABB, the time of impact is zero.
+#[inline]
+fn ray_aabb_time_of_impact(
+    origin: Vector,
+    inv_direction: Vector,
+    aabb: &ColliderAabb,
+    max_time_of_impact: Scalar,
+) -> Option<Scalar> {
+    let t1 = (aabb.min - origin) * inv_direction;
+    let t2 = (aabb.max - origin) * inv_direction;
+
+    let t_min = t1.min(t2).max_element().max(0.0);
+    let t_max = t1.max(t2).min_element().min(max_time_of_impact);
+
+    (t_min <= t_max).then_some(t_min)
 }
 
 /// The result of a [point projection](spatial_query#point-projection) on a [collider](Collider).
@@ -3,7 +3,6 @@ use bevy::{
     ecs::entity::{EntityMapper, MapEntities},
     prelude::*,
 };
-use parry::query::{details::TOICompositeShapeShapeBestFirstVisitor, ShapeCastOptions};
 
 /// A component used for [shapecasting](spatial_query#shapecasting).
 ///
@@ -300,32 +299,15 @@ impl ShapeCaster {
         }
 
         let shape_isometry = make_isometry(self.global_origin(), shape_rotation);
-        let shape_direction = self.global_direction().adjust_precision().into();
 
         while hits.count < self.max_hits {
-            let pipeline_shape = query_pipeline.as_composite_shape(query_filter.clone());
-            let mut visitor = TOICompositeShapeShapeBestFirstVisitor::new(
-                &*query_pipeline.dispatcher,
+            if let Some(hit) = query_pipeline.cast_shape_internal(
+                &self.shape,
                 &shape_isometry,
-                &shape_direction,
-                &pipeline_shape,
-                &**self.shape.shape_scaled(),
-                ShapeCastOptions {
-                    max_time_of_impact: self.max_time_of_impact,
-                    stop_at_penetration: !self.ignore_origin_penetration,
-                    ..default()
-                },
-            );
-
-            if let Some(hit) = query_pipeline.qbvh.traverse_best_first(&mut visitor).map(
-                |(_, (entity_index, hit))| ShapeHitData {
-                    entity: query_pipeline.entity_from_index(entity_index),
-                    time_of_impact: hit.time_of_impact,
-                    point1: hit.witness1.into(),
-                    point2: hit.witness2.into(),
-                    normal1: hit.normal1.into(),
-                    normal2: hit.normal2.into(),
-                },
+                self.global_direction(),
+                self.max_time_of_impact,
+                self.ignore_origin_penetration,
+                &query_filter,
             ) {
                 if (hits.vector.len() as u32) < hits.count + 1 {
                     hits.vector.push(hit);
@@ -61,20 +61,8 @@ use bevy::{ecs::system::SystemParam, prelude::*};
 /// ```
 #[derive(SystemParam)]
 pub struct SpatialQuery<'w, 's> {
-    pub(crate) colliders: Query<
-        'w,
-        's,
-        (
-            Entity,
-            &'static Position,
-            &'static Rotation,
-            &'static Collider,
-            Option<&'static CollisionLayers>,
-        ),
-    >,
-    pub(crate) added_colliders: Query<'w, 's, Entity, Added<Collider>>,
     /// The [`SpatialQueryPipeline`].
-    pub query_pipeline: ResMut<'w, SpatialQueryPipeline>,
+    pub query_pipeline: SpatialQueryPipeline<'w, 's>,
 }
 
 impl<'w, 's> SpatialQuery<'w, 's> {
@@ -82,8 +70,7 @@ impl<'w, 's> SpatialQuery<'w, 's> {
     /// [`PhysicsStepSet::SpatialQuery`], but if you modify colliders or their positions before that, you can
     /// call this to make sure the data is up to date when performing spatial queries using [`SpatialQuery`].
     pub fn update_pipeline(&mut self) {
-        self.query_pipeline
-            .update(self.colliders.iter(), self.added_colliders.iter());
+        self.query_pipeline.update();
     }
 
     /// Casts a [ray](spatial_query#raycasting) and computes the closest [hit](RayHitData) with a collider.
@@ -0,0 +1,536 @@
+//! Smooths out the rendered motion of bodies by easing their `Transform`
+//! between physics steps when using a fixed timestep.
+//!
+//! See [`PhysicsInterpolationPlugin`].
+
+use crate::{
+    prelude::*,
+    sync::{position_to_transform, PreviousGlobalTransform, SyncConfig, SyncSet},
+};
+use bevy::{
+    ecs::{intern::Interned, schedule::ScheduleLabel},
+    prelude::*,
+};
+
+/// Smooths out the rendered motion of bodies by easing their `Transform`
+/// between physics steps when using a fixed timestep.
+///
+/// With [`TimestepMode::Fixed`], the simulation can be run zero, one, or several times per frame,
+/// depending on how much time has accumulated. If the physics state is written to `Transform`
+/// as is, the rendered motion can visibly stutter whenever the frame rate and the physics tick rate diverge.
+///
+/// Easing is opt-in per entity, and can be enabled by adding one of the following components
+/// to a [rigid body](RigidBody):
+///
+/// - [`TransformInterpolation`]: Blends between the previous and current physics state
+///   based on the accumulated overstep. This is always smooth and accurate, but lags behind
+///   the simulation by up to one physics step.
+/// - [`TransformExtrapolation`]: Predicts the future state based on the current state and velocity.
+///   This has no lag, but can overshoot when the velocity changes suddenly, for example on impact.
+///
+/// The eased `Transform` is only used for rendering. At the start of each frame, the `Transform`
+/// of eased bodies is reset to match the actual [`Position`] and [`Rotation`], so gameplay code
+/// reads and writes `Transform` relative to the true physics state. Changes to [`Position`], [`Rotation`],
+/// or `Transform` that are not made by the simulation, like teleporting a body, are detected automatically,
+/// and cause the body to snap to the new state instead of being eased across the gap.
+///
+/// Easing is only performed when [`SyncConfig::position_to_transform`] is enabled.
+/// For timestep modes other than [`TimestepMode::Fixed`], the `Transform` simply follows the physics state.
+///
+/// ## Example
+///
+/// ```no_run
+#[cfg_attr(feature = "2d", doc = "use avian2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use avian3d::prelude::*;")]
+/// use bevy::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     // Render the body with interpolation.
+///     commands.spawn((
+///         RigidBody::Dynamic,
+#[cfg_attr(feature = "2d", doc = "        Collider::circle(0.5),")]
+#[cfg_attr(feature = "3d", doc = "        Collider::sphere(0.5),")]
+///         TransformInterpolation,
+///     ));
+/// }
+/// ```
+pub struct PhysicsInterpolationPlugin {
+    schedule: Interned<dyn ScheduleLabel>,
+}
+
+impl PhysicsInterpolationPlugin {
+    /// Creates a [`PhysicsInterpolationPlugin`] with the schedule that is used for running the [`PhysicsSchedule`].
+    ///
+    /// The default schedule is `PostUpdate`.
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+        }
+    }
+}
+
+impl Default for PhysicsInterpolationPlugin {
+    fn default() -> Self {
+        Self::new(PostUpdate)
+    }
+}
+
+impl Plugin for PhysicsInterpolationPlugin {
+    fn build(&self, app: &mut App) {
+        // Reset eased transforms to the actual physics state before any gameplay code runs.
+        app.add_systems(
+            First,
+            reset_eased_transforms.run_if(|config: Res<SyncConfig>| config.position_to_transform),
+        );
+
+        app.add_systems(
+            self.schedule,
+            init_interpolation_state.in_set(PhysicsSet::Prepare),
+        );
+
+        app.get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first")
+            .add_systems((
+                record_start_state.in_set(PhysicsStepSet::First),
+                record_end_state.in_set(PhysicsStepSet::Last),
+            ));
+
+        app.add_systems(
+            self.schedule,
+            (interpolate_transforms, extrapolate_transforms)
+                .chain()
+                .in_set(SyncSet::PositionToTransform)
+                .after(position_to_transform)
+                .run_if(|config: Res<SyncConfig>| config.position_to_transform),
+        );
+    }
+}
+
+/// Enables `Transform` interpolation for a [rigid body](RigidBody).
+///
+/// The rendered `Transform` is blended between the physics states before and after the most recent
+/// physics step, based on how much time has accumulated towards the next step. This makes movement
+/// smooth regardless of the frame rate, but introduces up to one physics step of visual lag.
+///
+/// See [`PhysicsInterpolationPlugin`] for more information.
+#[derive(Component, Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub struct TransformInterpolation;
+
+/// Enables `Transform` extrapolation for a [rigid body](RigidBody).
+///
+/// The rendered `Transform` is predicted from the current physics state and the body's
+/// [`LinearVelocity`] and [`AngularVelocity`], based on how much time has accumulated towards
+/// the next physics step. Unlike [`TransformInterpolation`], this has no visual lag,
+/// but can overshoot when the velocity changes suddenly, for example on impact.
+///
+/// If an entity has both [`TransformInterpolation`] and [`TransformExtrapolation`],
+/// interpolation is used.
+///
+/// See [`PhysicsInterpolationPlugin`] for more information.
+#[derive(Component, Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub struct TransformExtrapolation;
+
+/// The physics states that the `Transform` of an eased body is computed from.
+///
+/// Automatically added for entities with [`TransformInterpolation`] or [`TransformExtrapolation`].
+#[derive(Component, Reflect, Clone, Copy, Debug, Default, PartialEq)]
+#[reflect(Debug, Component, Default, PartialEq)]
+pub struct InterpolationState {
+    /// The [`Position`] and [`Rotation`] at the start of the most recent physics step.
+    pub start: Option<(Position, Rotation)>,
+    /// The [`Position`] and [`Rotation`] at the end of the most recent physics step.
+    pub end: Option<(Position, Rotation)>,
+}
+
+impl InterpolationState {
+    /// Clears the stored states, making the body snap to its current physics state
+    /// instead of being eased from its previous state.
+    pub fn reset(&mut self) {
+        self.start = None;
+        self.end = None;
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn init_interpolation_state(
+    mut commands: Commands,
+    query: Query<
+        Entity,
+        (
+            Or<(Added<TransformInterpolation>, Added<TransformExtrapolation>)>,
+            Without<InterpolationState>,
+        ),
+    >,
+) {
+    for entity in &query {
+        commands
+            .entity(entity)
+            .try_insert(InterpolationState::default());
+    }
+}
+
+fn record_start_state(mut query: Query<(&Position, &Rotation, &mut InterpolationState)>) {
+    for (pos, rot, mut state) in &mut query {
+        state.start = Some((*pos, *rot));
+    }
+}
+
+fn record_end_state(mut query: Query<(&Position, &Rotation, &mut InterpolationState)>) {
+    for (pos, rot, mut state) in &mut query {
+        state.end = Some((*pos, *rot));
+    }
+}
+
+type ParentComponents = (
+    &'static GlobalTransform,
+    Option<&'static Position>,
+    Option<&'static Rotation>,
+);
+
+/// Computes the local `Transform` of an entity whose global translation and rotation
+/// should be `translation` and `rotation`.
+///
+/// Like in [`position_to_transform`], the parent's [`Position`] and [`Rotation`]
+/// are used instead of its `GlobalTransform` if it is a physics entity.
+fn local_transform(
+    mut transform: Transform,
+    translation: Vector,
+    rotation: Rotation,
+    parent: Option<(&GlobalTransform, Option<&Position>, Option<&Rotation>)>,
+) -> Transform {
+    #[cfg(feature = "2d")]
+    let rotation = Quaternion::from(rotation).f32();
+    #[cfg(feature = "3d")]
+    let rotation = rotation.f32();
+
+    let Some((parent_transform, parent_pos, parent_rot)) = parent else {
+        #[cfg(feature = "2d")]
+        {
+            transform.translation = translation.f32().extend(transform.translation.z);
+        }
+        #[cfg(feature = "3d")]
+        {
+            transform.translation = translation.f32();
+        }
+        transform.rotation = rotation;
+        return transform;
+    };
+
+    let parent_transform = parent_transform.compute_transform();
+    #[cfg(feature = "2d")]
+    let (parent_pos, parent_rot) = (
+        parent_pos.map_or(parent_transform.translation, |pos| {
+            pos.f32().extend(parent_transform.translation.z)
+        }),
+        parent_rot.map_or(parent_transform.rotation, |rot| {
+            Quaternion::from(*rot).f32()
+        }),
+    );
+    #[cfg(feature = "3d")]
+    let (parent_pos, parent_rot) = (
+        parent_pos.map_or(parent_transform.translation, |pos| pos.f32()),
+        parent_rot.map_or(parent_transform.rotation, |rot| rot.f32()),
+    );
+    let parent_scale = parent_transform.scale;
+    let parent_transform = Transform::from_translation(parent_pos)
+        .with_rotation(parent_rot)
+        .with_scale(parent_scale);
+
+    #[cfg(feature = "2d")]
+    let translation = translation
+        .f32()
+        .extend(parent_pos.z + transform.translation.z * parent_scale.z);
+    #[cfg(feature = "3d")]
+    let translation = translation.f32();
+
+    let new_transform =
+        GlobalTransform::from(Transform::from_translation(translation).with_rotation(rotation))
+            .reparented_to(&GlobalTransform::from(parent_transform));
+
+    transform.translation = new_transform.translation;
+    transform.rotation = new_transform.rotation;
+    transform
+}
+
+/// Resets the `Transform` of eased bodies to match their actual [`Position`] and [`Rotation`],
+/// so that gameplay code operates on the true physics state rather than the rendered one.
+///
+/// `GlobalTransform` and [`PreviousGlobalTransform`] are updated too, so that the reset
+/// is not mistaken for a `Transform` change made by the user.
+#[allow(clippy::type_complexity)]
+fn reset_eased_transforms(
+    mut query: Query<
+        (
+            &mut Transform,
+            &mut GlobalTransform,
+            Option<&mut PreviousGlobalTransform>,
+            &Position,
+            &Rotation,
+            &InterpolationState,
+            Option<&Parent>,
+        ),
+        Or<(With<TransformInterpolation>, With<TransformExtrapolation>)>,
+    >,
+    parents: Query<ParentComponents, (With<Children>, Without<InterpolationState>)>,
+) {
+    for (mut transform, mut global_transform, previous_transform, pos, rot, state, parent) in
+        &mut query
+    {
+        if state.end.is_none() {
+            continue;
+        }
+
+        let parent = match parent.map(|parent| parents.get(parent.get())) {
+            Some(Ok(parent)) => Some(parent),
+            Some(Err(_)) => continue,
+            None => None,
+        };
+        let new_transform = local_transform(*transform, pos.0, *rot, parent);
+        if new_transform == *transform {
+            continue;
+        }
+        *transform = new_transform;
+
+        let mut new_global_transform = global_transform.compute_transform();
+        #[cfg(feature = "2d")]
+        {
+            new_global_transform.translation = pos.f32().extend(new_global_transform.translation.z);
+            new_global_transform.rotation = Quaternion::from(*rot).f32();
+        }
+        #[cfg(feature = "3d")]
+        {
+            new_global_transform.translation = pos.f32();
+            new_global_transform.rotation = rot.f32();
+        }
+        *global_transform = GlobalTransform::from(new_global_transform);
+
+        if let Some(mut previous_transform) = previous_transform {
+            previous_transform.0 = *global_transform;
+        }
+    }
+}
+
+/// Returns the fraction of a physics step that has accumulated towards the next step
+/// and the corresponding amount of physics time in seconds, or `None` if the timestep is not fixed.
+fn overstep(time: &Time<Physics>) -> Option<(Scalar, Scalar)> {
+    let TimestepMode::Fixed {
+        delta, overstep, ..
+    } = time.timestep_mode()
+    else {
+        return None;
+    };
+
+    if delta.is_zero() {
+        return None;
+    }
+
+    let fraction = (overstep.as_secs_f64() / delta.as_secs_f64()).clamp(0.0, 1.0);
+    let seconds = overstep.as_secs_f64() * time.relative_speed_f64();
+    Some((fraction as Scalar, seconds as Scalar))
+}
+
+/// Returns the state to ease from, or `None` if the body should snap to its current state.
+///
+/// If the current physics state differs from the one recorded at the end of the last step,
+/// the body has been moved by something other than the simulation, like a teleport,
+/// and the stored states are reset.
+fn eased_state(
+    pos: &Position,
+    rot: &Rotation,
+    state: &mut InterpolationState,
+) -> Option<(Position, Rotation)> {
+    if state.end != Some((*pos, *rot)) {
+        if state.end.is_some() {
+            state.start = Some((*pos, *rot));
+            state.end = Some((*pos, *rot));
+        }
+        return None;
+    }
+    state.start
+}
+
+/// Interpolates the `Transform` of bodies with [`TransformInterpolation`]
+/// between the previous and current physics state.
+#[allow(clippy::type_complexity)]
+fn interpolate_transforms(
+    mut query: Query<
+        (
+            &mut Transform,
+            &Position,
+            &Rotation,
+            &mut InterpolationState,
+            Option<&Parent>,
+        ),
+        With<TransformInterpolation>,
+    >,
+    parents: Query<ParentComponents, With<Children>>,
+    time: Res<Time<Physics>>,
+) {
+    let Some((fraction, _)) = overstep(&time) else {
+        return;
+    };
+
+    for (mut transform, pos, rot, mut state, parent) in &mut query {
+        let Some((start_pos, start_rot)) = eased_state(pos, rot, &mut state) else {
+            continue;
+        };
+
+        let translation = start_pos.lerp(pos.0, fraction);
+        let rotation = start_rot.slerp(*rot, fraction);
+
+        let parent = match parent.map(|parent| parents.get(parent.get())) {
+            Some(Ok(parent)) => Some(parent),
+            Some(Err(_)) => continue,
+            None => None,
+        };
+        *transform = local_transform(*transform, translation, rotation, parent);
+    }
+}
+
+/// Extrapolates the `Transform` of bodies with [`TransformExtrapolation`]
+/// based on the current physics state and velocity.
+#[allow(clippy::type_complexity)]
+fn extrapolate_transforms(
+    mut query: Query<
+        (
+            &mut Transform,
+            &Position,
+            &Rotation,
+            &LinearVelocity,
+            &AngularVelocity,
+            &mut InterpolationState,
+            Option<&Parent>,
+        ),
+        (
+            With<TransformExtrapolation>,
+            Without<TransformInterpolation>,
+        ),
+    >,
+    parents: Query<ParentComponents, With<Children>>,
+    time: Res<Time<Physics>>,
+) {
+    let Some((_, seconds)) = overstep(&time) else {
+        return;
+    };
+
+    for (mut transform, pos, rot, lin_vel, ang_vel, mut state, parent) in &mut query {
+        if eased_state(pos, rot, &mut state).is_none() {
+            continue;
+        }
+
+        let translation = pos.0 + lin_vel.0 * seconds;
+        #[cfg(feature = "2d")]
+        let rotation = (*rot * Rotation::radians(ang_vel.0 * seconds)).normalize();
+        #[cfg(feature = "3d")]
+        let rotation =
+            Rotation((Quaternion::from_scaled_axis(ang_vel.0 * seconds) * rot.0).normalize());
+
+        let parent = match parent.map(|parent| parents.get(parent.get())) {
+            Some(Ok(parent)) => Some(parent),
+            Some(Err(_)) => continue,
+            None => None,
+        };
+        *transform = local_transform(*transform, translation, rotation, parent);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{create_app, tick};
+
+    #[test]
+    fn interpolation_eases_between_steps() {
+        let mut app = create_app();
+        app.insert_resource(Gravity::ZERO);
+
+        let entity = app
+            .world_mut()
+            .spawn((
+                RigidBody::Kinematic,
+                LinearVelocity(Vector::X * 60.0),
+                TransformInterpolation,
+            ))
+            .id();
+
+        // Run a few frames at 60 FPS so that the physics states are recorded.
+        for _ in 0..5 {
+            tick(&mut app, 1.0 / 60.0);
+        }
+
+        // Advance by half a physics step. No step is run, but the overstep accumulates.
+        tick(&mut app, 1.0 / 120.0);
+
+        let body = app.world().entity(entity);
+        let position = body.get::<Position>().unwrap().0;
+        let state = body.get::<InterpolationState>().unwrap();
+        let start = state.start.unwrap().0;
+        let translation = body.get::<Transform>().unwrap().translation;
+
+        assert!(translation.x > start.f32().x);
+        assert!(translation.x < position.f32().x);
+    }
+
+    #[test]
+    fn extrapolation_predicts_from_velocity() {
+        let mut app = create_app();
+        app.insert_resource(Gravity::ZERO);
+
+        let entity = app
+            .world_mut()
+            .spawn((
+                RigidBody::Kinematic,
+                LinearVelocity(Vector::X * 60.0),
+                TransformExtrapolation,
+            ))
+            .id();
+
+        for _ in 0..5 {
+            tick(&mut app, 1.0 / 60.0);
+        }
+
+        tick(&mut app, 1.0 / 120.0);
+
+        let body = app.world().entity(entity);
+        let position = body.get::<Position>().unwrap().0;
+        let translation = body.get::<Transform>().unwrap().translation;
+
+        assert!(translation.x > position.f32().x);
+    }
+
+    #[test]
+    fn teleport_snaps_instead_of_easing() {
+        let mut app = create_app();
+        app.insert_resource(Gravity::ZERO);
+
+        let entity = app
+            .world_mut()
+            .spawn((
+                RigidBody::Kinematic,
+                LinearVelocity(Vector::X * 60.0),
+                TransformInterpolation,
+            ))
+            .id();
+
+        for _ in 0..5 {
+            tick(&mut app, 1.0 / 60.0);
+        }
+
+        // Teleport the body, and advance by less than a step.
+        app.world_mut().get_mut::<Position>(entity).unwrap().y = 100.0;
+        tick(&mut app, 1.0 / 120.0);
+
+        let body = app.world().entity(entity);
+        let position = body.get::<Position>().unwrap().0;
+        let translation = body.get::<Transform>().unwrap().translation;
+
+        assert_eq!(position.y, 100.0);
+        assert_eq!(translation.y, 100.0);
+    }
+}
@@ -12,6 +12,7 @@ use bevy::{
 
 // TODO: Where should this be?
 pub mod ancestor_marker;
+pub mod interpolation;
 
 /// Responsible for synchronizing physics components with other data, like keeping [`Position`]
 /// and [`Rotation`] in sync with `Transform`.
@@ -33,6 +34,14 @@ pub mod ancestor_marker;
 ///
 /// If you would like a child entity to be rigidly attached to its parent, you could use a [`FixedJoint`]
 /// or write your own system to handle hierarchies differently.
+///
+/// ## Interpolation and extrapolation
+///
+/// With a fixed timestep, the physics state written to `Transform` can stutter when the frame rate
+/// and the physics tick rate diverge. To smooth out the rendered motion, bodies can opt in to
+/// [`TransformInterpolation`](interpolation::TransformInterpolation) or
+/// [`TransformExtrapolation`](interpolation::TransformExtrapolation).
+/// See [`PhysicsInterpolationPlugin`](interpolation::PhysicsInterpolationPlugin) for more information.
 pub struct SyncPlugin {
     schedule: Interned<dyn ScheduleLabel>,
 }
@@ -5,6 +5,7 @@ use crate::prelude::*;
 ))]
 use approx::assert_relative_eq;
 use bevy::{
+    app::PluginGroupBuilder,
     ecs::schedule::{LogLevel, ScheduleBuildSettings, ScheduleLabel},
     prelude::*,
     time::TimeUpdateStrategy,
@@ -30,14 +31,21 @@ macro_rules! setup_insta {
     };
 }
 
-fn create_app() -> App {
+pub(crate) fn create_app() -> App {
+    create_app_with_physics(
+        PhysicsPlugins::default()
+            .build()
+            .disable::<ColliderHierarchyPlugin>(),
+    )
+}
+
+/// Creates an app with the given physics plugins and a clock that is advanced manually with [`tick`].
+pub(crate) fn create_app_with_physics(physics_plugins: PluginGroupBuilder) -> App {
     let mut app = App::new();
     app.add_plugins((
         MinimalPlugins,
         TransformPlugin,
-        PhysicsPlugins::default()
-            .build()
-            .disable::<ColliderHierarchyPlugin>(),
+        physics_plugins,
         bevy::asset::AssetPlugin::default(),
         #[cfg(feature = "bevy_scene")]
         bevy::scene::ScenePlugin,
@@ -48,13 +56,18 @@ fn create_app() -> App {
     app
 }
 
-fn tick_60_fps(app: &mut App) {
+pub(crate) fn tick_60_fps(app: &mut App) {
+    tick(app, 1. / 60.);
+}
+
+/// Advances the clock of the app by the given number of seconds and runs an update.
+pub(crate) fn tick(app: &mut App, seconds: f64) {
     let mut update_strategy = app.world_mut().resource_mut::<TimeUpdateStrategy>();
     let TimeUpdateStrategy::ManualInstant(prev_time) = *update_strategy else {
         unimplemented!()
     };
     *update_strategy =
-        TimeUpdateStrategy::ManualInstant(prev_time + Duration::from_secs_f64(1. / 60.));
+        TimeUpdateStrategy::ManualInstant(prev_time + Duration::from_secs_f64(seconds));
     app.update();
 }
 
@@ -189,90 +202,2299 @@ fn body_with_velocity_moves() {
     }
 }
 
-#[derive(Component, Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Ord)]
-#[cfg(feature = "3d")]
-struct Id(usize);
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn revolute_joint_velocity_motor_spins_body() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+
+    let anchor = app.world_mut().spawn(RigidBody::Static).id();
+    let wheel = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            #[cfg(feature = "2d")]
+            MassPropertiesBundle::new_computed(&Collider::circle(0.5), 1.0),
+            #[cfg(feature = "3d")]
+            MassPropertiesBundle::new_computed(&Collider::sphere(0.5), 1.0),
+        ))
+        .id();
+    app.world_mut()
+        .spawn(RevoluteJoint::new(anchor, wheel).with_motor(JointMotor::velocity(2.0)));
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    let angular_velocity = app.world().get::<AngularVelocity>(wheel).unwrap();
+
+    #[cfg(feature = "2d")]
+    assert_relative_eq!(angular_velocity.0, 2.0, epsilon = 0.01);
+    #[cfg(feature = "3d")]
+    assert_relative_eq!(angular_velocity.z, 2.0, epsilon = 0.01);
+}
 
-#[cfg(all(feature = "3d", feature = "enhanced-determinism"))]
 #[test]
-fn cubes_simulation_is_deterministic_across_machines() {
-    setup_insta!();
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn revolute_joint_position_motor_takes_shortest_arc() {
     let mut app = create_app();
 
-    app.add_systems(Startup, setup_cubes_simulation);
+    app.insert_resource(Gravity::ZERO);
 
-    const SECONDS: usize = 10;
-    const UPDATES: usize = 60 * SECONDS;
+    // The target and the initial angle are on opposite sides of the wrap-around at pi,
+    // but only 0.28 radians apart.
+    let target = PI - 0.1;
+    let initial_angle = -PI + 0.18;
 
-    for _ in 0..UPDATES {
+    let anchor = app.world_mut().spawn(RigidBody::Static).id();
+    let wheel = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            #[cfg(feature = "2d")]
+            Rotation::radians(initial_angle),
+            #[cfg(feature = "3d")]
+            Rotation(Quaternion::from_rotation_z(initial_angle)),
+            #[cfg(feature = "2d")]
+            MassPropertiesBundle::new_computed(&Collider::circle(0.5), 1.0),
+            #[cfg(feature = "3d")]
+            MassPropertiesBundle::new_computed(&Collider::sphere(0.5), 1.0),
+        ))
+        .id();
+    app.world_mut().spawn(
+        RevoluteJoint::new(anchor, wheel)
+            .with_motor(JointMotor::position(target, 200.0, 20.0).with_max_force(1000.0)),
+    );
+
+    let angle = |app: &App| {
+        let direction = *app.world().get::<Rotation>(wheel).unwrap() * Vector::X;
+        direction.y.atan2(direction.x)
+    };
+
+    for _ in 0..180 {
         tick_60_fps(&mut app);
+
+        // The wheel should never turn the long way around through zero.
+        assert!(angle(&app).abs() > FRAC_PI_2);
     }
 
-    let mut app_query = app.world_mut().query::<(&Id, &Transform)>();
+    assert_relative_eq!(angle(&app), target, epsilon = 0.05);
+}
 
-    let mut bodies: Vec<(&Id, &Transform)> = app_query.iter(app.world()).collect();
-    bodies.sort_by_key(|b| b.0);
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn prismatic_joint_position_motor_reaches_target() {
+    let mut app = create_app();
 
-    assert_debug_snapshot!(bodies);
+    app.insert_resource(Gravity::ZERO);
+
+    let anchor = app.world_mut().spawn(RigidBody::Static).id();
+    let slider = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            #[cfg(feature = "2d")]
+            MassPropertiesBundle::new_computed(&Collider::circle(0.5), 1.0),
+            #[cfg(feature = "3d")]
+            MassPropertiesBundle::new_computed(&Collider::sphere(0.5), 1.0),
+        ))
+        .id();
+    app.world_mut().spawn(
+        PrismaticJoint::new(anchor, slider)
+            .with_motor(JointMotor::position(1.0, 200.0, 20.0).with_max_force(1000.0)),
+    );
+
+    for _ in 0..180 {
+        tick_60_fps(&mut app);
+    }
+
+    let position = app.world().get::<Position>(slider).unwrap();
+
+    assert_relative_eq!(position.x, 1.0, epsilon = 0.05);
+    assert_relative_eq!(position.y, 0.0, epsilon = 0.01);
 }
 
-#[cfg(all(feature = "3d", feature = "default-collider"))]
 #[test]
-fn cubes_simulation_is_locally_deterministic() {
-    use itertools::Itertools;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn breakable_joint_breaks_when_force_exceeds_threshold() {
+    let mut app = create_app();
 
-    fn run_cubes() -> Vec<(Id, Transform)> {
+    let anchor = app.world_mut().spawn(RigidBody::Static).id();
+    let mut spawn_hanging_body = |max_force: Scalar| {
+        let body = app
+            .world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                #[cfg(feature = "2d")]
+                MassPropertiesBundle::new_computed(&Collider::circle(0.5), 1.0),
+                #[cfg(feature = "3d")]
+                MassPropertiesBundle::new_computed(&Collider::sphere(0.5), 1.0),
+            ))
+            .id();
+        let joint = app
+            .world_mut()
+            .spawn((
+                FixedJoint::new(anchor, body),
+                BreakableJoint::new(max_force, Scalar::MAX),
+            ))
+            .id();
+        (body, joint)
+    };
+
+    // The weight of the bodies is less than 10 N.
+    let (weak_body, weak_joint) = spawn_hanging_body(1.0);
+    let (strong_body, strong_joint) = spawn_hanging_body(100.0);
+
+    tick_60_fps(&mut app);
+
+    let events = app.world().resource::<Events<JointBroken>>();
+    assert_eq!(events.len(), 1);
+
+    for _ in 0..30 {
+        tick_60_fps(&mut app);
+    }
+
+    assert!(app.world().get_entity(weak_joint).is_none());
+    assert!(app.world().get::<Position>(weak_body).unwrap().y < -0.1);
+
+    assert!(app.world().get_entity(strong_joint).is_some());
+    assert!(app.world().get::<Position>(strong_body).unwrap().y > -0.01);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn joint_forces_match_weight_of_hanging_body() {
+    let mut app = create_app();
+
+    let anchor = app.world_mut().spawn(RigidBody::Static).id();
+    let body = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            #[cfg(feature = "2d")]
+            MassPropertiesBundle::new_computed(&Collider::circle(0.5), 1.0),
+            #[cfg(feature = "3d")]
+            MassPropertiesBundle::new_computed(&Collider::sphere(0.5), 1.0),
+        ))
+        .id();
+    let joint = app
+        .world_mut()
+        .spawn((FixedJoint::new(anchor, body), JointForces::default()))
+        .id();
+
+    for _ in 0..30 {
+        tick_60_fps(&mut app);
+    }
+
+    let mass = app.world().get::<Mass>(body).unwrap().0;
+    let gravity = app.world().resource::<Gravity>().0;
+    let joint_forces = app.world().get::<JointForces>(joint).unwrap();
+
+    assert_relative_eq!(
+        joint_forces.force.length(),
+        mass * gravity.length(),
+        epsilon = 0.05 * mass * gravity.length()
+    );
+    assert!(joint_forces.force.x.abs() < 0.01);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn joint_forces_match_impulse_for_any_substep_count() {
+    use crate::dynamics::solver::{JointSolverMethod, SolverConfig};
+
+    // Returns the force reported by a joint that stops a moving body,
+    // and the force computed from the change in the body's momentum.
+    fn measure_forces(joint_solver: JointSolverMethod, substeps: u32) -> (Vector, Vector) {
         let mut app = create_app();
+        app.insert_resource(Gravity::ZERO);
+        app.insert_resource(SubstepCount(substeps));
+        app.insert_resource(SolverConfig {
+            joint_solver,
+            ..default()
+        });
 
-        app.add_systems(Startup, setup_cubes_simulation);
+        let anchor = app.world_mut().spawn(RigidBody::Static).id();
+        let body = app
+            .world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                LinearVelocity(Vector::X * 2.0),
+                #[cfg(feature = "2d")]
+                MassPropertiesBundle::new_computed(&Collider::circle(0.5), 1.0),
+                #[cfg(feature = "3d")]
+                MassPropertiesBundle::new_computed(&Collider::sphere(0.5), 1.0),
+            ))
+            .id();
+        let joint = app
+            .world_mut()
+            .spawn((FixedJoint::new(anchor, body), JointForces::default()))
+            .id();
 
-        const SECONDS: usize = 5;
-        const UPDATES: usize = 60 * SECONDS;
+        tick_60_fps(&mut app);
 
-        for _ in 0..UPDATES {
-            tick_60_fps(&mut app);
-        }
+        let mass = app.world().get::<Mass>(body).unwrap().0;
+        let velocity = app.world().get::<LinearVelocity>(body).unwrap().0;
+        // The reported force acts on the first body, so it opposes the change in the momentum of the second body.
+        let momentum_force = mass * (Vector::X * 2.0 - velocity) * 60.0;
+        let joint_force = app.world().get::<JointForces>(joint).unwrap().force;
 
-        let mut app_query = app.world_mut().query::<(&Id, &Transform)>();
+        (joint_force, momentum_force)
+    }
 
-        let mut bodies: Vec<(Id, Transform)> = app_query
-            .iter(app.world())
-            .map(|(id, transform)| (*id, *transform))
-            .collect();
-        bodies.sort_by_key(|b| b.0);
-        bodies
+    for joint_solver in [JointSolverMethod::Xpbd, JointSolverMethod::Impulse] {
+        let (force1, momentum_force1) = measure_forces(joint_solver, 1);
+        let (force4, momentum_force4) = measure_forces(joint_solver, 4);
+
+        // The joint force should match the impulse applied over the whole step,
+        // not just the last substep.
+        assert!(momentum_force1.length() > 1.0 && momentum_force4.length() > 1.0);
+        assert_relative_eq!(
+            force1,
+            momentum_force1,
+            epsilon = 0.05 * momentum_force1.length()
+        );
+        assert_relative_eq!(
+            force4,
+            momentum_force4,
+            epsilon = 0.05 * momentum_force4.length()
+        );
     }
+}
 
-    // run simulation and check that results are equal each time
-    for (a, b) in (0..4).map(|_| run_cubes()).tuple_windows() {
-        assert_eq!(a, b);
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn generic_joint_only_allows_free_axes() {
+    let mut app = create_app();
+
+    let anchor = app.world_mut().spawn(RigidBody::Static).id();
+    let body = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            LinearVelocity(Vector::X * 2.0),
+            #[cfg(feature = "2d")]
+            MassPropertiesBundle::new_computed(&Collider::circle(0.5), 1.0),
+            #[cfg(feature = "3d")]
+            MassPropertiesBundle::new_computed(&Collider::sphere(0.5), 1.0),
+        ))
+        .id();
+    app.world_mut()
+        .spawn(GenericJoint::new(anchor, body).with_linear_motion(JointAxis::Y, AxisMotion::Free));
+
+    for _ in 0..30 {
+        tick_60_fps(&mut app);
     }
+
+    // The body falls along the free Y axis, but can't move along the locked X axis.
+    let position = app.world().get::<Position>(body).unwrap();
+    assert!(position.x.abs() < 0.01);
+    assert!(position.y < -0.5);
 }
 
 #[test]
-fn no_ambiguity_errors() {
-    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
-    struct DeterministicSchedule;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn generic_joint_angular_motor_spins_body() {
+    let mut app = create_app();
 
-    App::new()
-        .add_plugins((
-            MinimalPlugins,
-            PhysicsPlugins::new(DeterministicSchedule)
-                .build()
-                .disable::<ColliderHierarchyPlugin>(),
-            bevy::asset::AssetPlugin::default(),
-            #[cfg(feature = "bevy_scene")]
-            bevy::scene::ScenePlugin,
+    app.insert_resource(Gravity::ZERO);
+
+    let anchor = app.world_mut().spawn(RigidBody::Static).id();
+    let body = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            #[cfg(feature = "2d")]
+            MassPropertiesBundle::new_computed(&Collider::circle(0.5), 1.0),
+            #[cfg(feature = "3d")]
+            MassPropertiesBundle::new_computed(&Collider::sphere(0.5), 1.0),
         ))
-        .init_resource::<Assets<Mesh>>()
-        .edit_schedule(DeterministicSchedule, |s| {
-            s.set_build_settings(ScheduleBuildSettings {
-                ambiguity_detection: LogLevel::Error,
-                ..default()
-            });
-        })
-        .add_systems(Update, |w: &mut World| {
-            w.run_schedule(DeterministicSchedule);
-        })
-        .update();
+        .id();
+
+    #[cfg(feature = "2d")]
+    let joint = GenericJoint::new(anchor, body)
+        .with_angular_motion(AxisMotion::Free)
+        .with_angular_motor(JointMotor::velocity(2.0));
+    #[cfg(feature = "3d")]
+    let joint = GenericJoint::new(anchor, body)
+        .with_angular_motion(JointAxis::Z, AxisMotion::Free)
+        .with_angular_motor(JointAxis::Z, JointMotor::velocity(2.0));
+
+    app.world_mut().spawn(joint);
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    let angular_velocity = app.world().get::<AngularVelocity>(body).unwrap();
+
+    #[cfg(feature = "2d")]
+    assert_relative_eq!(angular_velocity.0, 2.0, epsilon = 0.01);
+    #[cfg(feature = "3d")]
+    {
+        assert_relative_eq!(angular_velocity.z, 2.0, epsilon = 0.01);
+        assert!(angular_velocity.truncate().length() < 0.01);
+    }
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn disabled_joint_is_not_solved() {
+    let mut app = create_app();
+
+    let anchor = app.world_mut().spawn(RigidBody::Static).id();
+    let body = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            #[cfg(feature = "2d")]
+            MassPropertiesBundle::new_computed(&Collider::circle(0.5), 1.0),
+            #[cfg(feature = "3d")]
+            MassPropertiesBundle::new_computed(&Collider::sphere(0.5), 1.0),
+        ))
+        .id();
+    let joint = app
+        .world_mut()
+        .spawn((FixedJoint::new(anchor, body), JointDisabled))
+        .id();
+
+    for _ in 0..30 {
+        tick_60_fps(&mut app);
+    }
+
+    // The body falls freely while the joint is disabled.
+    assert!(app.world().get::<Position>(body).unwrap().y < -0.5);
+
+    app.world_mut().entity_mut(joint).remove::<JointDisabled>();
+
+    for _ in 0..30 {
+        tick_60_fps(&mut app);
+    }
+
+    // The enabled joint pulls the body back to the anchor.
+    assert!(app.world().get::<Position>(body).unwrap().length() < 0.1);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn joint_with_collision_disabled_ignores_contacts() {
+    let mut app = create_app();
+
+    let ground = app
+        .world_mut()
+        .spawn((
+            RigidBody::Static,
+            #[cfg(feature = "2d")]
+            Collider::rectangle(20.0, 1.0),
+            #[cfg(feature = "3d")]
+            Collider::cuboid(20.0, 1.0, 20.0),
+        ))
+        .id();
+    let mut spawn_tethered_ball = |x: Scalar, disable_collision: bool| {
+        let ball = app
+            .world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                #[cfg(feature = "2d")]
+                Collider::circle(0.5),
+                #[cfg(feature = "3d")]
+                Collider::sphere(0.5),
+                Position(Vector::X * x + Vector::Y * 2.0),
+            ))
+            .id();
+        app.world_mut().spawn(
+            DistanceJoint::new(ground, ball)
+                .with_limits(0.0, 10.0)
+                .with_collision_disabled(disable_collision),
+        );
+        ball
+    };
+
+    let colliding_ball = spawn_tethered_ball(-3.0, false);
+    let ghost_ball = spawn_tethered_ball(3.0, true);
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    // Only the ball whose joint has collisions disabled falls through the ground.
+    assert!(app.world().get::<Position>(colliding_ball).unwrap().y > 0.9);
+    assert!(app.world().get::<Position>(ghost_ball).unwrap().y < 0.0);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn removing_joint_restores_collisions() {
+    let mut app = create_app();
+
+    let ground = app
+        .world_mut()
+        .spawn((
+            RigidBody::Static,
+            #[cfg(feature = "2d")]
+            Collider::rectangle(20.0, 1.0),
+            #[cfg(feature = "3d")]
+            Collider::cuboid(20.0, 1.0, 20.0),
+        ))
+        .id();
+    let ball = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            #[cfg(feature = "2d")]
+            Collider::circle(0.5),
+            #[cfg(feature = "3d")]
+            Collider::sphere(0.5),
+            Position(Vector::Y * 2.0),
+        ))
+        .id();
+    let joint = app
+        .world_mut()
+        .spawn(DistanceJoint::new(ground, ball).with_limits(0.0, 10.0))
+        .id();
+
+    tick_60_fps(&mut app);
+
+    // Collisions between the bodies are disabled by default.
+    let filter = app.world().resource::<JointCollisionFilter>();
+    assert!(filter.contains(ground, ball));
+
+    app.world_mut().despawn(joint);
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    // The ball lands on the ground once the joint is gone.
+    let filter = app.world().resource::<JointCollisionFilter>();
+    assert!(filter.is_empty());
+    assert!(app.world().get::<Position>(ball).unwrap().y > 0.9);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn impulse_joints_hold_heavy_chain_together() {
+    use crate::dynamics::solver::{JointSolverMethod, SolverConfig};
+
+    // Returns the largest gap between joint anchors in a chain holding a heavy body.
+    fn max_chain_gap(joint_solver: JointSolverMethod) -> Scalar {
+        let mut app = create_app();
+        app.insert_resource(SolverConfig {
+            joint_solver,
+            ..default()
+        });
+
+        let mut previous = app.world_mut().spawn(RigidBody::Static).id();
+        let mut joints = Vec::new();
+
+        for i in 1..=5 {
+            // The last link is a hundred times heavier than the others.
+            let density = if i == 5 { 100.0 } else { 1.0 };
+            let link = app
+                .world_mut()
+                .spawn((
+                    RigidBody::Dynamic,
+                    Position(Vector::X * i as Scalar),
+                    #[cfg(feature = "2d")]
+                    MassPropertiesBundle::new_computed(&Collider::circle(0.25), density),
+                    #[cfg(feature = "3d")]
+                    MassPropertiesBundle::new_computed(&Collider::sphere(0.25), density),
+                ))
+                .id();
+            let joint = app
+                .world_mut()
+                .spawn(
+                    RevoluteJoint::new(previous, link)
+                        .with_local_anchor_1(Vector::X * 0.5)
+                        .with_local_anchor_2(Vector::NEG_X * 0.5),
+                )
+                .id();
+            joints.push(joint);
+            previous = link;
+        }
+
+        let mut max_gap: Scalar = 0.0;
+
+        for _ in 0..120 {
+            tick_60_fps(&mut app);
+
+            for &joint in &joints {
+                let joint = app.world().get::<RevoluteJoint>(joint).unwrap();
+                let [body1, body2] = [joint.entity1, joint.entity2].map(|entity| {
+                    let entity = app.world().entity(entity);
+                    (
+                        *entity.get::<Position>().unwrap(),
+                        *entity.get::<Rotation>().unwrap(),
+                    )
+                });
+                let anchor1 = body1.0 .0 + body1.1 * joint.local_anchor1;
+                let anchor2 = body2.0 .0 + body2.1 * joint.local_anchor2;
+                max_gap = max_gap.max(anchor1.distance(anchor2));
+            }
+        }
+
+        max_gap
+    }
+
+    let xpbd_gap = max_chain_gap(JointSolverMethod::Xpbd);
+    let impulse_gap = max_chain_gap(JointSolverMethod::Impulse);
+
+    assert!(impulse_gap < 0.05);
+    assert!(impulse_gap < xpbd_gap);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn soft_joint_oscillates_at_configured_frequency() {
+    use crate::dynamics::solver::{JointSolverMethod, SolverConfig};
+
+    const FREQUENCY: Scalar = 1.0;
+
+    // Returns the measured oscillation frequency of a body attached to a static body with a soft joint.
+    fn measure_frequency(joint_solver: JointSolverMethod, density: Scalar) -> Scalar {
+        let mut app = create_app();
+        app.insert_resource(Gravity::ZERO);
+        app.insert_resource(SolverConfig {
+            joint_solver,
+            ..default()
+        });
+
+        let anchor = app.world_mut().spawn(RigidBody::Static).id();
+        let body = app
+            .world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                Position(Vector::X * 0.5),
+                #[cfg(feature = "2d")]
+                MassPropertiesBundle::new_computed(&Collider::circle(0.5), density),
+                #[cfg(feature = "3d")]
+                MassPropertiesBundle::new_computed(&Collider::sphere(0.5), density),
+            ))
+            .id();
+        app.world_mut().spawn(
+            FixedJoint::new(anchor, body)
+                .with_linear_softness(SoftnessParameters::new(0.1, FREQUENCY))
+                .with_linear_velocity_damping(0.0)
+                .with_angular_velocity_damping(0.0),
+        );
+
+        // Record the times when the body crosses the rest position.
+        let mut crossings = Vec::new();
+        let mut previous_x: Scalar = 0.5;
+
+        for frame in 1..=240 {
+            tick_60_fps(&mut app);
+
+            let x = app.world().get::<Position>(body).unwrap().x;
+            if x.signum() != previous_x.signum() {
+                // Interpolate the time of the crossing within the frame.
+                let t = previous_x / (previous_x - x);
+                crossings.push((frame as Scalar - 1.0 + t) / 60.0);
+            }
+            previous_x = x;
+        }
+
+        assert!(crossings.len() >= 4, "the joint should oscillate");
+
+        // The rest position is crossed twice per period.
+        let half_period =
+            (crossings[crossings.len() - 1] - crossings[0]) / (crossings.len() - 1) as Scalar;
+        1.0 / (2.0 * half_period)
+    }
+
+    for joint_solver in [JointSolverMethod::Xpbd, JointSolverMethod::Impulse] {
+        // The frequency should not depend on the mass of the body.
+        for density in [1.0, 100.0] {
+            let frequency = measure_frequency(joint_solver, density);
+            assert!(
+                (frequency - FREQUENCY).abs() < 0.1 * FREQUENCY,
+                "{joint_solver:?} with density {density}: {frequency} Hz"
+            );
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn spawn_arm(app: &mut App, constructor: RagdollConstructor) -> (Entity, [Entity; 3]) {
+    let root = app
+        .world_mut()
+        .spawn((TransformBundle::default(), constructor))
+        .id();
+    let mut parent = root;
+    let bones = ["Upper", "Lower", "Hand"].map(|name| {
+        let offset = if parent == root { 0.0 } else { 1.0 };
+        parent = app
+            .world_mut()
+            .spawn((
+                Name::new(name),
+                TransformBundle::from_transform(Transform::from_xyz(offset, 0.0, 0.0)),
+            ))
+            .set_parent(parent)
+            .id();
+        parent
+    });
+    (root, bones)
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn ragdoll_constructor_connects_bones_with_joints() {
+    let mut app = create_app();
+
+    let (root, [upper, lower, hand]) = spawn_arm(
+        &mut app,
+        RagdollConstructor::new(RagdollBoneConfig::new(0.2)),
+    );
+
+    tick_60_fps(&mut app);
+
+    let ragdoll = app.world().get::<Ragdoll>(root).unwrap().clone();
+    assert_eq!(ragdoll.bodies.len(), 3);
+    assert!(app.world().get::<RagdollConstructor>(root).is_none());
+
+    #[cfg(feature = "2d")]
+    let joint_count = app
+        .world_mut()
+        .query::<&RevoluteJoint>()
+        .iter(app.world())
+        .count();
+    #[cfg(feature = "3d")]
+    let joint_count = app
+        .world_mut()
+        .query::<&SphericalJoint>()
+        .iter(app.world())
+        .count();
+    assert_eq!(joint_count, 2);
+
+    for _ in 0..30 {
+        tick_60_fps(&mut app);
+    }
+
+    // The bodies fall, but stay connected.
+    let positions = ragdoll
+        .bodies
+        .iter()
+        .map(|body| app.world().get::<Position>(*body).unwrap().0)
+        .collect::<Vec<_>>();
+    assert!(positions[2].y < -0.5);
+    assert_relative_eq!(positions[0].distance(positions[1]), 1.0, epsilon = 0.05);
+    assert_relative_eq!(positions[1].distance(positions[2]), 1.0, epsilon = 0.05);
+
+    // The bones follow the bodies.
+    for (bone, position) in [upper, lower, hand].iter().zip(positions) {
+        let translation = app
+            .world()
+            .get::<GlobalTransform>(*bone)
+            .unwrap()
+            .translation();
+        #[cfg(feature = "2d")]
+        assert_relative_eq!(
+            translation.truncate().adjust_precision(),
+            position,
+            epsilon = 0.01
+        );
+        #[cfg(feature = "3d")]
+        assert_relative_eq!(translation.adjust_precision(), position, epsilon = 0.01);
+    }
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn animated_ragdoll_follows_bone_poses() {
+    let mut app = create_app();
+
+    let (root, [upper, ..]) = spawn_arm(
+        &mut app,
+        RagdollConstructor::new(RagdollBoneConfig::new(0.2)).with_blend(0.0),
+    );
+
+    tick_60_fps(&mut app);
+
+    let bodies = app.world().get::<Ragdoll>(root).unwrap().bodies.clone();
+    assert_eq!(
+        app.world().get::<RigidBody>(bodies[0]),
+        Some(&RigidBody::Kinematic)
+    );
+
+    // Animate the upper bone, moving the whole arm.
+    app.world_mut()
+        .get_mut::<Transform>(upper)
+        .unwrap()
+        .translation
+        .y = 2.0;
+
+    for _ in 0..5 {
+        tick_60_fps(&mut app);
+    }
+
+    // The kinematic bodies follow the animated pose, and the bones are left untouched.
+    assert_relative_eq!(
+        app.world().get::<Position>(bodies[0]).unwrap().y,
+        2.0,
+        epsilon = 0.001
+    );
+    assert_relative_eq!(
+        app.world().get::<Position>(bodies[2]).unwrap().y,
+        2.0,
+        epsilon = 0.001
+    );
+    assert_eq!(
+        app.world().get::<Transform>(upper).unwrap().translation.y,
+        2.0
+    );
+
+    // Switch to the simulated pose.
+    app.world_mut().get_mut::<Ragdoll>(root).unwrap().blend = 1.0;
+
+    for _ in 0..30 {
+        tick_60_fps(&mut app);
+    }
+
+    assert_eq!(
+        app.world().get::<RigidBody>(bodies[0]),
+        Some(&RigidBody::Dynamic)
+    );
+    assert!(app.world().get::<Position>(bodies[0]).unwrap().y < 1.5);
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn spawn_box(
+    app: &mut App,
+    body: RigidBody,
+    position: Vector,
+    width: Scalar,
+    height: Scalar,
+) -> Entity {
+    app.world_mut()
+        .spawn((
+            body,
+            #[cfg(feature = "2d")]
+            Collider::rectangle(width, height),
+            #[cfg(feature = "3d")]
+            Collider::cuboid(width, height, 20.0),
+            Position(position),
+        ))
+        .id()
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn spawn_character(app: &mut App, position: Vector, controller: CharacterController) -> Entity {
+    // The capsule extends 0.9 units from its center along the y-axis.
+    app.world_mut()
+        .spawn((
+            RigidBody::Kinematic,
+            Collider::capsule(0.4, 1.0),
+            Position(position),
+            controller,
+        ))
+        .id()
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn character_controller_slides_along_wall() {
+    let mut app = create_app();
+
+    let wall = spawn_box(&mut app, RigidBody::Static, Vector::X * 2.5, 1.0, 20.0);
+    let character = spawn_character(&mut app, Vector::ZERO, CharacterController::default());
+
+    tick_60_fps(&mut app);
+    app.world_mut()
+        .get_mut::<CharacterVelocity>(character)
+        .unwrap()
+        .0 = Vector::X * 4.0 + Vector::Y * 2.0;
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    // The character is stopped by the wall, but keeps moving along it.
+    let position = app.world().get::<Position>(character).unwrap().0;
+    assert!(position.x < 1.6);
+    assert!(position.x > 1.5);
+    assert!(position.y > 1.8);
+
+    let state = app
+        .world()
+        .get::<CharacterControllerState>(character)
+        .unwrap();
+    assert!(!state.grounded);
+    assert_eq!(state.collisions.len(), 1);
+    assert_eq!(state.collisions[0].entity, wall);
+    assert_relative_eq!(state.collisions[0].normal, -Vector::X, epsilon = 0.001);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn character_controller_detects_ground() {
+    let mut app = create_app();
+
+    let ground = spawn_box(&mut app, RigidBody::Static, -Vector::Y * 0.5, 20.0, 1.0);
+    let character = spawn_character(&mut app, Vector::Y * 2.0, CharacterController::default());
+
+    tick_60_fps(&mut app);
+    app.world_mut()
+        .get_mut::<CharacterVelocity>(character)
+        .unwrap()
+        .0 = -Vector::Y * 5.0;
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    // The character lands on the ground, keeping a small gap to it.
+    let position = app.world().get::<Position>(character).unwrap().0;
+    assert_relative_eq!(position.y, 0.91, epsilon = 0.005);
+
+    let state = app
+        .world()
+        .get::<CharacterControllerState>(character)
+        .unwrap();
+    assert!(state.grounded);
+    let ground_collision = state.ground.unwrap();
+    assert_eq!(ground_collision.entity, ground);
+    assert_relative_eq!(ground_collision.normal, Vector::Y, epsilon = 0.001);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn character_controller_steps_up_small_obstacle() {
+    let mut app = create_app();
+
+    spawn_box(&mut app, RigidBody::Static, -Vector::Y * 0.5, 20.0, 1.0);
+    // A step that is 0.2 units high.
+    spawn_box(
+        &mut app,
+        RigidBody::Static,
+        Vector::X * 4.0 + Vector::Y * 0.1,
+        4.0,
+        0.2,
+    );
+    let character = spawn_character(
+        &mut app,
+        Vector::Y * 0.91,
+        CharacterController::default().with_max_step_height(0.3),
+    );
+
+    tick_60_fps(&mut app);
+    app.world_mut()
+        .get_mut::<CharacterVelocity>(character)
+        .unwrap()
+        .0 = Vector::X * 3.0;
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    // The character is standing on top of the step.
+    let position = app.world().get::<Position>(character).unwrap().0;
+    assert!(position.x > 2.5);
+    assert_relative_eq!(position.y, 1.11, epsilon = 0.02);
+    assert!(
+        app.world()
+            .get::<CharacterControllerState>(character)
+            .unwrap()
+            .grounded
+    );
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn character_controller_moves_with_platform() {
+    let mut app = create_app();
+
+    let platform = spawn_box(&mut app, RigidBody::Kinematic, -Vector::Y * 0.5, 4.0, 1.0);
+    app.world_mut()
+        .entity_mut(platform)
+        .insert(LinearVelocity(Vector::X));
+    let character = spawn_character(&mut app, Vector::Y * 0.91, CharacterController::default());
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    // The character is carried along by the platform.
+    let position = app.world().get::<Position>(character).unwrap().0;
+    let platform_position = app.world().get::<Position>(platform).unwrap().0;
+    assert_relative_eq!(position.x, platform_position.x, epsilon = 0.05);
+    assert_relative_eq!(position.y, 0.91, epsilon = 0.005);
+    assert!(
+        app.world()
+            .get::<CharacterControllerState>(character)
+            .unwrap()
+            .grounded
+    );
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn spawn_vehicle(app: &mut App) -> (Entity, Vec<Entity>) {
+    spawn_box(app, RigidBody::Static, -Vector::Y * 0.5, 100.0, 1.0);
+    let chassis = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            #[cfg(feature = "2d")]
+            Collider::rectangle(4.0, 0.5),
+            #[cfg(feature = "3d")]
+            Collider::cuboid(2.0, 0.5, 4.0),
+            Position(Vector::Y),
+        ))
+        .id();
+
+    #[cfg(feature = "2d")]
+    let anchors = [Vector::new(-1.5, -0.25), Vector::new(1.5, -0.25)];
+    #[cfg(feature = "3d")]
+    let anchors = [
+        Vector::new(-0.8, -0.25, -1.5),
+        Vector::new(0.8, -0.25, -1.5),
+        Vector::new(-0.8, -0.25, 1.5),
+        Vector::new(0.8, -0.25, 1.5),
+    ];
+    let wheels = anchors
+        .into_iter()
+        .map(|anchor| app.world_mut().spawn(Wheel::new(chassis, anchor, 0.4)).id())
+        .collect();
+
+    (chassis, wheels)
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn wheel_suspension_supports_chassis() {
+    let mut app = create_app();
+    let (chassis, wheels) = spawn_vehicle(&mut app);
+
+    for _ in 0..180 {
+        tick_60_fps(&mut app);
+    }
+
+    // Each wheel carries an equal share of the weight, compressing the suspension
+    // by `weight / stiffness` from its rest length.
+    let mass = app.world().get::<Mass>(chassis).unwrap().0;
+    let wheel_force = mass * 9.81 / wheels.len() as Scalar;
+    let compression = wheel_force / 100.0;
+
+    for &wheel in &wheels {
+        let state = app.world().get::<WheelState>(wheel).unwrap();
+        assert!(state.contact.is_some());
+        assert_relative_eq!(state.suspension_force, wheel_force, epsilon = 0.2);
+        assert_relative_eq!(state.suspension_length, 0.3 - compression, epsilon = 0.01);
+    }
+
+    let position = app.world().get::<Position>(chassis).unwrap().0;
+    assert_relative_eq!(position.y, 0.25 + 0.3 - compression + 0.4, epsilon = 0.01);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn wheel_drive_and_brake_torques_move_vehicle() {
+    let mut app = create_app();
+    let (chassis, wheels) = spawn_vehicle(&mut app);
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    for &wheel in &wheels {
+        app.world_mut()
+            .get_mut::<Wheel>(wheel)
+            .unwrap()
+            .drive_torque = 5.0;
+    }
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    // The vehicle drives forward with the wheels rolling on the ground.
+    #[cfg(feature = "2d")]
+    let forward = Vector::X;
+    #[cfg(feature = "3d")]
+    let forward = Vector::NEG_Z;
+    let speed = app
+        .world()
+        .get::<LinearVelocity>(chassis)
+        .unwrap()
+        .dot(forward);
+    assert!(speed > 0.5);
+
+    for &wheel in &wheels {
+        let state = app.world().get::<WheelState>(wheel).unwrap();
+        assert_relative_eq!(state.angular_velocity * 0.4, speed, epsilon = 0.1);
+
+        let mut wheel = app.world_mut().get_mut::<Wheel>(wheel).unwrap();
+        wheel.drive_torque = 0.0;
+        wheel.brake_torque = 20.0;
+    }
+
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+
+    // The brakes stop the vehicle.
+    let velocity = app.world().get::<LinearVelocity>(chassis).unwrap();
+    assert!(velocity.length() < 0.05);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn body_floats_in_fluid_volume() {
+    let mut app = create_app();
+
+    // A pool of water with its surface at y = 0.
+    app.world_mut().spawn((
+        FluidVolume::new(1.0).with_drag(4.0, 4.0),
+        #[cfg(feature = "2d")]
+        Collider::rectangle(20.0, 10.0),
+        #[cfg(feature = "3d")]
+        Collider::cuboid(20.0, 10.0, 20.0),
+        Sensor,
+        Position(Vector::NEG_Y * 5.0),
+    ));
+
+    let light_body = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            #[cfg(feature = "2d")]
+            Collider::rectangle(1.0, 1.0),
+            #[cfg(feature = "3d")]
+            Collider::cuboid(1.0, 1.0, 1.0),
+            ColliderDensity(0.5),
+            Position(Vector::Y * 2.0 - Vector::X * 2.0),
+        ))
+        .id();
+    let heavy_body = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            #[cfg(feature = "2d")]
+            Collider::rectangle(1.0, 1.0),
+            #[cfg(feature = "3d")]
+            Collider::cuboid(1.0, 1.0, 1.0),
+            ColliderDensity(2.0),
+            Position(Vector::Y * 2.0 + Vector::X * 2.0),
+        ))
+        .id();
+
+    for _ in 0..300 {
+        tick_60_fps(&mut app);
+    }
+
+    // The body with half of the density of the fluid floats half submerged,
+    // and the body with a higher density sinks.
+    let light_position = app.world().get::<Position>(light_body).unwrap().0;
+    assert_relative_eq!(light_position.y, 0.0, epsilon = 0.02);
+    let heavy_position = app.world().get::<Position>(heavy_body).unwrap().0;
+    assert!(heavy_position.y < -3.0);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn fluid_volume_wakes_up_sleeping_body() {
+    let mut app = create_app();
+
+    // The ground is at y = -5.0.
+    app.world_mut().spawn((
+        RigidBody::Static,
+        #[cfg(feature = "2d")]
+        Collider::rectangle(20.0, 1.0),
+        #[cfg(feature = "3d")]
+        Collider::cuboid(20.0, 1.0, 20.0),
+        Position(Vector::NEG_Y * 5.5),
+    ));
+
+    let body = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            #[cfg(feature = "2d")]
+            Collider::rectangle(1.0, 1.0),
+            #[cfg(feature = "3d")]
+            Collider::cuboid(1.0, 1.0, 1.0),
+            ColliderDensity(0.5),
+            Position(Vector::NEG_Y * 4.5),
+        ))
+        .id();
+
+    // Let the body fall asleep on the ground.
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+    assert!(app.world().get::<Sleeping>(body).is_some());
+
+    // A pool of water that the sleeping body is submerged in.
+    app.world_mut().spawn((
+        FluidVolume::new(1.0),
+        #[cfg(feature = "2d")]
+        Collider::rectangle(20.0, 10.0),
+        #[cfg(feature = "3d")]
+        Collider::cuboid(20.0, 10.0, 20.0),
+        Sensor,
+        Position(Vector::NEG_Y * 5.0),
+    ));
+
+    for _ in 0..10 {
+        tick_60_fps(&mut app);
+    }
+
+    assert!(app.world().get::<Sleeping>(body).is_none());
+    assert!(app.world().get::<Position>(body).unwrap().y > -4.4);
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn radial_gravity_and_gravity_override() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    app.world_mut().spawn((
+        RigidBody::Static,
+        Position::default(),
+        RadialGravity::new(10.0, 1.0),
+    ));
+
+    let attracted_body = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            #[cfg(feature = "2d")]
+            Collider::circle(0.5),
+            #[cfg(feature = "3d")]
+            Collider::sphere(0.5),
+            Position(Vector::X * 10.0),
+        ))
+        .id();
+    let overridden_body = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            #[cfg(feature = "2d")]
+            Collider::circle(0.5),
+            #[cfg(feature = "3d")]
+            Collider::sphere(0.5),
+            Position(Vector::NEG_X * 10.0),
+            GravityOverride(Vector::Y * 5.0),
+        ))
+        .id();
+
+    tick_60_fps(&mut app);
+
+    // The acceleration follows the inverse-square law outside of the radius.
+    let local_gravity = app.world().get::<LocalGravity>(attracted_body).unwrap();
+    assert_relative_eq!(local_gravity.get(), Vector::NEG_X * 0.1, epsilon = 0.0001);
+    let local_gravity = app.world().get::<LocalGravity>(overridden_body).unwrap();
+    assert_eq!(local_gravity.get(), Vector::Y * 5.0);
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    let position = app.world().get::<Position>(attracted_body).unwrap().0;
+    assert!(position.x < 10.0);
+    assert_relative_eq!(position.y, 0.0, epsilon = 0.0001);
+    let position = app.world().get::<Position>(overridden_body).unwrap().0;
+    assert!(position.y > 2.0);
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn gravity_zones_replace_global_gravity() {
+    let mut app = create_app();
+
+    // A zero-g zone, and a zone with upwards gravity and a higher priority overlapping its right half.
+    for (zone, position) in [
+        (GravityZone::ZERO, Vector::ZERO),
+        (
+            GravityZone::new(Vector::Y * 5.0).with_priority(1),
+            Vector::X * 5.0,
+        ),
+    ] {
+        app.world_mut().spawn((
+            zone,
+            #[cfg(feature = "2d")]
+            Collider::rectangle(10.0, 10.0),
+            #[cfg(feature = "3d")]
+            Collider::cuboid(10.0, 10.0, 10.0),
+            Sensor,
+            Position(position),
+        ));
+    }
+
+    let mut spawn_body = |position: Vector| {
+        app.world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                #[cfg(feature = "2d")]
+                Collider::circle(0.5),
+                #[cfg(feature = "3d")]
+                Collider::sphere(0.5),
+                Position(position),
+            ))
+            .id()
+    };
+    let floating_body = spawn_body(Vector::NEG_X * 2.0);
+    let rising_body = spawn_body(Vector::X * 2.0);
+    let falling_body = spawn_body(Vector::X * 20.0);
+
+    for _ in 0..30 {
+        tick_60_fps(&mut app);
+    }
+
+    let position = app.world().get::<Position>(floating_body).unwrap().0;
+    assert_relative_eq!(position.y, 0.0, epsilon = 0.0001);
+    let position = app.world().get::<Position>(rising_body).unwrap().0;
+    assert!(position.y > 0.1);
+    let position = app.world().get::<Position>(falling_body).unwrap().0;
+    assert!(position.y < -0.1);
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn removing_last_gravity_field_restores_global_gravity() {
+    let mut app = create_app();
+
+    let body = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            #[cfg(feature = "2d")]
+            Collider::circle(0.5),
+            #[cfg(feature = "3d")]
+            Collider::sphere(0.5),
+            GravityOverride(Vector::ZERO),
+            SleepingDisabled,
+        ))
+        .id();
+
+    for _ in 0..5 {
+        tick_60_fps(&mut app);
+    }
+    assert_eq!(
+        app.world().get::<LocalGravity>(body).unwrap().get(),
+        Vector::ZERO
+    );
+
+    app.world_mut().entity_mut(body).remove::<GravityOverride>();
+    tick_60_fps(&mut app);
+
+    let gravity = app.world().resource::<Gravity>().0;
+    assert_eq!(
+        app.world().get::<LocalGravity>(body).unwrap().get(),
+        gravity
+    );
+
+    // Changing the global gravity without any gravity fields also updates the body.
+    app.insert_resource(Gravity(Vector::X));
+    tick_60_fps(&mut app);
+    assert_eq!(
+        app.world().get::<LocalGravity>(body).unwrap().get(),
+        Vector::X
+    );
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn changed_gravity_wakes_up_sleeping_body() {
+    let mut app = create_app();
+
+    let body = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            #[cfg(feature = "2d")]
+            Collider::circle(0.5),
+            #[cfg(feature = "3d")]
+            Collider::sphere(0.5),
+            GravityOverride(Vector::ZERO),
+        ))
+        .id();
+
+    for _ in 0..5 {
+        tick_60_fps(&mut app);
+    }
+    app.world_mut().entity_mut(body).insert(Sleeping);
+    tick_60_fps(&mut app);
+    assert!(app.world().get::<Sleeping>(body).is_some());
+
+    // Replacing the override with radial gravity changes the gravity of the body.
+    app.world_mut().entity_mut(body).remove::<GravityOverride>();
+    app.world_mut()
+        .spawn((Position(Vector::X * 10.0), RadialGravity::new(10.0, 1.0)));
+    tick_60_fps(&mut app);
+    tick_60_fps(&mut app);
+
+    assert!(app.world().get::<Sleeping>(body).is_none());
+    assert!(app.world().get::<Position>(body).unwrap().x > 0.0);
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn aerodynamic_drag_limits_falling_speed() {
+    let mut app = create_app();
+
+    let body = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            #[cfg(feature = "2d")]
+            Collider::rectangle(1.0, 1.0),
+            #[cfg(feature = "3d")]
+            Collider::cuboid(1.0, 1.0, 1.0),
+            Aerodynamics::new(0.5),
+        ))
+        .id();
+
+    for _ in 0..300 {
+        tick_60_fps(&mut app);
+    }
+
+    // At terminal velocity, drag is equal to the weight of the body:
+    // 0.5 * density * speed^2 * drag_coefficient * area = mass * gravity
+    let atmosphere = *app.world().resource::<Atmosphere>();
+    let terminal_speed = (2.0 * 9.81 / (atmosphere.density * 0.5)).sqrt();
+    let lin_vel = app.world().get::<LinearVelocity>(body).unwrap();
+    assert_relative_eq!(lin_vel.y, -terminal_speed, epsilon = 0.05);
+    let forces = app.world().get::<AerodynamicForces>(body).unwrap();
+    assert_relative_eq!(forces.drag.y, 9.81, epsilon = 0.05);
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn lift_surface_generates_lift() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    // A wing at the center of mass, moving forwards and slightly downwards.
+    let body = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            #[cfg(feature = "2d")]
+            Collider::rectangle(1.0, 0.1),
+            #[cfg(feature = "3d")]
+            Collider::cuboid(1.0, 0.1, 1.0),
+            Aerodynamics::new(0.0).with_lift_surface(LiftSurface::new(
+                Vector::ZERO,
+                Vector::X,
+                Vector::Y,
+                1.0,
+            )),
+            ColliderDensity(100.0),
+            #[cfg(feature = "2d")]
+            LinearVelocity(Vector::new(10.0, -1.0)),
+            #[cfg(feature = "3d")]
+            LinearVelocity(Vector::new(10.0, -1.0, 0.0)),
+        ))
+        .id();
+
+    tick_60_fps(&mut app);
+
+    // The lift is perpendicular to the airflow, and pushes the wing upwards.
+    let forces = *app.world().get::<AerodynamicForces>(body).unwrap();
+    let lin_vel = app.world().get::<LinearVelocity>(body).unwrap().0;
+    assert!(forces.lift.y > 0.0);
+    assert_relative_eq!(
+        forces.lift.normalize().dot(lin_vel.normalize()),
+        0.0,
+        epsilon = 0.01
+    );
+    assert!(forces.drag.dot(lin_vel) < 0.0);
+    assert!(lin_vel.y > -1.0);
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn rolling_friction_stops_rolling_ball() {
+    let mut app = create_app();
+
+    spawn_box(&mut app, RigidBody::Static, Vector::NEG_Y * 0.5, 100.0, 1.0);
+
+    let mut spawn_ball = |x: Scalar, friction: Friction| {
+        app.world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                #[cfg(feature = "2d")]
+                Collider::circle(0.5),
+                #[cfg(feature = "3d")]
+                Collider::sphere(0.5),
+                Position(Vector::X * x + Vector::Y * 0.5),
+                LinearVelocity(Vector::X * 2.0),
+                #[cfg(feature = "2d")]
+                AngularVelocity(-4.0),
+                #[cfg(feature = "3d")]
+                AngularVelocity(Vector::NEG_Z * 4.0),
+                friction,
+            ))
+            .id()
+    };
+
+    // The ground has no rolling friction, so the combined coefficient is half of the ball's.
+    let rolling_ball = spawn_ball(-20.0, Friction::new(0.5).with_rolling_coefficient(0.1));
+    let free_ball = spawn_ball(20.0, Friction::new(0.5));
+
+    for _ in 0..300 {
+        tick_60_fps(&mut app);
+    }
+
+    let lin_vel = app.world().get::<LinearVelocity>(rolling_ball).unwrap();
+    assert!(lin_vel.length() < 0.05);
+    let lin_vel = app.world().get::<LinearVelocity>(free_ball).unwrap();
+    assert!(lin_vel.x > 1.5);
+}
+
+#[cfg(all(
+    feature = "3d",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn spinning_friction_stops_spinning_ball() {
+    let mut app = create_app();
+
+    spawn_box(&mut app, RigidBody::Static, Vector::NEG_Y * 0.5, 100.0, 1.0);
+
+    let mut spawn_ball = |position: Vector, friction: Friction| {
+        app.world_mut()
+            .spawn((
+                RigidBody::Dynamic,
+                Collider::sphere(0.5),
+                Position(position),
+                AngularVelocity(Vector::Y * 10.0),
+                friction,
+            ))
+            .id()
+    };
+
+    let spinning_ball = spawn_ball(
+        Vector::new(-20.0, 0.5, 0.0),
+        Friction::new(0.5).with_spinning_coefficient(0.1),
+    );
+    let free_ball = spawn_ball(Vector::new(20.0, 0.5, 0.0), Friction::new(0.5));
+
+    for _ in 0..300 {
+        tick_60_fps(&mut app);
+    }
+
+    // Regular friction can't stop a ball spinning around the contact normal.
+    let ang_vel = app.world().get::<AngularVelocity>(spinning_ball).unwrap();
+    assert!(ang_vel.length() < 0.05);
+    let ang_vel = app.world().get::<AngularVelocity>(free_ball).unwrap();
+    assert!(ang_vel.y > 5.0);
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn surface_velocity_moves_bodies_like_conveyor_belt() {
+    let mut app = create_app();
+
+    let belt = spawn_box(&mut app, RigidBody::Static, Vector::NEG_Y * 0.5, 100.0, 1.0);
+    app.world_mut()
+        .entity_mut(belt)
+        .insert(SurfaceVelocity(Vector::X * 2.0));
+    let body = spawn_box(&mut app, RigidBody::Dynamic, Vector::Y * 0.5, 1.0, 1.0);
+
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+
+    // The belt doesn't move, but the body is carried along its surface.
+    assert_eq!(app.world().get::<Position>(belt).unwrap().x, 0.0);
+    let lin_vel = app.world().get::<LinearVelocity>(body).unwrap();
+    assert_relative_eq!(lin_vel.x, 2.0, epsilon = 0.01);
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn anisotropic_friction_only_slides_along_primary_direction() {
+    let mut app = create_app();
+
+    // The first ground is slippery along its x-axis, and the second ground is rotated
+    // so that the slippery direction points along the contact normal.
+    let slippery_ground = spawn_box(&mut app, RigidBody::Static, Vector::NEG_Y * 0.5, 100.0, 1.0);
+    let rough_ground = spawn_box(&mut app, RigidBody::Static, Vector::Y * 9.5, 100.0, 1.0);
+    app.world_mut()
+        .entity_mut(slippery_ground)
+        .insert(AnisotropicFriction::new(Vector::X, 0.0, 1.0));
+    app.world_mut()
+        .entity_mut(rough_ground)
+        .insert(AnisotropicFriction::new(Vector::Y, 0.0, 1.0));
+
+    let slippery_body = spawn_box(&mut app, RigidBody::Dynamic, Vector::Y * 0.5, 1.0, 1.0);
+    let rough_body = spawn_box(&mut app, RigidBody::Dynamic, Vector::Y * 10.5, 1.0, 1.0);
+    for body in [slippery_body, rough_body] {
+        app.world_mut()
+            .entity_mut(body)
+            .insert(LinearVelocity(Vector::X * 3.0));
+    }
+
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+
+    let lin_vel = app.world().get::<LinearVelocity>(slippery_body).unwrap();
+    assert_relative_eq!(lin_vel.x, 3.0, epsilon = 0.01);
+    let lin_vel = app.world().get::<LinearVelocity>(rough_body).unwrap();
+    assert!(lin_vel.x.abs() < 0.01);
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn anisotropic_friction_uses_smaller_scale_of_both_colliders() {
+    let mut app = create_app();
+
+    // Each pair has one slippery collider and one collider with anisotropic friction that doesn't
+    // scale friction at all, so the result must not depend on the order of the colliders.
+    let slippery = AnisotropicFriction::new(Vector::X, 0.0, 1.0);
+    let rough = AnisotropicFriction::new(Vector::X, 1.0, 1.0);
+
+    let mut bodies = vec![];
+    for (i, (ground_friction, body_friction)) in [(slippery, rough), (rough, slippery)]
+        .into_iter()
+        .enumerate()
+    {
+        let y = i as Scalar * 10.0;
+        let ground = spawn_box(
+            &mut app,
+            RigidBody::Static,
+            Vector::Y * (y - 0.5),
+            100.0,
+            1.0,
+        );
+        let body = spawn_box(
+            &mut app,
+            RigidBody::Dynamic,
+            Vector::Y * (y + 0.5),
+            1.0,
+            1.0,
+        );
+        app.world_mut().entity_mut(ground).insert(ground_friction);
+        app.world_mut()
+            .entity_mut(body)
+            .insert((body_friction, LinearVelocity(Vector::X * 3.0)));
+        bodies.push(body);
+    }
+
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+
+    for body in bodies {
+        let lin_vel = app.world().get::<LinearVelocity>(body).unwrap();
+        assert_relative_eq!(lin_vel.x, 3.0, epsilon = 0.01);
+    }
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    feature = "physics-material",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn physics_materials_and_material_pair_overrides() {
+    let mut app = create_app();
+
+    // Physics material assets are initialized when the plugins are finished.
+    app.finish();
+
+    let mut materials = app.world_mut().resource_mut::<Assets<PhysicsMaterial>>();
+    let ice = materials.add(PhysicsMaterial::new(
+        Friction::ZERO.with_combine_rule(CoefficientCombine::Min),
+        0.0,
+        2.0,
+    ));
+    let rubber = materials.add(PhysicsMaterial::new(1.0, 0.0, 1.0));
+    let tire = materials.add(PhysicsMaterial::new(1.0, 0.0, 1.0));
+
+    // Tires don't grip rubber at all.
+    app.world_mut()
+        .resource_mut::<PhysicsMaterialPairs>()
+        .insert(
+            &rubber,
+            &tire,
+            PhysicsMaterialPair::default().with_friction(0.0),
+        );
+
+    let ground = spawn_box(&mut app, RigidBody::Static, Vector::NEG_Y * 0.5, 100.0, 1.0);
+    app.world_mut().entity_mut(ground).insert(rubber.clone());
+
+    let mut spawn_body = |x: Scalar, material: Handle<PhysicsMaterial>| {
+        let body = spawn_box(
+            &mut app,
+            RigidBody::Dynamic,
+            Vector::X * x + Vector::Y * 0.5,
+            1.0,
+            1.0,
+        );
+        app.world_mut()
+            .entity_mut(body)
+            .insert((material, LinearVelocity(Vector::X * 3.0)));
+        body
+    };
+    let ice_body = spawn_body(-20.0, ice);
+    let rubber_body = spawn_body(0.0, rubber);
+    let tire_body = spawn_body(20.0, tire);
+
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+
+    assert_eq!(
+        app.world().get::<ColliderDensity>(ice_body),
+        Some(&ColliderDensity(2.0))
+    );
+    let lin_vel = app.world().get::<LinearVelocity>(ice_body).unwrap();
+    assert_relative_eq!(lin_vel.x, 3.0, epsilon = 0.01);
+    let lin_vel = app.world().get::<LinearVelocity>(rubber_body).unwrap();
+    assert!(lin_vel.x.abs() < 0.01);
+    let lin_vel = app.world().get::<LinearVelocity>(tire_body).unwrap();
+    assert_relative_eq!(lin_vel.x, 3.0, epsilon = 0.01);
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    feature = "physics-material",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn child_collider_friction_overrides_rigid_body_friction() {
+    // Child colliders need the collider hierarchy, so all of the plugins are used.
+    let mut app = create_app_with_physics(PhysicsPlugins::default().build());
+
+    // Physics material assets are initialized when the plugins are finished.
+    app.finish();
+
+    let slippery = app
+        .world_mut()
+        .resource_mut::<Assets<PhysicsMaterial>>()
+        .add(PhysicsMaterial::new(
+            Friction::ZERO.with_combine_rule(CoefficientCombine::Min),
+            0.0,
+            1.0,
+        ));
+
+    #[cfg(feature = "2d")]
+    let (ground_shape, box_shape) = (
+        Collider::rectangle(100.0, 1.0),
+        Collider::rectangle(1.0, 1.0),
+    );
+    #[cfg(feature = "3d")]
+    let (ground_shape, box_shape) = (
+        Collider::cuboid(100.0, 1.0, 100.0),
+        Collider::cuboid(1.0, 1.0, 1.0),
+    );
+
+    app.world_mut().spawn((
+        RigidBody::Static,
+        ground_shape,
+        TransformBundle::from_transform(Transform::from_xyz(0.0, -0.5, 0.0)),
+    ));
+
+    // Spawns a rough body with a single child collider.
+    let mut spawn_body = |x: f32, child: Option<Friction>| {
+        let mut body = app.world_mut().spawn((
+            RigidBody::Dynamic,
+            Friction::new(1.0).with_combine_rule(CoefficientCombine::Max),
+            LinearVelocity(Vector::X * 3.0),
+            TransformBundle::from_transform(Transform::from_xyz(x, 0.5, 0.0)),
+        ));
+        body.with_children(|children| {
+            let mut collider = children.spawn((box_shape.clone(), TransformBundle::default()));
+            if let Some(friction) = child {
+                collider.insert(friction);
+            }
+        });
+        body.id()
+    };
+
+    let rough_body = spawn_body(-20.0, None);
+    let slippery_body = spawn_body(
+        0.0,
+        Some(Friction::ZERO.with_combine_rule(CoefficientCombine::Min)),
+    );
+    let slippery_material_body = spawn_body(20.0, None);
+    app.world_mut()
+        .entity_mut(slippery_material_body)
+        .insert(slippery);
+
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+
+    // The child collider of the rough body inherits the friction of the body.
+    let lin_vel = app.world().get::<LinearVelocity>(rough_body).unwrap();
+    assert!(lin_vel.x.abs() < 0.05);
+    // The friction of the child collider overrides the friction of the body.
+    let lin_vel = app.world().get::<LinearVelocity>(slippery_body).unwrap();
+    assert_relative_eq!(lin_vel.x, 3.0, epsilon = 0.01);
+    // The physics material of the body overrides its friction for the child collider too.
+    let lin_vel = app
+        .world()
+        .get::<LinearVelocity>(slippery_material_body)
+        .unwrap();
+    assert_relative_eq!(lin_vel.x, 3.0, epsilon = 0.01);
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn stacked_bodies_sleep_and_wake_as_island() {
+    let mut app = create_app();
+
+    spawn_box(&mut app, RigidBody::Static, Vector::NEG_Y * 0.5, 100.0, 1.0);
+    let stack: Vec<Entity> = (0..3)
+        .map(|i| {
+            spawn_box(
+                &mut app,
+                RigidBody::Dynamic,
+                Vector::Y * (0.5 + i as Scalar),
+                1.0,
+                1.0,
+            )
+        })
+        .collect();
+    let lone_body = spawn_box(&mut app, RigidBody::Dynamic, Vector::X * 10.0, 1.0, 1.0);
+
+    for _ in 0..10 {
+        tick_60_fps(&mut app);
+    }
+
+    // The ground is static, so it doesn't connect the stack to the lone body.
+    let islands = app.world().resource::<PhysicsIslands>();
+    assert!(islands.are_connected(stack[0], stack[2]));
+    assert!(!islands.are_connected(stack[0], lone_body));
+
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+
+    // The whole stack has fallen asleep.
+    assert!(stack
+        .iter()
+        .all(|&body| app.world().get::<Sleeping>(body).is_some()));
+    assert!(
+        app.world()
+            .resource::<PhysicsIslands>()
+            .get(stack[0])
+            .unwrap()
+            .is_sleeping
+    );
+
+    // Pushing the top body wakes up the whole stack.
+    app.world_mut()
+        .entity_mut(stack[2])
+        .insert(LinearVelocity(Vector::X));
+    tick_60_fps(&mut app);
+    tick_60_fps(&mut app);
+
+    assert!(stack
+        .iter()
+        .all(|&body| app.world().get::<Sleeping>(body).is_none()));
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn island_splits_when_contact_ends() {
+    let mut app = create_app();
+
+    spawn_box(&mut app, RigidBody::Static, Vector::NEG_Y * 0.5, 100.0, 1.0);
+    let bottom = spawn_box(&mut app, RigidBody::Dynamic, Vector::Y * 0.5, 1.0, 1.0);
+    let top = spawn_box(&mut app, RigidBody::Dynamic, Vector::Y * 1.5, 1.0, 1.0);
+
+    for _ in 0..10 {
+        tick_60_fps(&mut app);
+    }
+
+    assert!(app
+        .world()
+        .resource::<PhysicsIslands>()
+        .are_connected(bottom, top));
+
+    // Moving the top body away ends the contact, which splits the island.
+    app.world_mut().get_mut::<Position>(top).unwrap().0 = Vector::X * 10.0 + Vector::Y * 0.5;
+    tick_60_fps(&mut app);
+    tick_60_fps(&mut app);
+
+    let islands = app.world().resource::<PhysicsIslands>();
+    assert!(!islands.are_connected(bottom, top));
+    assert_eq!(islands.get(top).unwrap().bodies, vec![top]);
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64"),
+    feature = "parallel",
+    not(feature = "enhanced-determinism")
+))]
+#[test]
+fn contact_constraint_colors_do_not_share_bodies() {
+    use crate::dynamics::solver::{graph_coloring::ContactConstraintColors, ContactConstraints};
+    use bevy::ecs::entity::EntityHashSet;
+
+    let mut app = create_app();
+
+    // A pyramid of boxes, where most of the bodies are in contact with several others.
+    spawn_box(&mut app, RigidBody::Static, Vector::NEG_Y * 0.5, 100.0, 1.0);
+    let mut top_box = Entity::PLACEHOLDER;
+    for row in 0..10 {
+        for i in 0..(10 - row) {
+            let x = i as Scalar - (10 - row) as Scalar * 0.5;
+            let y = 0.5 + row as Scalar;
+            top_box = spawn_box(
+                &mut app,
+                RigidBody::Dynamic,
+                Vector::X * x + Vector::Y * y,
+                1.0,
+                1.0,
+            );
+        }
+    }
+
+    for _ in 0..20 {
+        tick_60_fps(&mut app);
+    }
+
+    let colors = app.world().resource::<ContactConstraintColors>();
+    let constraints = app.world().resource::<ContactConstraints>();
+    assert!(colors.color_counts().len() > 1);
+    assert!(colors.colored_count() <= constraints.len());
+
+    // No two constraints of the same color affect the same dynamic body.
+    let mut start = 0;
+    for &count in colors.color_counts() {
+        let mut bodies = EntityHashSet::default();
+        for constraint in &constraints[start..start + count] {
+            for entity in [constraint.entity1, constraint.entity2] {
+                assert!(colors.is_shared(entity) || bodies.insert(entity));
+            }
+        }
+        start += count;
+    }
+
+    // The pyramid is still standing.
+    let position = app.world().get::<Position>(top_box).unwrap();
+    assert!(position.y > 9.0);
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64"),
+    feature = "parallel",
+    not(feature = "enhanced-determinism")
+))]
+#[test]
+fn bodies_on_shared_ground_are_all_colored() {
+    use crate::dynamics::solver::{
+        graph_coloring::{ContactConstraintColors, MAX_COLORS},
+        ContactConstraints,
+    };
+
+    let mut app = create_app();
+
+    // More boxes than there are colors, all resting on the same static ground.
+    let ground = spawn_box(&mut app, RigidBody::Static, Vector::NEG_Y * 0.5, 100.0, 1.0);
+    for i in 0..2 * MAX_COLORS {
+        let x = (i as Scalar - MAX_COLORS as Scalar) * 2.0;
+        spawn_box(
+            &mut app,
+            RigidBody::Dynamic,
+            Vector::X * x + Vector::Y * 0.5,
+            1.0,
+            1.0,
+        );
+    }
+
+    for _ in 0..5 {
+        tick_60_fps(&mut app);
+    }
+
+    // The ground is shared, so all of the constraints fit in a single color.
+    let colors = app.world().resource::<ContactConstraintColors>();
+    let constraints = app.world().resource::<ContactConstraints>();
+    assert_eq!(constraints.len(), 2 * MAX_COLORS);
+    assert!(colors.is_shared(ground));
+    assert_eq!(colors.colored_count(), constraints.len());
+    assert_eq!(colors.color_counts(), &[2 * MAX_COLORS]);
+}
+
+#[derive(Component, Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Ord)]
+#[cfg(feature = "3d")]
+struct Id(usize);
+
+#[cfg(all(feature = "3d", feature = "enhanced-determinism"))]
+#[test]
+fn cubes_simulation_is_deterministic_across_machines() {
+    setup_insta!();
+    let mut app = create_app();
+
+    app.add_systems(Startup, setup_cubes_simulation);
+
+    const SECONDS: usize = 10;
+    const UPDATES: usize = 60 * SECONDS;
+
+    for _ in 0..UPDATES {
+        tick_60_fps(&mut app);
+    }
+
+    let mut app_query = app.world_mut().query::<(&Id, &Transform)>();
+
+    let mut bodies: Vec<(&Id, &Transform)> = app_query.iter(app.world()).collect();
+    bodies.sort_by_key(|b| b.0);
+
+    assert_debug_snapshot!(bodies);
+}
+
+#[cfg(all(feature = "3d", feature = "default-collider"))]
+#[test]
+fn cubes_simulation_is_locally_deterministic() {
+    use itertools::Itertools;
+
+    fn run_cubes() -> Vec<(Id, Transform)> {
+        let mut app = create_app();
+
+        app.add_systems(Startup, setup_cubes_simulation);
+
+        const SECONDS: usize = 5;
+        const UPDATES: usize = 60 * SECONDS;
+
+        for _ in 0..UPDATES {
+            tick_60_fps(&mut app);
+        }
+
+        let mut app_query = app.world_mut().query::<(&Id, &Transform)>();
+
+        let mut bodies: Vec<(Id, Transform)> = app_query
+            .iter(app.world())
+            .map(|(id, transform)| (*id, *transform))
+            .collect();
+        bodies.sort_by_key(|b| b.0);
+        bodies
+    }
+
+    // run simulation and check that results are equal each time
+    for (a, b) in (0..4).map(|_| run_cubes()).tuple_windows() {
+        assert_eq!(a, b);
+    }
+}
+
+#[test]
+fn no_ambiguity_errors() {
+    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct DeterministicSchedule;
+
+    App::new()
+        .add_plugins((
+            MinimalPlugins,
+            PhysicsPlugins::new(DeterministicSchedule)
+                .build()
+                .disable::<ColliderHierarchyPlugin>(),
+            bevy::asset::AssetPlugin::default(),
+            #[cfg(feature = "bevy_scene")]
+            bevy::scene::ScenePlugin,
+        ))
+        .init_resource::<Assets<Mesh>>()
+        .edit_schedule(DeterministicSchedule, |s| {
+            s.set_build_settings(ScheduleBuildSettings {
+                ambiguity_detection: LogLevel::Error,
+                ..default()
+            });
+        })
+        .add_systems(Update, |w: &mut World| {
+            w.run_schedule(DeterministicSchedule);
+        })
+        .update();
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn broad_phase_tree_matches_sweep_and_prune() {
+    // Runs a scene of moving sensors and returns the sorted broad phase pairs for each frame.
+    // Sensors are used so that the motion doesn't depend on the order in which contacts are solved.
+    fn collect_pairs(method: BroadPhaseMethod) -> Vec<Vec<(Entity, Entity)>> {
+        let mut app = create_app();
+        app.insert_resource(Gravity::ZERO);
+        app.insert_resource(BroadPhaseConfig {
+            method,
+            ..default()
+        });
+
+        // A static floor that the bodies move into.
+        for i in 0..4 {
+            app.world_mut().spawn((
+                RigidBody::Static,
+                #[cfg(feature = "2d")]
+                Collider::rectangle(2.0, 0.5),
+                #[cfg(feature = "3d")]
+                Collider::cuboid(2.0, 0.5, 2.0),
+                Position(Vector::X * i as Scalar * 2.0 - Vector::Y),
+            ));
+        }
+
+        // A grid of overlapping bodies moving in different directions.
+        for i in 0..6 {
+            for j in 0..6 {
+                let offset = (i * 7 + j * 3) % 5;
+                app.world_mut().spawn((
+                    RigidBody::Dynamic,
+                    #[cfg(feature = "2d")]
+                    Collider::rectangle(1.0, 1.0),
+                    #[cfg(feature = "3d")]
+                    Collider::cuboid(1.0, 1.0, 1.0),
+                    Sensor,
+                    SleepingDisabled,
+                    Position(Vector::X * i as Scalar * 0.9 + Vector::Y * j as Scalar * 0.9),
+                    LinearVelocity(
+                        Vector::X * (offset as Scalar - 2.0)
+                            - Vector::Y * ((offset as Scalar - 2.0) * 0.5 + 1.0),
+                    ),
+                ));
+            }
+        }
+
+        (0..60)
+            .map(|_| {
+                tick_60_fps(&mut app);
+                let mut pairs = app.world().resource::<BroadCollisionPairs>().0.clone();
+                pairs.sort();
+                pairs
+            })
+            .collect()
+    }
+
+    let sweep_and_prune = collect_pairs(BroadPhaseMethod::SweepAndPrune);
+    let tree = collect_pairs(BroadPhaseMethod::DynamicAabbTree);
+
+    assert!(sweep_and_prune.iter().all(|pairs| !pairs.is_empty()));
+    for (frame, (expected, actual)) in sweep_and_prune.iter().zip(&tree).enumerate() {
+        assert_eq!(expected, actual, "pairs differ in frame {frame}");
+    }
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn spatial_queries_find_colliders() {
+    use bevy::ecs::system::RunSystemOnce;
+
+    #[cfg(feature = "2d")]
+    let (ball_collider, box_collider) = (Collider::circle(0.5), Collider::rectangle(1.0, 1.0));
+    #[cfg(feature = "3d")]
+    let (ball_collider, box_collider) = (Collider::sphere(0.5), Collider::cuboid(1.0, 1.0, 1.0));
+
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    let wall = app
+        .world_mut()
+        .spawn((
+            RigidBody::Static,
+            box_collider.clone(),
+            Position(Vector::X * 5.0),
+        ))
+        .id();
+    let ball = app
+        .world_mut()
+        .spawn((
+            RigidBody::Dynamic,
+            ball_collider.clone(),
+            Position(Vector::Y * 5.0),
+        ))
+        .id();
+
+    tick_60_fps(&mut app);
+
+    let (ray_hit, shape_hit, point_intersections, shape_intersections, aabb_intersections) = app
+        .world_mut()
+        .run_system_once(move |spatial_query: SpatialQuery| {
+            (
+                spatial_query.cast_ray(
+                    Vector::ZERO,
+                    Dir::X,
+                    100.0,
+                    true,
+                    SpatialQueryFilter::default(),
+                ),
+                spatial_query.cast_shape(
+                    &ball_collider,
+                    Vector::ZERO,
+                    default(),
+                    Dir::Y,
+                    100.0,
+                    true,
+                    SpatialQueryFilter::default(),
+                ),
+                spatial_query.point_intersections(Vector::X * 5.0, SpatialQueryFilter::default()),
+                spatial_query.shape_intersections(
+                    &box_collider,
+                    Vector::Y * 4.0,
+                    default(),
+                    SpatialQueryFilter::default(),
+                ),
+                spatial_query.aabb_intersections_with_aabb(ColliderAabb::new(
+                    Vector::ZERO,
+                    Vector::splat(10.0),
+                )),
+            )
+        });
+
+    let ray_hit = ray_hit.expect("the ray should hit the wall");
+    assert_eq!(ray_hit.entity, wall);
+    assert_relative_eq!(ray_hit.time_of_impact, 4.5, epsilon = 0.001);
+
+    let shape_hit = shape_hit.expect("the shape cast should hit the ball");
+    assert_eq!(shape_hit.entity, ball);
+    assert_relative_eq!(shape_hit.time_of_impact, 4.0, epsilon = 0.001);
+
+    assert_eq!(point_intersections, vec![wall]);
+    assert_eq!(shape_intersections, vec![ball]);
+
+    let mut aabb_intersections = aabb_intersections;
+    aabb_intersections.sort();
+    assert_eq!(aabb_intersections, vec![wall, ball]);
+
+    // Move the wall and remove the ball. The queries should reflect the changes after the next step.
+    app.world_mut().get_mut::<Position>(wall).unwrap().0 = Vector::NEG_X * 5.0;
+    app.world_mut().despawn(ball);
+
+    tick_60_fps(&mut app);
+
+    let (hit_right, hit_left, aabb_intersections) =
+        app.world_mut()
+            .run_system_once(|spatial_query: SpatialQuery| {
+                let filter = SpatialQueryFilter::default();
+                (
+                    spatial_query.cast_ray(Vector::ZERO, Dir::X, 100.0, true, filter.clone()),
+                    spatial_query.cast_ray(Vector::ZERO, Dir::NEG_X, 100.0, true, filter),
+                    spatial_query.aabb_intersections_with_aabb(ColliderAabb::new(
+                        Vector::ZERO,
+                        Vector::splat(10.0),
+                    )),
+                )
+            });
+
+    assert!(hit_right.is_none());
+    assert_eq!(hit_left.map(|hit| hit.entity), Some(wall));
+    assert_eq!(aabb_intersections, vec![wall]);
 }
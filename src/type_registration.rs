@@ -1,10 +1,13 @@
 use crate::{
     prelude::*,
-    sync::{ancestor_marker::AncestorMarker, PreviousGlobalTransform, SyncConfig},
+    sync::{
+        ancestor_marker::AncestorMarker, interpolation::InterpolationState,
+        PreviousGlobalTransform, SyncConfig,
+    },
 };
 use bevy::prelude::*;
 use broad_phase::AabbIntersections;
-use dynamics::solver::SolverConfig;
+use dynamics::solver::{JointSolverMethod, SolverConfig};
 
 /// Registers physics types to the `TypeRegistry` resource in `bevy_reflect`.
 pub struct PhysicsTypeRegistrationPlugin;
@@ -15,6 +18,8 @@ impl Plugin for PhysicsTypeRegistrationPlugin {
             .register_type::<Time<Substeps>>()
             .register_type::<SubstepCount>()
             .register_type::<BroadCollisionPairs>()
+            .register_type::<BroadPhaseConfig>()
+            .register_type::<BroadPhaseMethod>()
             .register_type::<AabbIntersections>()
             .register_type::<SleepingThreshold>()
             .register_type::<DeactivationTime>()
@@ -35,6 +40,8 @@ impl Plugin for PhysicsTypeRegistrationPlugin {
             .register_type::<PreSolveAngularVelocity>()
             .register_type::<Restitution>()
             .register_type::<Friction>()
+            .register_type::<AnisotropicFriction>()
+            .register_type::<SurfaceVelocity>()
             .register_type::<LinearDamping>()
             .register_type::<AngularDamping>()
             .register_type::<ExternalForce>()
@@ -42,6 +49,10 @@ impl Plugin for PhysicsTypeRegistrationPlugin {
             .register_type::<ExternalImpulse>()
             .register_type::<ExternalAngularImpulse>()
             .register_type::<GravityScale>()
+            .register_type::<GravityOverride>()
+            .register_type::<LocalGravity>()
+            .register_type::<RadialGravity>()
+            .register_type::<GravityZone>()
             .register_type::<Mass>()
             .register_type::<InverseMass>()
             .register_type::<Inertia>()
@@ -62,16 +73,25 @@ impl Plugin for PhysicsTypeRegistrationPlugin {
             .register_type::<SpeculativeMargin>()
             .register_type::<SweptCcd>()
             .register_type::<CollisionMargin>()
+            .register_type::<ActiveCollisionHooks>()
             .register_type::<NarrowPhaseConfig>()
             .register_type::<SolverConfig>()
+            .register_type::<JointSolverMethod>()
             .register_type::<SyncConfig>()
+            .register_type::<TransformInterpolation>()
+            .register_type::<TransformExtrapolation>()
+            .register_type::<InterpolationState>()
             .register_type::<AncestorMarker<RigidBody>>()
             .register_type::<AncestorMarker<ColliderMarker>>()
             .register_type::<RayCaster>()
             .register_type::<DistanceJoint>()
             .register_type::<FixedJoint>()
+            .register_type::<GenericJoint>()
             .register_type::<PrismaticJoint>()
-            .register_type::<RevoluteJoint>();
+            .register_type::<RevoluteJoint>()
+            .register_type::<BreakableJoint>()
+            .register_type::<JointForces>()
+            .register_type::<JointDisabled>();
 
         #[cfg(feature = "default-collider")]
         app.register_type::<ColliderConstructor>()